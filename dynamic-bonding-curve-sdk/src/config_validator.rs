@@ -0,0 +1,15 @@
+use dynamic_bonding_curve::{params::config_violations::ConfigViolations, ConfigParameters};
+
+pub use dynamic_bonding_curve::params::config_violations::ConfigViolation;
+
+/// Run the checks of create_config on the config parameters and return every violation instead of the first error.
+/// An empty list means that the parameters pass ConfigParameters::validate_parameters. The quote mint isn't checked as it's an account of the instruction,
+/// and neither is the token supply, that depends on the amounts computed from the curve
+pub fn validate_config_parameters(
+    config_parameters: &ConfigParameters,
+    current_timestamp: u64,
+) -> Vec<ConfigViolation> {
+    let mut violations = ConfigViolations::default();
+    config_parameters.check_parameters(&mut violations, current_timestamp);
+    violations.into_violations()
+}
@@ -0,0 +1,400 @@
+use anyhow::{ensure, Context, Result};
+use dynamic_bonding_curve::{
+    constants::{MAX_CURVE_POINT, MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    curve::get_delta_amount_quote_unsigned_256,
+    migration_handler::{get_migration_handler, MigratedCollectFeeMode},
+    params::{
+        fee_parameters::PoolFeeParameters,
+        liquidity_distribution::{
+            get_base_token_for_swap, get_migration_threshold_price, LiquidityDistributionParameters,
+        },
+    },
+    price_curve::PriceCurve,
+    state::{CurveType, MigrationOption, PoolConfig},
+    u128x128_math::Rounding,
+    ConfigParameters, LiquidityVestingInfoParams, LockedVestingParams, MigratedPoolFee,
+    MigratedPoolMarketCapFeeSchedulerParams, MigrationFee, TokenSupplyParams,
+};
+
+/// Shape of the curve between the initial and the migration market cap
+#[derive(Debug, Clone, PartialEq)]
+pub enum CurveShape {
+    /// A single constant product segment
+    ConstantProduct,
+    /// Price grows linearly with the base token sold, on the linear price curve of the program
+    LinearPrice,
+    /// Constant product segments split at the given market caps, the liquidity of each segment is scaled by its weight.
+    /// `market_caps` must be increasing and between the initial and the migration market cap, and there must be one more weight than market caps
+    MultiSegment {
+        market_caps: Vec<f64>,
+        liquidity_weights: Vec<f64>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct CurveDesignParameters {
+    /// total token supply, including decimals. The supply that isn't needed by the curve, the migration and the locked vesting goes to the leftover receiver
+    pub total_token_supply: u64,
+    pub token_base_decimal: u8,
+    pub token_quote_decimal: u8,
+    /// market cap at the start of the curve, in quote token without decimals
+    pub initial_market_cap: f64,
+    /// market cap when the pool reaches the migration quote threshold, in quote token without decimals
+    pub migration_market_cap: f64,
+    /// percentage of the total token supply sold on the curve
+    pub percentage_supply_on_curve: u8,
+    pub curve_shape: CurveShape,
+    pub migration_option: u8,
+    pub migration_fee: MigrationFee,
+    pub locked_vesting: LockedVestingParams,
+    pub pool_fees: PoolFeeParameters,
+    pub collect_fee_mode: u8,
+    pub activation_type: u8,
+    pub creator_trading_fee_percentage: u8,
+}
+
+/// Build the config parameters of a curve from market caps, the other fields are defaulted the same way as the typescript designCurve helper.
+/// The curve is checked against the program rules and must fit in the total token supply
+pub fn design_curve(params: &CurveDesignParameters) -> Result<ConfigParameters> {
+    let CurveDesignParameters {
+        total_token_supply,
+        token_base_decimal,
+        token_quote_decimal,
+        initial_market_cap,
+        migration_market_cap,
+        percentage_supply_on_curve,
+        ref curve_shape,
+        migration_option,
+        migration_fee,
+        locked_vesting,
+        pool_fees,
+        collect_fee_mode,
+        activation_type,
+        creator_trading_fee_percentage,
+    } = *params;
+
+    ensure!(total_token_supply > 0, "total token supply is zero");
+    ensure!(
+        initial_market_cap.is_finite()
+            && initial_market_cap > 0.0
+            && migration_market_cap.is_finite()
+            && migration_market_cap > initial_market_cap,
+        "invalid market caps"
+    );
+    ensure!(
+        percentage_supply_on_curve > 0 && percentage_supply_on_curve < 100,
+        "invalid percentage supply on curve"
+    );
+
+    let market_caps =
+        get_segment_market_caps(initial_market_cap, migration_market_cap, curve_shape)?;
+    let sqrt_prices = market_caps
+        .iter()
+        .map(|&market_cap| {
+            get_sqrt_price_from_market_cap(market_cap, total_token_supply, token_quote_decimal)
+        })
+        .collect::<Result<Vec<u128>>>()?;
+    ensure!(
+        sqrt_prices.windows(2).all(|prices| prices[0] < prices[1]),
+        "curve segments are too narrow"
+    );
+    let swap_base_amount_target =
+        u128::from(total_token_supply) * u128::from(percentage_supply_on_curve) / 100;
+
+    let (curve_type, curve, migration_quote_threshold) = match curve_shape {
+        CurveShape::LinearPrice => {
+            let (curve, migration_quote_threshold) = design_price_curve(
+                CurveType::LinearPrice,
+                &sqrt_prices,
+                swap_base_amount_target,
+            )?;
+            (CurveType::LinearPrice, curve, migration_quote_threshold)
+        }
+        _ => {
+            let (curve, migration_quote_threshold) =
+                design_constant_product_curve(&sqrt_prices, curve_shape, swap_base_amount_target)?;
+            (CurveType::ConstantProduct, curve, migration_quote_threshold)
+        }
+    };
+
+    let config_parameters = ConfigParameters {
+        pool_fees,
+        collect_fee_mode,
+        migration_option,
+        activation_type,
+        token_type: 0, // spl token
+        token_decimal: token_base_decimal,
+        partner_liquidity_percentage: 0,
+        partner_permanent_locked_liquidity_percentage: 100,
+        creator_liquidity_percentage: 0,
+        creator_permanent_locked_liquidity_percentage: 0,
+        migration_quote_threshold,
+        sqrt_start_price: sqrt_prices[0],
+        locked_vesting,
+        migration_fee_option: 0,
+        token_supply: Some(TokenSupplyParams {
+            pre_migration_token_supply: total_token_supply,
+            post_migration_token_supply: total_token_supply,
+        }),
+        creator_trading_fee_percentage,
+        token_update_authority: 0,
+        migration_fee,
+        migrated_pool_fee: MigratedPoolFee::default(),
+        pool_creation_fee: 0,
+        partner_liquidity_vesting_info: LiquidityVestingInfoParams::default(),
+        creator_liquidity_vesting_info: LiquidityVestingInfoParams::default(),
+        migrated_pool_base_fee_mode: 0,
+        migrated_pool_market_cap_fee_scheduler_params:
+            MigratedPoolMarketCapFeeSchedulerParams::default(),
+        enable_first_swap_with_min_fee: false,
+        compounding_fee_bps: 0,
+        max_buy_amount_per_wallet: 0,
+        buy_cap_duration: 0,
+        allowlist_duration: 0,
+        allowlist_merkle_root: [0; 32],
+        fee_steps: vec![],
+        curve_type: curve_type.into(),
+        enable_creator_allowlist: false,
+        buyback_fee_source: 0,
+        buyback_max_quote_amount_per_period: 0,
+        buyback_period: 0,
+        curve,
+    };
+
+    validate_curve_design(&config_parameters)?;
+
+    Ok(config_parameters)
+}
+
+// constant product segments between the sqrt prices, scaled so that the base amount sold on the curve is swap_base_amount_target
+fn design_constant_product_curve(
+    sqrt_prices: &[u128],
+    curve_shape: &CurveShape,
+    swap_base_amount_target: u128,
+) -> Result<(Vec<LiquidityDistributionParameters>, u64)> {
+    let liquidity_weights = get_liquidity_weights(curve_shape)?;
+
+    // base amount sold on each segment is liquidity * (1 / lower_sqrt_price - 1 / upper_sqrt_price)
+    let base_amount_per_liquidity: f64 = sqrt_prices
+        .windows(2)
+        .zip(liquidity_weights.iter())
+        .map(|(sqrt_prices, weight)| {
+            weight * (1.0 / sqrt_prices[0] as f64 - 1.0 / sqrt_prices[1] as f64)
+        })
+        .sum();
+    let liquidity_scale = swap_base_amount_target as f64 / base_amount_per_liquidity;
+
+    let curve = sqrt_prices[1..]
+        .iter()
+        .zip(liquidity_weights.iter())
+        .map(|(&sqrt_price, weight)| LiquidityDistributionParameters {
+            sqrt_price,
+            liquidity: (liquidity_scale * weight) as u128,
+        })
+        .collect::<Vec<_>>();
+    ensure!(
+        curve.iter().all(|point| point.liquidity > 0),
+        "curve liquidity is zero"
+    );
+
+    // round down, so the migration price never goes over the end of the curve
+    let mut migration_quote_threshold = 0u64;
+    for (sqrt_prices, point) in sqrt_prices.windows(2).zip(curve.iter()) {
+        let quote_amount: u64 = get_delta_amount_quote_unsigned_256(
+            sqrt_prices[0],
+            sqrt_prices[1],
+            point.liquidity,
+            Rounding::Down,
+        )?
+        .try_into()
+        .ok()
+        .context("migration quote threshold overflow")?;
+        migration_quote_threshold = migration_quote_threshold
+            .checked_add(quote_amount)
+            .context("migration quote threshold overflow")?;
+    }
+
+    Ok((curve, migration_quote_threshold))
+}
+
+// a single curve point holding the end price and the base amount sold on the curve
+fn design_price_curve(
+    curve_type: CurveType,
+    sqrt_prices: &[u128],
+    swap_base_amount_target: u128,
+) -> Result<(Vec<LiquidityDistributionParameters>, u64)> {
+    let sqrt_start_price = sqrt_prices[0];
+    let end_sqrt_price = sqrt_prices[sqrt_prices.len() - 1];
+    let price_curve = PriceCurve::new(
+        curve_type,
+        sqrt_start_price,
+        end_sqrt_price,
+        swap_base_amount_target,
+    )?;
+
+    // round down, so the migration price never goes over the end of the curve
+    let migration_quote_threshold: u64 = price_curve
+        .get_delta_amount_quote(sqrt_start_price, end_sqrt_price, Rounding::Down)?
+        .try_into()
+        .ok()
+        .context("migration quote threshold overflow")?;
+
+    let curve = vec![LiquidityDistributionParameters {
+        sqrt_price: end_sqrt_price,
+        liquidity: swap_base_amount_target,
+    }];
+    Ok((curve, migration_quote_threshold))
+}
+
+// same checks as create_config, the curve must also fit in the token supply
+fn validate_curve_design(config_parameters: &ConfigParameters) -> Result<()> {
+    // the designed config has no liquidity vesting, the only check that depends on the current timestamp
+    config_parameters.validate_parameters(0)?;
+
+    let ConfigParameters {
+        migration_quote_threshold,
+        sqrt_start_price,
+        ref curve,
+        ..
+    } = *config_parameters;
+    let price_curve = config_parameters.get_price_curve()?;
+
+    let migration_sqrt_price = match price_curve.as_ref() {
+        Some(price_curve) => price_curve.get_migration_sqrt_price(migration_quote_threshold)?,
+        None => get_migration_threshold_price(migration_quote_threshold, sqrt_start_price, curve)?,
+    };
+    ensure!(
+        migration_sqrt_price < MAX_SQRT_PRICE,
+        "migration price is over the max price"
+    );
+    let swap_base_amount: u64 = match price_curve.as_ref() {
+        Some(price_curve) => price_curve.get_delta_amount_base(
+            sqrt_start_price,
+            migration_sqrt_price,
+            Rounding::Up,
+        )?,
+        None => get_base_token_for_swap(sqrt_start_price, migration_sqrt_price, curve)?,
+    }
+    .try_into()
+    .ok()
+    .context("swap base amount overflow")?;
+
+    let migration_option = MigrationOption::try_from(config_parameters.migration_option)
+        .ok()
+        .context("invalid migration option")?;
+    let migrated_collect_fee_mode =
+        MigratedCollectFeeMode::try_from(config_parameters.migrated_pool_fee.collect_fee_mode)
+            .ok()
+            .context("invalid migrated collect fee mode")?;
+    let (migration_base_threshold, _) = get_migration_handler(
+        migration_option,
+        migrated_collect_fee_mode,
+        migration_sqrt_price,
+    )
+    .get_included_protocol_fee_migration_amounts_1(
+        migration_quote_threshold,
+        config_parameters.migration_fee.fee_percentage,
+    )?;
+    ensure!(
+        swap_base_amount > 0 && migration_base_threshold > 0,
+        "curve has no base token to swap or migrate"
+    );
+
+    // the required supply of a config without fixed token supply, with the swap buffer
+    let swap_base_amount_buffer = match price_curve.as_ref() {
+        Some(price_curve) => {
+            PoolConfig::get_swap_amount_with_buffer_on_price_curve(swap_base_amount, price_curve)?
+        }
+        None => PoolConfig::get_swap_amount_with_buffer(swap_base_amount, sqrt_start_price, curve)?,
+    };
+    let initial_base_supply = PoolConfig::get_total_token_supply(
+        swap_base_amount_buffer,
+        migration_base_threshold,
+        &config_parameters.locked_vesting,
+    )?;
+
+    if let Some(TokenSupplyParams {
+        pre_migration_token_supply,
+        post_migration_token_supply,
+    }) = config_parameters.token_supply
+    {
+        let minimum_base_supply = PoolConfig::get_total_token_supply(
+            swap_base_amount,
+            migration_base_threshold,
+            &config_parameters.locked_vesting,
+        )?;
+        ensure!(
+            minimum_base_supply <= post_migration_token_supply
+                && post_migration_token_supply <= pre_migration_token_supply
+                && initial_base_supply <= pre_migration_token_supply,
+            "token supply is smaller than the supply required by the curve"
+        );
+    }
+
+    Ok(())
+}
+
+fn get_segment_market_caps(
+    initial_market_cap: f64,
+    migration_market_cap: f64,
+    curve_shape: &CurveShape,
+) -> Result<Vec<f64>> {
+    let market_caps = match curve_shape {
+        CurveShape::ConstantProduct | CurveShape::LinearPrice => {
+            vec![initial_market_cap, migration_market_cap]
+        }
+        CurveShape::MultiSegment {
+            market_caps,
+            liquidity_weights,
+        } => {
+            ensure!(
+                liquidity_weights.len() == market_caps.len() + 1
+                    && liquidity_weights.len() <= MAX_CURVE_POINT,
+                "invalid number of curve segments"
+            );
+            let mut segment_market_caps = Vec::with_capacity(market_caps.len() + 2);
+            segment_market_caps.push(initial_market_cap);
+            segment_market_caps.extend_from_slice(market_caps);
+            segment_market_caps.push(migration_market_cap);
+            ensure!(
+                segment_market_caps.windows(2).all(|caps| caps[0] < caps[1]),
+                "segment market caps must be increasing"
+            );
+            segment_market_caps
+        }
+    };
+    Ok(market_caps)
+}
+
+fn get_liquidity_weights(curve_shape: &CurveShape) -> Result<Vec<f64>> {
+    let liquidity_weights = match curve_shape {
+        CurveShape::ConstantProduct | CurveShape::LinearPrice => vec![1.0],
+        CurveShape::MultiSegment {
+            liquidity_weights, ..
+        } => {
+            ensure!(
+                liquidity_weights
+                    .iter()
+                    .all(|weight| weight.is_finite() && *weight > 0.0),
+                "liquidity weights must be positive"
+            );
+            liquidity_weights.clone()
+        }
+    };
+    Ok(liquidity_weights)
+}
+
+/// Sqrt price in Q64 of a market cap, the price being the quote amount of one base token, with decimals
+pub fn get_sqrt_price_from_market_cap(
+    market_cap: f64,
+    total_token_supply: u64,
+    token_quote_decimal: u8,
+) -> Result<u128> {
+    let price = market_cap * 10f64.powi(token_quote_decimal.into()) / total_token_supply as f64;
+    let sqrt_price = (price.sqrt() * 2f64.powi(64)) as u128;
+    ensure!(
+        (MIN_SQRT_PRICE..MAX_SQRT_PRICE).contains(&sqrt_price),
+        "market cap is out of the price range"
+    );
+    Ok(sqrt_price)
+}
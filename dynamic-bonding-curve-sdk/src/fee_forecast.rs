@@ -0,0 +1,166 @@
+use anyhow::{ensure, Context, Result};
+use dynamic_bonding_curve::{
+    activation_handler::ActivationType,
+    constants::fee::MAX_FEE_NUMERATOR,
+    params::swap::TradeDirection,
+    state::{PoolConfig, PoolConfigExtension, VirtualPool},
+};
+
+/// Estimated slot duration, used to convert slots to seconds for the dynamic fee of pools activated by slot
+pub const ESTIMATED_SLOT_DURATION_MS: u64 = 400;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct FeeForecast {
+    /// point (slot or timestamp, depends on the activation type of the pool) of the forecast
+    pub point: u64,
+    pub base_fee_numerator: u64,
+    pub variable_fee_numerator: u64,
+    /// base fee + variable fee, capped at MAX_FEE_NUMERATOR
+    pub total_fee_numerator: u64,
+}
+
+/// Base fee numerator of a swap at the given point, assuming the pool price doesn't move until then.
+/// For rate limiter, `amount` is the quote amount of the swap, for other base fee modes it's ignored
+pub fn get_base_fee_numerator(
+    pool: &VirtualPool,
+    config: &PoolConfig,
+    config_extension: Option<&PoolConfigExtension>, // Required for sells when the config has a sell base fee, and when the base fee is a fee step table
+    swap_base_for_quote: bool,
+    point: u64,
+    amount: u64,
+) -> Result<u64> {
+    ensure!(
+        point >= pool.activation_point,
+        "point is before activation point"
+    );
+
+    let trade_direction = if swap_base_for_quote {
+        TradeDirection::BaseToQuote
+    } else {
+        TradeDirection::QuoteToBase
+    };
+    let fee_config = config.get_fee_config(
+        trade_direction,
+        config_extension,
+        point,
+        pool.activation_point,
+    )?;
+    let config = fee_config.as_deref().unwrap_or(config);
+
+    let base_fee_numerator = config
+        .pool_fees
+        .base_fee
+        .get_base_fee_handler()?
+        .get_base_fee_numerator_from_included_fee_amount(
+            point,
+            pool.activation_point,
+            trade_direction,
+            amount,
+            config.sqrt_start_price,
+            pool.sqrt_price,
+        )?;
+
+    Ok(base_fee_numerator)
+}
+
+/// Variable fee numerator after the volatility of the pool decays until the given timestamp, assuming the price doesn't move until then.
+/// That is the variable fee that the swaps pay once a swap happened at the timestamp, a swap at the timestamp itself still pays the variable fee of the last swap
+pub fn get_variable_fee_numerator(
+    pool: &VirtualPool,
+    config: &PoolConfig,
+    timestamp: u64,
+) -> Result<u64> {
+    let dynamic_fee = &config.pool_fees.dynamic_fee;
+    if !dynamic_fee.is_dynamic_fee_enable() {
+        return Ok(0);
+    }
+
+    let mut volatility_tracker = pool.volatility_tracker;
+    volatility_tracker.update_references(dynamic_fee, pool.sqrt_price, timestamp)?;
+    volatility_tracker.update_volatility_accumulator(dynamic_fee, pool.sqrt_price)?;
+
+    let variable_fee_numerator = dynamic_fee
+        .get_variable_fee_numerator(&volatility_tracker)?
+        .min(MAX_FEE_NUMERATOR.into());
+
+    Ok(variable_fee_numerator.try_into()?)
+}
+
+/// Forecast the fee of a swap at the given point. For pools activated by slot, the timestamp of the point is estimated from the current slot and timestamp
+pub fn get_fee_forecast(
+    pool: &VirtualPool,
+    config: &PoolConfig,
+    config_extension: Option<&PoolConfigExtension>,
+    swap_base_for_quote: bool,
+    current_timestamp: u64,
+    current_slot: u64,
+    point: u64,
+    amount: u64,
+) -> Result<FeeForecast> {
+    let base_fee_numerator = get_base_fee_numerator(
+        pool,
+        config,
+        config_extension,
+        swap_base_for_quote,
+        point,
+        amount,
+    )?;
+
+    let activation_type =
+        ActivationType::try_from(config.activation_type).context("invalid activation type")?;
+    let timestamp = match activation_type {
+        ActivationType::Slot => {
+            point
+                .saturating_sub(current_slot)
+                .saturating_mul(ESTIMATED_SLOT_DURATION_MS)
+                / 1000
+                + current_timestamp
+        }
+        ActivationType::Timestamp => point,
+    };
+    let variable_fee_numerator = get_variable_fee_numerator(pool, config, timestamp)?;
+
+    let total_fee_numerator = base_fee_numerator
+        .saturating_add(variable_fee_numerator)
+        .min(MAX_FEE_NUMERATOR);
+
+    Ok(FeeForecast {
+        point,
+        base_fee_numerator,
+        variable_fee_numerator,
+        total_fee_numerator,
+    })
+}
+
+/// Forecast the fee of a swap at every `step` points from `start_point` to `end_point` (inclusive)
+pub fn get_fee_curve(
+    pool: &VirtualPool,
+    config: &PoolConfig,
+    config_extension: Option<&PoolConfigExtension>,
+    swap_base_for_quote: bool,
+    current_timestamp: u64,
+    current_slot: u64,
+    start_point: u64,
+    end_point: u64,
+    step: u64,
+    amount: u64,
+) -> Result<Vec<FeeForecast>> {
+    ensure!(step > 0, "step is zero");
+    ensure!(start_point <= end_point, "invalid point range");
+
+    (start_point..=end_point)
+        .step_by(step.try_into()?)
+        .map(|point| {
+            get_fee_forecast(
+                pool,
+                config,
+                config_extension,
+                swap_base_for_quote,
+                current_timestamp,
+                current_slot,
+                point,
+                amount,
+            )
+        })
+        .collect()
+}
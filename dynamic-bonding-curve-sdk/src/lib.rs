@@ -1,3 +1,6 @@
+pub mod config_validator;
+pub mod curve_designer;
+pub mod fee_forecast;
 pub mod quote_exact_in;
 pub mod quote_exact_out;
 pub mod quote_partial_fill;
@@ -2,12 +2,13 @@ use anyhow::{ensure, Context, Result};
 use dynamic_bonding_curve::{
     activation_handler::ActivationType,
     params::swap::TradeDirection,
-    state::{fee::FeeMode, PoolConfig, SwapResult2, VirtualPool},
+    state::{fee::FeeMode, PoolConfig, PoolConfigExtension, SwapResult2, VirtualPool},
 };
 
 pub fn quote_exact_out(
     pool: &VirtualPool,
     config: &PoolConfig,
+    config_extension: Option<&PoolConfigExtension>, // Required for sells when the config has a sell base fee, and when the base fee is a fee step table
     swap_base_for_quote: bool,
     current_timestamp: u64,
     current_slot: u64,
@@ -19,6 +20,11 @@ pub fn quote_exact_out(
         "virtual pool is completed"
     );
 
+    ensure!(
+        !pool.is_circuit_breaker_triggered(config, current_timestamp)?,
+        "circuit breaker is triggered"
+    );
+
     ensure!(out_amount > 0, "amount is zero");
 
     let activation_type =
@@ -34,6 +40,14 @@ pub fn quote_exact_out(
         TradeDirection::QuoteToBase
     };
 
+    let fee_config = config.get_fee_config(
+        trade_direction,
+        config_extension,
+        current_point,
+        pool.activation_point,
+    )?;
+    let config = fee_config.as_deref().unwrap_or(config);
+
     let fee_mode = &FeeMode::get_fee_mode(config.collect_fee_mode, trade_direction, false)?;
 
     let swap_result = pool.get_swap_result_from_exact_output(
@@ -2,24 +2,31 @@ use anyhow::{ensure, Context, Result};
 use dynamic_bonding_curve::{
     activation_handler::ActivationType,
     params::swap::TradeDirection,
-    state::{fee::FeeMode, PoolConfig, SwapResult2, VirtualPool},
+    state::{fee::FeeMode, PoolConfig, PoolConfigExtension, SwapResult2, VirtualPool},
 };
 
 pub fn quote_partial_fill(
     pool: &VirtualPool,
     config: &PoolConfig,
+    config_extension: Option<&PoolConfigExtension>, // Required for sells when the config has a sell base fee, and when the base fee is a fee step table
     swap_base_for_quote: bool,
     current_timestamp: u64,
     current_slot: u64,
     in_amount: u64,
     has_referral: bool,
     eligible_for_first_swap_with_min_fee: bool, // Only for creator to bundle swap in initialize pool instruction to avoid anti sniper suite fee
+    sqrt_price_limit: Option<u128>, // Swap stops at this price, the unused input is returned in amount_left
 ) -> Result<SwapResult2> {
     ensure!(
         !pool.is_curve_complete(config.migration_quote_threshold),
         "virtual pool is completed"
     );
 
+    ensure!(
+        !pool.is_circuit_breaker_triggered(config, current_timestamp)?,
+        "circuit breaker is triggered"
+    );
+
     ensure!(in_amount > 0, "amount is zero");
 
     let activation_type =
@@ -34,6 +41,14 @@ pub fn quote_partial_fill(
     } else {
         TradeDirection::QuoteToBase
     };
+    let fee_config = config.get_fee_config(
+        trade_direction,
+        config_extension,
+        current_point,
+        pool.activation_point,
+    )?;
+    let config = fee_config.as_deref().unwrap_or(config);
+
     let fee_mode = &FeeMode::get_fee_mode(config.collect_fee_mode, trade_direction, has_referral)?;
 
     let swap_result = pool.get_swap_result_from_partial_input(
@@ -43,6 +58,7 @@ pub fn quote_partial_fill(
         trade_direction,
         current_point,
         eligible_for_first_swap_with_min_fee,
+        sqrt_price_limit,
     )?;
 
     Ok(swap_result)
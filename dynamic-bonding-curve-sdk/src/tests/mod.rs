@@ -2,6 +2,9 @@ use std::fs;
 
 use dynamic_bonding_curve::state::{PoolConfig, VirtualPool};
 
+mod test_config_validator;
+mod test_curve_designer;
+mod test_fee_forecast;
 mod test_quote_exact_out;
 mod test_quote_partial_fill;
 
@@ -0,0 +1,225 @@
+use dynamic_bonding_curve::{
+    constants::MAX_MIGRATED_POOL_FEE_BPS,
+    params::fee_parameters::{BaseFeeParameters, DynamicFeeParameters, PoolFeeParameters},
+    state::{BuybackFeeSource, CollectFeeMode, CurveType, MigrationFeeOption, MigrationOption},
+    ConfigParameters, LiquidityVestingInfoParams, LockedVestingParams, MigratedPoolFee,
+    MigrationFee,
+};
+
+use crate::{
+    config_validator::validate_config_parameters,
+    curve_designer::{design_curve, CurveDesignParameters, CurveShape},
+};
+
+const CURRENT_TIMESTAMP: u64 = 1_750_000_000;
+
+fn get_config_parameters() -> ConfigParameters {
+    design_curve(&CurveDesignParameters {
+        total_token_supply: 1_000_000_000_000_000,
+        token_base_decimal: 6,
+        token_quote_decimal: 9,
+        initial_market_cap: 30.0,
+        migration_market_cap: 300.0,
+        percentage_supply_on_curve: 70,
+        curve_shape: CurveShape::ConstantProduct,
+        migration_option: MigrationOption::DammV2.into(),
+        migration_fee: MigrationFee::default(),
+        locked_vesting: LockedVestingParams::default(),
+        pool_fees: PoolFeeParameters {
+            base_fee: BaseFeeParameters {
+                cliff_fee_numerator: 2_500_000,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        collect_fee_mode: 0,
+        activation_type: 0,
+        creator_trading_fee_percentage: 0,
+    })
+    .unwrap()
+}
+
+fn get_violation_fields(config_parameters: &ConfigParameters) -> Vec<String> {
+    let violations = validate_config_parameters(config_parameters, CURRENT_TIMESTAMP);
+    // every fixture is also checked against the program validation
+    assert_eq!(
+        violations.is_empty(),
+        config_parameters
+            .validate_parameters(CURRENT_TIMESTAMP)
+            .is_ok()
+    );
+    violations
+        .into_iter()
+        .map(|violation| violation.field)
+        .collect()
+}
+
+#[test]
+fn test_validate_valid_config_parameters() {
+    let config_parameters = get_config_parameters();
+    assert!(get_violation_fields(&config_parameters).is_empty());
+}
+
+#[test]
+fn test_validate_config_parameters_matches_program() {
+    let valid_config_parameters = get_config_parameters();
+    let mut fixtures = vec![valid_config_parameters.clone(); 10];
+    fixtures[0].activation_type = 2;
+    fixtures[1].collect_fee_mode = 3;
+    fixtures[2].max_buy_amount_per_wallet = 1_000;
+    fixtures[3].allowlist_duration = 3_600;
+    fixtures[4].allowlist_merkle_root = [1u8; 32];
+    fixtures[5].pool_creation_fee = 1;
+    fixtures[6].locked_vesting = LockedVestingParams {
+        amount_per_period: 1_000,
+        number_of_period: 10,
+        ..Default::default()
+    };
+    fixtures[7].migration_quote_threshold = 0;
+    fixtures[8].collect_fee_mode = CollectFeeMode::QuoteTokenBuybackBurn.into();
+    fixtures[9].buyback_period = 100;
+
+    for config_parameters in fixtures.iter() {
+        assert!(!get_violation_fields(config_parameters).is_empty());
+    }
+
+    // the launch phases are valid when set together
+    let mut config_parameters = valid_config_parameters;
+    config_parameters.max_buy_amount_per_wallet = 1_000;
+    config_parameters.buy_cap_duration = 100;
+    config_parameters.allowlist_duration = 100;
+    config_parameters.allowlist_merkle_root = [1u8; 32];
+    assert!(get_violation_fields(&config_parameters).is_empty());
+
+    // the buyback spend per period is required in the buyback burn collect fee mode
+    config_parameters.collect_fee_mode = CollectFeeMode::QuoteTokenBuybackBurn.into();
+    assert_eq!(
+        get_violation_fields(&config_parameters),
+        vec!["buyback_max_quote_amount_per_period", "buyback_period"]
+    );
+    config_parameters.buyback_fee_source = BuybackFeeSource::PartnerAndCreator.into();
+    config_parameters.buyback_max_quote_amount_per_period = 1_000_000;
+    config_parameters.buyback_period = 100;
+    assert!(get_violation_fields(&config_parameters).is_empty());
+}
+
+#[test]
+fn test_validate_config_parameters_reports_every_violation() {
+    let mut config_parameters = get_config_parameters();
+    config_parameters.token_decimal = 5;
+    config_parameters.creator_trading_fee_percentage = 101;
+    config_parameters.migration_fee_option = MigrationFeeOption::Customizable.into();
+    config_parameters.migrated_pool_fee = MigratedPoolFee {
+        pool_fee_bps: MAX_MIGRATED_POOL_FEE_BPS + 1,
+        ..Default::default()
+    };
+    config_parameters.migration_fee = MigrationFee {
+        fee_percentage: 0,
+        creator_fee_percentage: 50,
+    };
+    config_parameters.pool_fees.dynamic_fee = Some(DynamicFeeParameters {
+        filter_period: 10,
+        decay_period: 10,
+        ..Default::default()
+    });
+    config_parameters.curve[0].liquidity = 0;
+
+    assert_eq!(
+        get_violation_fields(&config_parameters),
+        vec![
+            "pool_fees.dynamic_fee.bin_step",
+            "pool_fees.dynamic_fee.bin_step_u128",
+            "pool_fees.dynamic_fee.filter_period",
+            "creator_trading_fee_percentage",
+            "migration_fee.creator_fee_percentage",
+            "migrated_pool_fee.pool_fee_bps",
+            "token_decimal",
+            "curve[0].liquidity",
+        ]
+    );
+
+    let violations = validate_config_parameters(&config_parameters, CURRENT_TIMESTAMP);
+    assert_eq!(violations[5].allowed_range, "10 to 1000");
+}
+
+#[test]
+fn test_validate_config_parameters_damm_v1_migration() {
+    let mut config_parameters = get_config_parameters();
+    config_parameters.migration_option = MigrationOption::MeteoraDamm.into();
+    config_parameters.token_type = 1;
+    config_parameters.partner_permanent_locked_liquidity_percentage = 90;
+    config_parameters.partner_liquidity_vesting_info = LiquidityVestingInfoParams {
+        vesting_percentage: 10,
+        bps_per_period: 100,
+        number_of_periods: 100,
+        frequency: 3600,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        get_violation_fields(&config_parameters),
+        vec!["token_type", "partner_liquidity_vesting_info"]
+    );
+
+    // the liquidity percentages must still sum to 100
+    config_parameters.partner_liquidity_vesting_info = LiquidityVestingInfoParams::default();
+    assert_eq!(
+        get_violation_fields(&config_parameters),
+        vec!["partner_liquidity_percentage", "token_type"]
+    );
+}
+
+#[test]
+fn test_validate_config_parameters_curve() {
+    let mut config_parameters = get_config_parameters();
+    let mut last_point = *config_parameters.curve.last().unwrap();
+    last_point.liquidity = 1;
+    config_parameters.curve.push(last_point);
+
+    assert_eq!(
+        get_violation_fields(&config_parameters),
+        vec!["curve[1].sqrt_price"]
+    );
+
+    config_parameters.curve.clear();
+    config_parameters.sqrt_start_price = 0;
+    assert_eq!(
+        get_violation_fields(&config_parameters),
+        vec!["sqrt_start_price", "curve"]
+    );
+}
+
+#[test]
+fn test_validate_config_parameters_price_curve() {
+    let mut config_parameters = get_config_parameters();
+    config_parameters.curve_type = CurveType::LinearPrice.into();
+    config_parameters.curve[0].liquidity = 700_000_000_000_000;
+    assert!(get_violation_fields(&config_parameters).is_empty());
+
+    // the base amount sold on the curve must fit in u64
+    config_parameters.curve[0].liquidity = u128::from(u64::MAX) + 1;
+    assert_eq!(
+        get_violation_fields(&config_parameters),
+        vec!["curve[0].liquidity"]
+    );
+
+    // a single point
+    config_parameters.curve[0].liquidity = 700_000_000_000_000;
+    let mut last_point = config_parameters.curve[0];
+    last_point.sqrt_price += 1;
+    config_parameters.curve.push(last_point);
+    assert_eq!(get_violation_fields(&config_parameters), vec!["curve"]);
+
+    // the exponential price curve needs a wide enough price range
+    config_parameters.curve.pop();
+    config_parameters.curve_type = CurveType::ExponentialPrice.into();
+    assert!(get_violation_fields(&config_parameters).is_empty());
+    config_parameters.curve[0].sqrt_price = config_parameters.sqrt_start_price + 1;
+    assert_eq!(
+        get_violation_fields(&config_parameters),
+        vec!["curve[0].sqrt_price"]
+    );
+
+    config_parameters.curve_type = 3;
+    assert_eq!(get_violation_fields(&config_parameters), vec!["curve_type"]);
+}
@@ -0,0 +1,170 @@
+use dynamic_bonding_curve::{
+    params::{
+        fee_parameters::{BaseFeeParameters, PoolFeeParameters},
+        liquidity_distribution::{get_base_token_for_swap, get_migration_threshold_price},
+    },
+    state::{CurveType, MigrationOption},
+    u128x128_math::Rounding,
+    ConfigParameters, LockedVestingParams, MigrationFee,
+};
+
+use crate::curve_designer::{design_curve, CurveDesignParameters, CurveShape};
+
+// 1B tokens with 6 decimals
+const TOTAL_TOKEN_SUPPLY: u64 = 1_000_000_000_000_000;
+
+fn get_design_parameters(curve_shape: CurveShape) -> CurveDesignParameters {
+    CurveDesignParameters {
+        total_token_supply: TOTAL_TOKEN_SUPPLY,
+        token_base_decimal: 6,
+        token_quote_decimal: 9,
+        initial_market_cap: 30.0,
+        migration_market_cap: 300.0,
+        percentage_supply_on_curve: 70,
+        curve_shape,
+        migration_option: MigrationOption::DammV2.into(),
+        migration_fee: MigrationFee::default(),
+        locked_vesting: LockedVestingParams::default(),
+        pool_fees: PoolFeeParameters {
+            base_fee: BaseFeeParameters {
+                cliff_fee_numerator: 2_500_000,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        collect_fee_mode: 0,
+        activation_type: 0,
+        creator_trading_fee_percentage: 0,
+    }
+}
+
+// the migration price is at the end of the curve and the whole curve is sold before migration
+fn assert_round_trip(config_parameters: &ConfigParameters, percentage_supply_on_curve: u64) {
+    let ConfigParameters {
+        migration_quote_threshold,
+        sqrt_start_price,
+        ref curve,
+        ..
+    } = *config_parameters;
+
+    let migration_sqrt_price =
+        get_migration_threshold_price(migration_quote_threshold, sqrt_start_price, curve).unwrap();
+    let last_sqrt_price = curve.last().unwrap().sqrt_price;
+    assert!(migration_sqrt_price <= last_sqrt_price);
+    assert!(last_sqrt_price - migration_sqrt_price <= last_sqrt_price / 1_000_000);
+
+    let swap_base_amount: u64 =
+        get_base_token_for_swap(sqrt_start_price, migration_sqrt_price, curve)
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let expected_swap_base_amount = TOTAL_TOKEN_SUPPLY / 100 * percentage_supply_on_curve;
+    assert!(
+        swap_base_amount.abs_diff(expected_swap_base_amount) <= expected_swap_base_amount / 10_000
+    );
+}
+
+#[test]
+fn test_design_constant_product_curve() {
+    let params = get_design_parameters(CurveShape::ConstantProduct);
+    let config_parameters = design_curve(&params).unwrap();
+
+    assert_eq!(config_parameters.curve.len(), 1);
+    assert_eq!(config_parameters.token_decimal, 6);
+    let token_supply = config_parameters.token_supply.unwrap();
+    assert_eq!(token_supply.pre_migration_token_supply, TOTAL_TOKEN_SUPPLY);
+    assert_eq!(token_supply.post_migration_token_supply, TOTAL_TOKEN_SUPPLY);
+
+    // the migration market cap is 10x the initial one
+    let sqrt_price_ratio =
+        config_parameters.curve[0].sqrt_price as f64 / config_parameters.sqrt_start_price as f64;
+    assert!((sqrt_price_ratio - 10f64.sqrt()).abs() < 1e-9);
+
+    assert_round_trip(&config_parameters, 70);
+}
+
+#[test]
+fn test_design_linear_price_curve() {
+    let params = get_design_parameters(CurveShape::LinearPrice);
+    let config_parameters = design_curve(&params).unwrap();
+    assert_eq!(
+        config_parameters.curve_type,
+        u8::from(CurveType::LinearPrice)
+    );
+    assert_eq!(config_parameters.curve.len(), 1);
+
+    // the whole curve is sold before migration
+    let expected_swap_base_amount = TOTAL_TOKEN_SUPPLY / 100 * 70;
+    let price_curve = config_parameters.get_price_curve().unwrap().unwrap();
+    assert_eq!(price_curve.base_amount, expected_swap_base_amount);
+    let migration_sqrt_price = price_curve
+        .get_migration_sqrt_price(config_parameters.migration_quote_threshold)
+        .unwrap();
+    assert!(
+        price_curve.end_sqrt_price - migration_sqrt_price <= price_curve.end_sqrt_price / 1_000_000
+    );
+
+    let swap_base_amount: u64 = price_curve
+        .get_delta_amount_base(
+            config_parameters.sqrt_start_price,
+            migration_sqrt_price,
+            Rounding::Up,
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+    assert!(
+        swap_base_amount.abs_diff(expected_swap_base_amount) <= expected_swap_base_amount / 10_000
+    );
+}
+
+#[test]
+fn test_design_multi_segment_curve() {
+    let params = get_design_parameters(CurveShape::MultiSegment {
+        market_caps: vec![60.0, 150.0],
+        liquidity_weights: vec![1.0, 2.0, 4.0],
+    });
+    let config_parameters = design_curve(&params).unwrap();
+    assert_eq!(config_parameters.curve.len(), 3);
+    assert_eq!(
+        config_parameters.curve[1].liquidity / config_parameters.curve[0].liquidity,
+        2
+    );
+    assert_round_trip(&config_parameters, 70);
+
+    // one weight per segment
+    let params = get_design_parameters(CurveShape::MultiSegment {
+        market_caps: vec![60.0, 150.0],
+        liquidity_weights: vec![1.0, 2.0],
+    });
+    assert!(design_curve(&params).is_err());
+
+    // market caps out of the curve range
+    let params = get_design_parameters(CurveShape::MultiSegment {
+        market_caps: vec![600.0],
+        liquidity_weights: vec![1.0, 1.0],
+    });
+    assert!(design_curve(&params).is_err());
+}
+
+#[test]
+fn test_design_curve_over_total_supply() {
+    // 90% sold on the curve leaves too little supply for the migration liquidity
+    let params = CurveDesignParameters {
+        percentage_supply_on_curve: 90,
+        ..get_design_parameters(CurveShape::ConstantProduct)
+    };
+    assert!(design_curve(&params).is_err());
+
+    // locked vesting doesn't fit in the leftover supply
+    let params = CurveDesignParameters {
+        locked_vesting: LockedVestingParams {
+            amount_per_period: TOTAL_TOKEN_SUPPLY / 10,
+            frequency: 1,
+            number_of_period: 1,
+            ..Default::default()
+        },
+        ..get_design_parameters(CurveShape::ConstantProduct)
+    };
+    assert!(design_curve(&params).is_err());
+}
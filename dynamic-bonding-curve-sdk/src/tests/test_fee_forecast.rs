@@ -0,0 +1,122 @@
+use dynamic_bonding_curve::{
+    activation_handler::ActivationType,
+    constants::dynamic_fee::BIN_STEP_BPS_U128_DEFAULT,
+    state::{BaseFeeMode, PoolConfig, VirtualPool},
+};
+
+use crate::fee_forecast::{get_fee_curve, get_fee_forecast, get_variable_fee_numerator};
+
+const ACTIVATION_POINT: u64 = 1_000_000;
+
+fn get_fee_scheduler_accounts() -> (PoolConfig, VirtualPool) {
+    let mut config = PoolConfig {
+        activation_type: ActivationType::Timestamp.into(),
+        ..Default::default()
+    };
+    // 10% cliff fee, reduced by 1% every minute for 9 minutes
+    config.pool_fees.base_fee.cliff_fee_numerator = 100_000_000;
+    config.pool_fees.base_fee.first_factor = 9;
+    config.pool_fees.base_fee.second_factor = 60;
+    config.pool_fees.base_fee.third_factor = 10_000_000;
+    config.pool_fees.base_fee.base_fee_mode = BaseFeeMode::FeeSchedulerLinear.into();
+
+    let pool = VirtualPool {
+        activation_point: ACTIVATION_POINT,
+        sqrt_price: 1 << 64,
+        ..Default::default()
+    };
+    (config, pool)
+}
+
+#[test]
+fn test_fee_forecast_fee_scheduler() {
+    let (config, pool) = get_fee_scheduler_accounts();
+
+    let forecast = get_fee_forecast(
+        &pool,
+        &config,
+        None,
+        false,
+        ACTIVATION_POINT,
+        0,
+        ACTIVATION_POINT + 180,
+        0,
+    )
+    .unwrap();
+    // fee drops to 7% in 3 minutes
+    assert_eq!(forecast.base_fee_numerator, 70_000_000);
+    assert_eq!(forecast.variable_fee_numerator, 0);
+    assert_eq!(forecast.total_fee_numerator, 70_000_000);
+
+    assert!(get_fee_forecast(
+        &pool,
+        &config,
+        None,
+        false,
+        ACTIVATION_POINT,
+        0,
+        ACTIVATION_POINT - 1,
+        0,
+    )
+    .is_err());
+}
+
+#[test]
+fn test_fee_curve_fee_scheduler() {
+    let (config, pool) = get_fee_scheduler_accounts();
+
+    let fee_curve = get_fee_curve(
+        &pool,
+        &config,
+        None,
+        false,
+        ACTIVATION_POINT,
+        0,
+        ACTIVATION_POINT,
+        ACTIVATION_POINT + 720,
+        60,
+        0,
+    )
+    .unwrap();
+
+    assert_eq!(fee_curve.len(), 13);
+    assert_eq!(fee_curve[0].base_fee_numerator, 100_000_000);
+    assert_eq!(fee_curve[5].point, ACTIVATION_POINT + 300);
+    assert_eq!(fee_curve[5].base_fee_numerator, 50_000_000);
+    // floored at the min base fee after the last period
+    assert_eq!(fee_curve[9].base_fee_numerator, 10_000_000);
+    assert_eq!(fee_curve[12].base_fee_numerator, 10_000_000);
+}
+
+#[test]
+fn test_variable_fee_decay() {
+    let (mut config, mut pool) = get_fee_scheduler_accounts();
+    let dynamic_fee = &mut config.pool_fees.dynamic_fee;
+    dynamic_fee.initialized = 1;
+    dynamic_fee.max_volatility_accumulator = 14_460_000;
+    dynamic_fee.variable_fee_control = 100_000;
+    dynamic_fee.bin_step = 1;
+    dynamic_fee.bin_step_u128 = BIN_STEP_BPS_U128_DEFAULT;
+    dynamic_fee.filter_period = 10;
+    dynamic_fee.decay_period = 120;
+    dynamic_fee.reduction_factor = 5_000;
+
+    pool.volatility_tracker.last_update_timestamp = ACTIVATION_POINT;
+    pool.volatility_tracker.sqrt_price_reference = pool.sqrt_price;
+    pool.volatility_tracker.volatility_accumulator = 1_000_000;
+    pool.volatility_tracker.volatility_reference = 1_000_000;
+
+    // high frequency window, the volatility doesn't decay
+    let fee_in_filter_period =
+        get_variable_fee_numerator(&pool, &config, ACTIVATION_POINT + 5).unwrap();
+    // half of the volatility decays
+    let fee_in_decay_period =
+        get_variable_fee_numerator(&pool, &config, ACTIVATION_POINT + 60).unwrap();
+    // all volatility decays
+    let fee_after_decay_period =
+        get_variable_fee_numerator(&pool, &config, ACTIVATION_POINT + 120).unwrap();
+
+    assert_eq!(fee_in_filter_period, 1_000_000);
+    assert_eq!(fee_in_decay_period, 250_000);
+    assert_eq!(fee_after_decay_period, 0);
+}
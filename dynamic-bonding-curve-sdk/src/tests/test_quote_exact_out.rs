@@ -18,6 +18,7 @@ fn test_quote_exact_out_fee_in_quote_from_base_for_quote() {
     let exact_out_swap_result = quote_exact_out(
         &pool,
         &config,
+        None,
         swap_base_for_quote,
         current_timestamp,
         current_slot,
@@ -31,12 +32,14 @@ fn test_quote_exact_out_fee_in_quote_from_base_for_quote() {
     let exact_in_swap_result = quote_exact_in(
         &pool,
         &config,
+        None,
         swap_base_for_quote,
         current_timestamp,
         current_slot,
         exact_out_swap_result.included_fee_input_amount,
         false,
         false,
+        None,
     )
     .unwrap();
     println!("exact_in_swap_result {:?}", exact_in_swap_result);
@@ -66,6 +69,7 @@ fn test_quote_exact_out_fee_in_quote_from_quote_to_base() {
     let exact_out_swap_result = quote_exact_out(
         &pool,
         &config,
+        None,
         swap_base_for_quote,
         current_timestamp,
         current_slot,
@@ -78,12 +82,14 @@ fn test_quote_exact_out_fee_in_quote_from_quote_to_base() {
     let exact_in_swap_result = quote_exact_in(
         &pool,
         &config,
+        None,
         swap_base_for_quote,
         current_timestamp,
         current_slot,
         exact_out_swap_result.included_fee_input_amount,
         false,
         false,
+        None,
     )
     .unwrap();
     println!("exact_in_swap_result {:?}", exact_in_swap_result);
@@ -108,6 +114,7 @@ fn test_quote_exact_out_fee_in_both_from_base_for_quote() {
     let exact_out_swap_result = quote_exact_out(
         &pool,
         &config,
+        None,
         swap_base_for_quote,
         current_timestamp,
         current_slot,
@@ -121,12 +128,14 @@ fn test_quote_exact_out_fee_in_both_from_base_for_quote() {
     let exact_in_swap_result = quote_exact_in(
         &pool,
         &config,
+        None,
         swap_base_for_quote,
         current_timestamp,
         current_slot,
         exact_out_swap_result.included_fee_input_amount,
         false,
         false,
+        None,
     )
     .unwrap();
     println!("exact_in_swap_result {:?}", exact_in_swap_result);
@@ -156,6 +165,7 @@ fn test_quote_exact_out_fee_in_both_from_quote_to_base() {
     let exact_out_swap_result = quote_exact_out(
         &pool,
         &config,
+        None,
         swap_base_for_quote,
         current_timestamp,
         current_slot,
@@ -168,12 +178,14 @@ fn test_quote_exact_out_fee_in_both_from_quote_to_base() {
     let exact_in_swap_result = quote_exact_in(
         &pool,
         &config,
+        None,
         swap_base_for_quote,
         current_timestamp,
         current_slot,
         exact_out_swap_result.included_fee_input_amount,
         false,
         false,
+        None,
     )
     .unwrap();
     println!("exact_in_swap_result {:?}", exact_in_swap_result);
@@ -19,24 +19,28 @@ fn test_quote_partial_fill_fee_in_quote_from_base_for_quote() {
     let partial_fill_swap_result = quote_partial_fill(
         &pool,
         &config,
+        None,
         swap_base_for_quote,
         current_timestamp,
         current_slot,
         input_amount,
         false,
         false,
+        None,
     )
     .unwrap();
 
     let exact_in_swap_result = quote_exact_in(
         &pool,
         &config,
+        None,
         swap_base_for_quote,
         current_timestamp,
         current_slot,
         input_amount,
         false,
         false,
+        None,
     )
     .unwrap();
 
@@ -57,24 +61,28 @@ fn test_quote_partial_fill_fee_in_quote_from_quote_to_base() {
     let partial_fill_swap_result = quote_partial_fill(
         &pool,
         &config,
+        None,
         swap_base_for_quote,
         current_timestamp,
         current_slot,
         input_amount,
         false,
         false,
+        None,
     )
     .unwrap();
 
     let exact_in_swap_result = quote_exact_in(
         &pool,
         &config,
+        None,
         swap_base_for_quote,
         current_timestamp,
         current_slot,
         input_amount,
         false,
         false,
+        None,
     )
     .unwrap();
     assert!(partial_fill_swap_result.eq(&exact_in_swap_result));
@@ -95,24 +103,28 @@ fn test_quote_partial_fill_fee_in_both_from_base_for_quote() {
     let partial_fill_swap_result = quote_partial_fill(
         &pool,
         &config,
+        None,
         swap_base_for_quote,
         current_timestamp,
         current_slot,
         input_amount,
         false,
         false,
+        None,
     )
     .unwrap();
 
     let exact_in_swap_result = quote_exact_in(
         &pool,
         &config,
+        None,
         swap_base_for_quote,
         current_timestamp,
         current_slot,
         input_amount,
         false,
         false,
+        None,
     )
     .unwrap();
 
@@ -133,24 +145,28 @@ fn test_quote_partial_fill_fee_in_both_from_quote_to_base() {
     let partial_fill_swap_result = quote_partial_fill(
         &pool,
         &config,
+        None,
         swap_base_for_quote,
         current_timestamp,
         current_slot,
         input_amount,
         false,
         false,
+        None,
     )
     .unwrap();
 
     let exact_in_swap_result = quote_exact_in(
         &pool,
         &config,
+        None,
         swap_base_for_quote,
         current_timestamp,
         current_slot,
         input_amount,
         false,
         false,
+        None,
     )
     .unwrap();
     assert!(partial_fill_swap_result.eq(&exact_in_swap_result));
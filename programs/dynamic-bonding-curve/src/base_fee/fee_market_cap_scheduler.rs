@@ -0,0 +1,142 @@
+use crate::{
+    activation_handler::ActivationType,
+    constants::fee::{FEE_DENOMINATOR, MAX_BASIS_POINT, MAX_FEE_NUMERATOR, MIN_FEE_NUMERATOR},
+    fee_math::get_fee_in_period,
+    math::safe_math::SafeMath,
+    params::{fee_parameters::validate_fee_fraction, swap::TradeDirection},
+    PoolError,
+};
+use anchor_lang::prelude::*;
+
+use super::{BaseFeeHandler, FeeSchedulerMode};
+
+// https://github.com/MeteoraAg/damm-v2/blob/f36db1b7ae2b465bf3fd773594bd62528c3d51cd/programs/cp-amm/src/base_fee/fee_market_cap_scheduler.rs
+// same as fee scheduler, but passed_period is the number of sqrt_price_step_bps that the current sqrt price is above the init sqrt price
+// passed_period = (current_sqrt_price - init_sqrt_price) * 10_000 / init_sqrt_price / sqrt_price_step_bps
+#[derive(Debug, Default)]
+pub struct FeeMarketCapScheduler {
+    pub cliff_fee_numerator: u64,
+    pub number_of_period: u16,
+    pub sqrt_price_step_bps: u64,
+    pub reduction_factor: u64,
+    pub fee_scheduler_mode: u8,
+}
+
+impl FeeMarketCapScheduler {
+    pub fn get_max_base_fee_numerator(&self) -> u64 {
+        self.cliff_fee_numerator
+    }
+
+    fn get_base_fee_numerator_by_period(&self, period: u64) -> Result<u64> {
+        let period = period.min(self.number_of_period.into());
+
+        let fee_scheduler_mode = FeeSchedulerMode::try_from(self.fee_scheduler_mode)
+            .map_err(|_| PoolError::TypeCastFailed)?;
+
+        match fee_scheduler_mode {
+            FeeSchedulerMode::Linear => {
+                let fee_numerator = self
+                    .cliff_fee_numerator
+                    .safe_sub(self.reduction_factor.safe_mul(period)?)?;
+                Ok(fee_numerator)
+            }
+            FeeSchedulerMode::Exponential => {
+                let period = u16::try_from(period).map_err(|_| PoolError::MathOverflow)?;
+                let fee_numerator =
+                    get_fee_in_period(self.cliff_fee_numerator, self.reduction_factor, period)?;
+                Ok(fee_numerator)
+            }
+        }
+    }
+
+    pub fn get_passed_period(
+        &self,
+        init_sqrt_price: u128,
+        current_sqrt_price: u128,
+    ) -> Result<u64> {
+        if current_sqrt_price <= init_sqrt_price {
+            return Ok(0);
+        }
+
+        let passed_period = current_sqrt_price
+            .safe_sub(init_sqrt_price)?
+            .safe_mul(MAX_BASIS_POINT.into())?
+            .safe_div(init_sqrt_price)?
+            .safe_div(self.sqrt_price_step_bps.into())?;
+
+        let passed_period = passed_period
+            .min(self.number_of_period.into())
+            .try_into()
+            .map_err(|_| PoolError::TypeCastFailed)?;
+        Ok(passed_period)
+    }
+
+    fn get_base_fee_numerator(
+        &self,
+        init_sqrt_price: u128,
+        current_sqrt_price: u128,
+    ) -> Result<u64> {
+        let period = self.get_passed_period(init_sqrt_price, current_sqrt_price)?;
+        self.get_base_fee_numerator_by_period(period)
+    }
+}
+
+impl BaseFeeHandler for FeeMarketCapScheduler {
+    fn validate(&self, _collect_fee_mode: u8, _activation_type: ActivationType) -> Result<()> {
+        // doesn't allow zero fee marketcap scheduler
+        require!(
+            self.number_of_period != 0
+                && self.sqrt_price_step_bps != 0
+                && self.reduction_factor != 0,
+            PoolError::InvalidFeeMarketCapScheduler
+        );
+
+        let min_fee_numerator = self.get_min_base_fee_numerator()?;
+        let max_fee_numerator = self.get_max_base_fee_numerator();
+        validate_fee_fraction(min_fee_numerator, FEE_DENOMINATOR)?;
+        validate_fee_fraction(max_fee_numerator, FEE_DENOMINATOR)?;
+        require!(
+            min_fee_numerator >= MIN_FEE_NUMERATOR && max_fee_numerator <= MAX_FEE_NUMERATOR,
+            PoolError::ExceedMaxFeeBps
+        );
+        Ok(())
+    }
+
+    fn get_base_fee_numerator_from_included_fee_amount(
+        &self,
+        _current_point: u64,
+        _activation_point: u64,
+        _trade_direction: TradeDirection,
+        _included_fee_amount: u64,
+        init_sqrt_price: u128,
+        current_sqrt_price: u128,
+    ) -> Result<u64> {
+        self.get_base_fee_numerator(init_sqrt_price, current_sqrt_price)
+    }
+
+    fn get_base_fee_numerator_from_excluded_fee_amount(
+        &self,
+        _current_point: u64,
+        _activation_point: u64,
+        _trade_direction: TradeDirection,
+        _excluded_fee_amount: u64,
+        init_sqrt_price: u128,
+        current_sqrt_price: u128,
+    ) -> Result<u64> {
+        self.get_base_fee_numerator(init_sqrt_price, current_sqrt_price)
+    }
+
+    fn validate_min_base_fee(&self) -> Result<()> {
+        let min_base_fee_numerator = self.get_min_base_fee_numerator()?;
+
+        require!(
+            min_base_fee_numerator >= MIN_FEE_NUMERATOR,
+            PoolError::InvalidMinBaseFee
+        );
+        Ok(())
+    }
+
+    fn get_min_base_fee_numerator(&self) -> Result<u64> {
+        self.get_base_fee_numerator_by_period(self.number_of_period.into())
+    }
+}
@@ -8,7 +8,7 @@ use crate::{
     },
     params::{fee_parameters::to_numerator, swap::TradeDirection},
     safe_math::SafeMath,
-    state::{CollectFeeMode, PoolFeesConfig},
+    state::{CollectFeeMode, PoolFeesConfig, RateLimiterMode},
     u128x128_math::Rounding,
     utils_math::{safe_mul_div_cast_u64, sqrt_u256},
     PoolError,
@@ -36,6 +36,7 @@ pub struct FeeRateLimiter {
     pub fee_increment_bps: u16,
     pub max_limiter_duration: u64,
     pub reference_amount: u64,
+    pub rate_limiter_mode: RateLimiterMode,
 }
 
 impl FeeRateLimiter {
@@ -49,8 +50,8 @@ impl FeeRateLimiter {
             return Ok(false);
         }
 
-        // only handle for the case quote to base and collect fee mode in quote token
-        if trade_direction == TradeDirection::BaseToQuote {
+        // fee is always charged in quote token: on input for buys and on output for sells
+        if !self.is_trade_direction_applied(trade_direction) {
             return Ok(false);
         }
 
@@ -62,6 +63,15 @@ impl FeeRateLimiter {
         Ok(true)
     }
 
+    fn is_trade_direction_applied(&self, trade_direction: TradeDirection) -> bool {
+        match (self.rate_limiter_mode, trade_direction) {
+            (RateLimiterMode::Both, _)
+            | (RateLimiterMode::QuoteToBase, TradeDirection::QuoteToBase)
+            | (RateLimiterMode::BaseToQuote, TradeDirection::BaseToQuote) => true,
+            _ => false,
+        }
+    }
+
     fn is_zero_rate_limiter(&self) -> bool {
         self.reference_amount == 0 && self.max_limiter_duration == 0 && self.fee_increment_bps == 0
     }
@@ -285,9 +295,11 @@ impl BaseFeeHandler for FeeRateLimiter {
     fn validate(&self, collect_fee_mode: u8, activation_type: ActivationType) -> Result<()> {
         let collect_fee_mode = CollectFeeMode::try_from(collect_fee_mode)
             .map_err(|_| PoolError::InvalidCollectFeeMode)?;
-        // can only be applied in quote token collect fee mode
+        // reference amount is in quote token, buys only pay fee in quote token in quote token collect fee mode,
+        // while sells pay fee on quote output in both collect fee modes
         require!(
-            collect_fee_mode == CollectFeeMode::QuoteToken,
+            collect_fee_mode.is_fee_on_quote_token_only()
+                || self.rate_limiter_mode == RateLimiterMode::BaseToQuote,
             PoolError::InvalidFeeRateLimiter
         );
 
@@ -338,6 +350,8 @@ impl BaseFeeHandler for FeeRateLimiter {
         activation_point: u64,
         trade_direction: TradeDirection,
         included_fee_amount: u64,
+        _init_sqrt_price: u128,
+        _current_sqrt_price: u128,
     ) -> Result<u64> {
         if self.is_rate_limiter_applied(current_point, activation_point, trade_direction)? {
             self.get_fee_numerator_from_included_fee_amount(included_fee_amount)
@@ -352,6 +366,8 @@ impl BaseFeeHandler for FeeRateLimiter {
         activation_point: u64,
         trade_direction: TradeDirection,
         excluded_fee_amount: u64,
+        _init_sqrt_price: u128,
+        _current_sqrt_price: u128,
     ) -> Result<u64> {
         if self.is_rate_limiter_applied(current_point, activation_point, trade_direction)? {
             self.get_fee_numerator_from_excluded_fee_amount(excluded_fee_amount)
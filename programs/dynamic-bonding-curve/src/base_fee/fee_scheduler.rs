@@ -100,6 +100,8 @@ impl BaseFeeHandler for FeeScheduler {
         activation_point: u64,
         _trade_direction: TradeDirection,
         _included_fee_amount: u64,
+        _init_sqrt_price: u128,
+        _current_sqrt_price: u128,
     ) -> Result<u64> {
         self.get_base_fee_numerator(current_point, activation_point)
     }
@@ -110,6 +112,8 @@ impl BaseFeeHandler for FeeScheduler {
         activation_point: u64,
         _trade_direction: TradeDirection,
         _excluded_fee_amount: u64,
+        _init_sqrt_price: u128,
+        _current_sqrt_price: u128,
     ) -> Result<u64> {
         self.get_base_fee_numerator(current_point, activation_point)
     }
@@ -0,0 +1,76 @@
+use crate::{
+    activation_handler::ActivationType,
+    constants::{
+        fee::{FEE_DENOMINATOR, MAX_FEE_NUMERATOR, MIN_FEE_NUMERATOR},
+        MAX_FEE_STEPS,
+    },
+    params::{fee_parameters::validate_fee_fraction, swap::TradeDirection},
+    PoolError,
+};
+use anchor_lang::prelude::*;
+
+use super::BaseFeeHandler;
+
+// the fee steps are stored in the config extension, the config only keeps the bounds of the step fees
+// fee_numerator is the highest step fee, and is replaced by the fee of the current step before a swap (PoolConfig::with_fee_step)
+#[derive(Debug, Default)]
+pub struct FeeStepTable {
+    pub fee_numerator: u64,
+    pub number_of_step: u16,
+    pub min_fee_numerator: u64,
+}
+
+impl BaseFeeHandler for FeeStepTable {
+    fn validate(&self, _collect_fee_mode: u8, _activation_type: ActivationType) -> Result<()> {
+        require!(
+            self.number_of_step != 0
+                && usize::from(self.number_of_step) <= MAX_FEE_STEPS
+                && self.min_fee_numerator <= self.fee_numerator,
+            PoolError::InvalidFeeStepTable
+        );
+
+        validate_fee_fraction(self.min_fee_numerator, FEE_DENOMINATOR)?;
+        validate_fee_fraction(self.fee_numerator, FEE_DENOMINATOR)?;
+        require!(
+            self.min_fee_numerator >= MIN_FEE_NUMERATOR && self.fee_numerator <= MAX_FEE_NUMERATOR,
+            PoolError::ExceedMaxFeeBps
+        );
+        Ok(())
+    }
+
+    fn get_base_fee_numerator_from_included_fee_amount(
+        &self,
+        _current_point: u64,
+        _activation_point: u64,
+        _trade_direction: TradeDirection,
+        _included_fee_amount: u64,
+        _init_sqrt_price: u128,
+        _current_sqrt_price: u128,
+    ) -> Result<u64> {
+        Ok(self.fee_numerator)
+    }
+
+    fn get_base_fee_numerator_from_excluded_fee_amount(
+        &self,
+        _current_point: u64,
+        _activation_point: u64,
+        _trade_direction: TradeDirection,
+        _excluded_fee_amount: u64,
+        _init_sqrt_price: u128,
+        _current_sqrt_price: u128,
+    ) -> Result<u64> {
+        Ok(self.fee_numerator)
+    }
+
+    fn validate_min_base_fee(&self) -> Result<()> {
+        require!(
+            self.min_fee_numerator >= MIN_FEE_NUMERATOR,
+            PoolError::InvalidMinBaseFee
+        );
+        Ok(())
+    }
+
+    fn get_min_base_fee_numerator(&self) -> Result<u64> {
+        Ok(self.min_fee_numerator)
+    }
+}
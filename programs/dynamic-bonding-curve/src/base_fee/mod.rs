@@ -2,11 +2,18 @@ pub mod fee_scheduler;
 pub use fee_scheduler::*;
 pub mod fee_rate_limiter;
 pub use fee_rate_limiter::*;
+pub mod fee_market_cap_scheduler;
+pub use fee_market_cap_scheduler::*;
+pub mod fee_step_table;
+pub use fee_step_table::*;
 
 use anchor_lang::prelude::*;
 
 use crate::{
-    activation_handler::ActivationType, params::swap::TradeDirection, state::BaseFeeMode, PoolError,
+    activation_handler::ActivationType,
+    params::swap::TradeDirection,
+    state::{BaseFeeMode, RateLimiterMode},
+    PoolError,
 };
 
 pub trait BaseFeeHandler {
@@ -17,6 +24,8 @@ pub trait BaseFeeHandler {
         activation_point: u64,
         trade_direction: TradeDirection,
         included_fee_amount: u64,
+        init_sqrt_price: u128,
+        current_sqrt_price: u128,
     ) -> Result<u64>;
     fn get_base_fee_numerator_from_excluded_fee_amount(
         &self,
@@ -24,6 +33,8 @@ pub trait BaseFeeHandler {
         activation_point: u64,
         trade_direction: TradeDirection,
         excluded_fee_amount: u64,
+        init_sqrt_price: u128,
+        current_sqrt_price: u128,
     ) -> Result<u64>;
 
     fn validate_min_base_fee(&self) -> Result<()>;
@@ -36,6 +47,7 @@ pub fn get_base_fee_handler(
     second_factor: u64,
     third_factor: u64,
     base_fee_mode: u8,
+    rate_limiter_mode: u8,
 ) -> Result<Box<dyn BaseFeeHandler>> {
     let base_fee_mode =
         BaseFeeMode::try_from(base_fee_mode).map_err(|_| PoolError::InvalidBaseFeeMode)?;
@@ -50,14 +62,40 @@ pub fn get_base_fee_handler(
             };
             Ok(Box::new(fee_scheduler))
         }
+        BaseFeeMode::FeeMarketCapSchedulerLinear
+        | BaseFeeMode::FeeMarketCapSchedulerExponential => {
+            let fee_scheduler_mode = if base_fee_mode == BaseFeeMode::FeeMarketCapSchedulerLinear {
+                FeeSchedulerMode::Linear
+            } else {
+                FeeSchedulerMode::Exponential
+            };
+            let fee_market_cap_scheduler = FeeMarketCapScheduler {
+                cliff_fee_numerator,
+                number_of_period: first_factor,
+                sqrt_price_step_bps: second_factor,
+                reduction_factor: third_factor,
+                fee_scheduler_mode: fee_scheduler_mode.into(),
+            };
+            Ok(Box::new(fee_market_cap_scheduler))
+        }
         BaseFeeMode::RateLimiter => {
             let fee_rate_limiter = FeeRateLimiter {
                 cliff_fee_numerator,
                 fee_increment_bps: first_factor,
                 max_limiter_duration: second_factor,
                 reference_amount: third_factor,
+                rate_limiter_mode: RateLimiterMode::try_from(rate_limiter_mode)
+                    .map_err(|_| PoolError::InvalidFeeRateLimiter)?,
             };
             Ok(Box::new(fee_rate_limiter))
         }
+        BaseFeeMode::FeeStepTable => {
+            let fee_step_table = FeeStepTable {
+                fee_numerator: cliff_fee_numerator,
+                number_of_step: first_factor,
+                min_fee_numerator: third_factor,
+            };
+            Ok(Box::new(fee_step_table))
+        }
     }
 }
@@ -55,6 +55,38 @@ static_assertions::const_assert_eq!(
     MAX_RATE_LIMITER_DURATION_IN_SLOTS
 );
 
+pub const MAX_BUY_CAP_DURATION_IN_SECONDS: u64 = 60 * 60 * 12; // 12 hours
+pub const MAX_BUY_CAP_DURATION_IN_SLOTS: u64 = 108000; // 12 hours
+static_assertions::const_assert_eq!(
+    MAX_BUY_CAP_DURATION_IN_SECONDS * 1000 / 400,
+    MAX_BUY_CAP_DURATION_IN_SLOTS
+);
+
+pub const MAX_ALLOWLIST_DURATION_IN_SECONDS: u64 = 60 * 60 * 24 * 7; // 7 days
+pub const MAX_ALLOWLIST_DURATION_IN_SLOTS: u64 = 1512000; // 7 days
+static_assertions::const_assert_eq!(
+    MAX_ALLOWLIST_DURATION_IN_SECONDS * 1000 / 400,
+    MAX_ALLOWLIST_DURATION_IN_SLOTS
+);
+
+pub const MAX_ALLOWLIST_PROOF_LENGTH: usize = 20;
+
+pub const MAX_SWAP_BATCH_LEGS: usize = 10;
+
+pub const MAX_VOLUME_FEE_TIERS: usize = 4;
+
+pub const MAX_CREATOR_FEE_SHARE_RECIPIENTS: usize = 5;
+
+pub const MAX_POOL_OBSERVATIONS: usize = 32;
+
+// min seconds between 2 observations of the pool observations ring buffer
+pub const MIN_POOL_OBSERVATION_INTERVAL: u64 = 60;
+
+pub const MAX_FEE_STEPS: usize = 8;
+
+// sha256("global:on_swap")[..8], so the hook program can be written in anchor with an `on_swap` instruction
+pub const SWAP_HOOK_DISCRIMINATOR: [u8; 8] = [201, 131, 123, 189, 167, 226, 124, 81];
+
 pub const MAX_MIGRATION_FEE_PERCENTAGE: u8 = 99;
 
 pub const MIN_LOCKED_LIQUIDITY_BPS: u16 = 1000; // 10%
@@ -124,6 +156,18 @@ pub mod seeds {
     pub const VIRTUAL_POOL_METADATA_PREFIX: &[u8] = b"virtual_pool_metadata";
     pub const BASE_LOCKER_PREFIX: &[u8] = b"base_locker";
     pub const OPERATOR_PREFIX: &[u8] = b"operator";
+    pub const WALLET_BUY_TRACKER_PREFIX: &[u8] = b"wallet_buy_tracker";
+    pub const PRESALE_ALLOWLIST_PREFIX: &[u8] = b"presale_allowlist";
+    pub const SWAP_HOOK_PREFIX: &[u8] = b"swap_hook";
+    pub const CONFIG_EXTENSION_PREFIX: &[u8] = b"config_extension";
+    pub const PROTOCOL_CONFIG_PREFIX: &[u8] = b"protocol_config";
+    pub const REFERRER_PREFIX: &[u8] = b"referrer";
+    pub const VOLUME_FEE_TIERS_PREFIX: &[u8] = b"volume_fee_tiers";
+    pub const WALLET_VOLUME_TRACKER_PREFIX: &[u8] = b"wallet_volume_tracker";
+    pub const CREATOR_FEE_SHARE_PREFIX: &[u8] = b"creator_fee_share";
+    pub const FEE_CLAIMER_TRANSFER_PREFIX: &[u8] = b"fee_claimer_transfer";
+    pub const ALLOWED_CREATOR_PREFIX: &[u8] = b"allowed_creator";
+    pub const POOL_OBSERVATIONS_PREFIX: &[u8] = b"pool_observations";
 }
 
 pub const MAX_OPERATION: u8 = 2; // Check OperatorPermission enum variants count
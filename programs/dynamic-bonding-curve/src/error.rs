@@ -209,6 +209,113 @@ pub enum PoolError {
 
     #[msg("Invalid compounding parameters")]
     InvalidCompoundingParameters,
+
+    #[msg("Invalid sqrt price limit")]
+    InvalidSqrtPriceLimit,
+
+    #[msg("Swap is expired")]
+    SwapExpired,
+
+    #[msg("Invalid wallet buy cap parameters")]
+    InvalidWalletBuyCapParameters,
+
+    #[msg("Wallet buy cap is exceeded")]
+    ExceededWalletBuyCap,
+
+    #[msg("Missing wallet buy tracker account")]
+    MissingWalletBuyTracker,
+
+    #[msg("Invalid allowlist parameters")]
+    InvalidAllowlistParameters,
+
+    #[msg("Allowlist phase is active, buying requires an allowlist proof")]
+    AllowlistPhaseActive,
+
+    #[msg("Missing presale allowlist account")]
+    MissingPresaleAllowlist,
+
+    #[msg("Invalid allowlist proof")]
+    InvalidAllowlistProof,
+
+    #[msg("Allowlist allocation is exceeded")]
+    ExceededAllowlistAllocation,
+
+    #[msg("Invalid swap batch legs")]
+    InvalidSwapBatchLegs,
+
+    #[msg("Native SOL swap is only supported for pool with native mint as quote token")]
+    InvalidNativeSolSwap,
+
+    #[msg("Missing system program account")]
+    MissingSystemProgram,
+
+    #[msg("Invalid swap hook")]
+    InvalidSwapHook,
+
+    #[msg("Missing swap hook account")]
+    MissingSwapHook,
+
+    #[msg("Invalid twap window")]
+    InvalidTwapWindow,
+
+    #[msg("Invalid sell base fee")]
+    InvalidSellBaseFee,
+
+    #[msg("Missing config extension account")]
+    MissingConfigExtension,
+
+    #[msg("Invalid protocol config")]
+    InvalidProtocolConfig,
+
+    #[msg("Invalid volume fee tiers")]
+    InvalidVolumeFeeTiers,
+
+    #[msg("Buyback and burn is not enabled")]
+    BuybackBurnNotEnabled,
+
+    #[msg("Invalid creator fee share")]
+    InvalidCreatorFeeShare,
+
+    #[msg("Creator trading fee is distributed by the creator fee share")]
+    CreatorFeeShareEnabled,
+
+    #[msg("Invalid fee claimer transfer")]
+    InvalidFeeClaimerTransfer,
+
+    #[msg("Circuit breaker is triggered")]
+    CircuitBreakerTriggered,
+
+    #[msg("Invalid fee step table")]
+    InvalidFeeStepTable,
+
+    #[msg("Invalid curve type")]
+    InvalidCurveType,
+
+    #[msg("Config is retired")]
+    ConfigRetired,
+
+    #[msg("Config is referenced by pools")]
+    ConfigInUse,
+
+    #[msg("Pool creator is not allowed by the partner")]
+    UnauthorizedPoolCreator,
+
+    #[msg("Creator allowlist is not enabled")]
+    CreatorAllowlistNotEnabled,
+
+    #[msg("Missing referrer token account of the fee mint")]
+    MissingReferrerTokenAccount,
+
+    #[msg("Missing associated token program account")]
+    MissingAssociatedTokenProgram,
+
+    #[msg("Invalid buyback parameters")]
+    InvalidBuybackParameters,
+
+    #[msg(
+        "Native SOL output can't be received in lamports with an existing native SOL token account"
+    )]
+    NativeSolTokenAccountExists,
 }
 
 impl From<ProtozolZapError> for PoolError {
@@ -6,7 +6,8 @@ use crate::{
         fee_parameters::PoolFeeParameters, liquidity_distribution::LiquidityDistributionParameters,
     },
     state::{SwapResult, SwapResult2},
-    ConfigParameters, LockedVestingParams, SwapParameters, SwapParameters2,
+    ConfigParameters, CreatorFeeShareRecipientParameters, LockedVestingParams,
+    ProtocolConfigParameters, SwapParameters, SwapParameters2, VolumeFeeTierParameters,
 };
 
 /// Create partner metadata
@@ -204,3 +205,147 @@ pub struct EvtClaimProtocolLiquidityMigrationFee {
     pub token_base_amount: u64,
     pub token_quote_amount: u64,
 }
+
+#[event]
+pub struct EvtCreatePresaleAllowlist {
+    pub config: Pubkey,
+    pub presale_allowlist: Pubkey,
+    pub merkle_root: [u8; 32],
+}
+
+#[event]
+pub struct EvtCreateSwapHook {
+    pub config: Pubkey,
+    pub swap_hook: Pubkey,
+    pub hook_program: Pubkey,
+}
+
+#[event]
+pub struct EvtCreateProtocolConfig {
+    pub protocol_config: Pubkey,
+    pub partner: Pubkey,
+    pub params: ProtocolConfigParameters,
+}
+
+#[event]
+pub struct EvtUpdateProtocolConfig {
+    pub protocol_config: Pubkey,
+    pub partner: Pubkey,
+    pub params: ProtocolConfigParameters,
+}
+
+#[event]
+pub struct EvtCreateReferrer {
+    pub referrer: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct EvtCreatePoolObservations {
+    pub pool: Pubkey,
+    pub pool_observations: Pubkey,
+}
+
+#[event]
+pub struct EvtClaimReferralFee {
+    pub referrer: Pubkey,
+    pub owner: Pubkey,
+    pub token_mint: Pubkey,
+    pub token_amount: u64,
+}
+
+#[event]
+pub struct EvtCreateVolumeFeeTiers {
+    pub config: Pubkey,
+    pub volume_fee_tiers: Pubkey,
+    pub tiers: Vec<VolumeFeeTierParameters>,
+}
+
+#[event]
+pub struct EvtUpdateVolumeFeeTiers {
+    pub config: Pubkey,
+    pub volume_fee_tiers: Pubkey,
+    pub tiers: Vec<VolumeFeeTierParameters>,
+}
+
+#[event]
+pub struct EvtCloseVolumeFeeTiers {
+    pub config: Pubkey,
+    pub volume_fee_tiers: Pubkey,
+}
+
+#[event]
+pub struct EvtBuybackAndBurn {
+    pub pool: Pubkey,
+    pub quote_amount: u64,
+    pub burned_base_amount: u64,
+    pub total_burned_base_amount: u64,
+    pub next_sqrt_price: u128,
+}
+
+#[event]
+pub struct EvtCreateCreatorFeeShare {
+    pub pool: Pubkey,
+    pub creator_fee_share: Pubkey,
+    pub recipients: Vec<CreatorFeeShareRecipientParameters>,
+}
+
+#[event]
+pub struct EvtDistributeCreatorFee {
+    pub pool: Pubkey,
+    pub creator_fee_share: Pubkey,
+    pub token_base_amount: u64,
+    pub token_quote_amount: u64,
+}
+
+#[event]
+pub struct EvtProposeFeeClaimer {
+    pub config: Pubkey,
+    pub fee_claimer: Pubkey,
+    pub new_fee_claimer: Pubkey,
+    pub new_leftover_receiver: Pubkey,
+}
+
+#[event]
+pub struct EvtCancelFeeClaimerTransfer {
+    pub config: Pubkey,
+    pub fee_claimer: Pubkey,
+    pub new_fee_claimer: Pubkey,
+    pub new_leftover_receiver: Pubkey,
+}
+
+#[event]
+pub struct EvtUpdateFeeClaimer {
+    pub config: Pubkey,
+    pub old_fee_claimer: Pubkey,
+    pub new_fee_claimer: Pubkey,
+    pub old_leftover_receiver: Pubkey,
+    pub new_leftover_receiver: Pubkey,
+}
+
+#[event]
+pub struct EvtRetireConfig {
+    pub config: Pubkey,
+    pub fee_claimer: Pubkey,
+}
+
+#[event]
+pub struct EvtCloseConfig {
+    pub config: Pubkey,
+    pub fee_claimer: Pubkey,
+    pub rent_receiver: Pubkey,
+}
+
+#[event]
+pub struct EvtCreateAllowedCreator {
+    pub config: Pubkey,
+    pub allowed_creator: Pubkey,
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct EvtCloseAllowedCreator {
+    pub config: Pubkey,
+    pub allowed_creator: Pubkey,
+    pub creator: Pubkey,
+}
@@ -0,0 +1,75 @@
+use crate::{
+    constants::seeds::PROTOCOL_CONFIG_PREFIX, state::ProtocolConfig, EvtCreateProtocolConfig,
+    PoolError,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct ProtocolConfigParameters {
+    /// protocol percentage of the trading fee
+    pub protocol_fee_percent: u8,
+    /// referral percentage of the protocol fee
+    pub referral_fee_percent: u8,
+    /// protocol percentage of the pool creation fee
+    pub protocol_pool_creation_fee_percent: u8,
+}
+
+impl ProtocolConfigParameters {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.protocol_fee_percent <= 100
+                && self.referral_fee_percent <= 100
+                && self.protocol_pool_creation_fee_percent <= 100,
+            PoolError::InvalidProtocolConfig
+        );
+        Ok(())
+    }
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(partner: Pubkey)]
+pub struct CreateProtocolConfigCtx<'info> {
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            PROTOCOL_CONFIG_PREFIX.as_ref(),
+            partner.as_ref(),
+        ],
+        bump,
+        space = 8 + ProtocolConfig::INIT_SPACE
+    )]
+    pub protocol_config: AccountLoader<'info, ProtocolConfig>,
+
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_protocol_config(
+    ctx: Context<CreateProtocolConfigCtx>,
+    partner: Pubkey,
+    params: ProtocolConfigParameters,
+) -> Result<()> {
+    params.validate()?;
+
+    let mut protocol_config = ctx.accounts.protocol_config.load_init()?;
+    protocol_config.initialize(
+        partner,
+        params.protocol_fee_percent,
+        params.referral_fee_percent,
+        params.protocol_pool_creation_fee_percent,
+    );
+
+    emit_cpi!(EvtCreateProtocolConfig {
+        protocol_config: ctx.accounts.protocol_config.key(),
+        partner,
+        params,
+    });
+
+    Ok(())
+}
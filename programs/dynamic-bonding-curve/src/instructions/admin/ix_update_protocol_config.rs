@@ -0,0 +1,34 @@
+use crate::{state::ProtocolConfig, EvtUpdateProtocolConfig, ProtocolConfigParameters};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateProtocolConfigCtx<'info> {
+    #[account(mut)]
+    pub protocol_config: AccountLoader<'info, ProtocolConfig>,
+
+    pub signer: Signer<'info>,
+}
+
+/// Only the configs created after the update use the new shares, existing configs keep their snapshot
+pub fn handle_update_protocol_config(
+    ctx: Context<UpdateProtocolConfigCtx>,
+    params: ProtocolConfigParameters,
+) -> Result<()> {
+    params.validate()?;
+
+    let mut protocol_config = ctx.accounts.protocol_config.load_mut()?;
+    protocol_config.update(
+        params.protocol_fee_percent,
+        params.referral_fee_percent,
+        params.protocol_pool_creation_fee_percent,
+    );
+
+    emit_cpi!(EvtUpdateProtocolConfig {
+        protocol_config: ctx.accounts.protocol_config.key(),
+        partner: protocol_config.partner,
+        params,
+    });
+
+    Ok(())
+}
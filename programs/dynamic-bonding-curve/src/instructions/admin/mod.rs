@@ -6,3 +6,7 @@ pub mod ix_create_operator_account;
 pub use ix_create_operator_account::*;
 pub mod ix_close_operator_account;
 pub use ix_close_operator_account::*;
+pub mod ix_create_protocol_config;
+pub use ix_create_protocol_config::*;
+pub mod ix_update_protocol_config;
+pub use ix_update_protocol_config::*;
@@ -3,7 +3,7 @@ use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
     const_pda, state::VirtualPool, token::transfer_token_from_pool_authority,
-    EvtClaimCreatorTradingFee,
+    EvtClaimCreatorTradingFee, PoolError,
 };
 
 /// Accounts for creator to claim trading fees
@@ -62,6 +62,18 @@ pub fn handle_claim_creator_trading_fee(
     max_quote_amount: u64,
 ) -> Result<()> {
     let mut pool = ctx.accounts.pool.load_mut()?;
+    require!(
+        !pool.is_creator_fee_share_enabled(),
+        PoolError::CreatorFeeShareEnabled
+    );
+
+    // creator quote fee is reserved for buyback and burn until the curve is complete
+    let max_quote_amount = if pool.is_creator_quote_fee_reserved_for_buyback()? {
+        0
+    } else {
+        max_quote_amount
+    };
+
     let (token_base_amount, token_quote_amount) =
         pool.claim_creator_trading_fee(max_base_amount, max_quote_amount)?;
 
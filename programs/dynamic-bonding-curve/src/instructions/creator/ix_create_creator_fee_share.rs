@@ -0,0 +1,97 @@
+use crate::constants::{
+    fee::MAX_BASIS_POINT, seeds::CREATOR_FEE_SHARE_PREFIX, MAX_CREATOR_FEE_SHARE_RECIPIENTS,
+};
+use crate::safe_math::SafeMath;
+use crate::state::{CreatorFeeShare, CreatorFeeShareRecipient, VirtualPool};
+use crate::{EvtCreateCreatorFeeShare, PoolError};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy)]
+pub struct CreatorFeeShareRecipientParameters {
+    /// recipient wallet, receives the creator trading fee in its associated or any token account owned by it
+    pub recipient: Pubkey,
+    /// share of the creator trading fee, in bps. The weights of all recipients must add up to 10000
+    pub weight_bps: u16,
+}
+
+/// Validate the recipients and pad them to MAX_CREATOR_FEE_SHARE_RECIPIENTS, recipients must be unique
+pub fn to_creator_fee_share_recipients(
+    recipients: &[CreatorFeeShareRecipientParameters],
+) -> Result<[CreatorFeeShareRecipient; MAX_CREATOR_FEE_SHARE_RECIPIENTS]> {
+    require!(
+        !recipients.is_empty() && recipients.len() <= MAX_CREATOR_FEE_SHARE_RECIPIENTS,
+        PoolError::InvalidCreatorFeeShare
+    );
+
+    let mut creator_fee_share_recipients =
+        [CreatorFeeShareRecipient::default(); MAX_CREATOR_FEE_SHARE_RECIPIENTS];
+    let mut total_weight_bps = 0u64;
+    for (i, recipient) in recipients.iter().enumerate() {
+        let is_unique = recipients[..i]
+            .iter()
+            .all(|other| other.recipient != recipient.recipient);
+        require!(
+            is_unique && recipient.recipient != Pubkey::default() && recipient.weight_bps > 0,
+            PoolError::InvalidCreatorFeeShare
+        );
+        total_weight_bps = total_weight_bps.safe_add(recipient.weight_bps.into())?;
+        creator_fee_share_recipients[i] = CreatorFeeShareRecipient {
+            recipient: recipient.recipient,
+            weight_bps: recipient.weight_bps,
+            ..Default::default()
+        };
+    }
+    require!(
+        total_weight_bps == MAX_BASIS_POINT,
+        PoolError::InvalidCreatorFeeShare
+    );
+    Ok(creator_fee_share_recipients)
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateCreatorFeeShareCtx<'info> {
+    #[account(mut)]
+    pub pool: AccountLoader<'info, VirtualPool>,
+
+    #[account(
+        init,
+        seeds = [
+            CREATOR_FEE_SHARE_PREFIX.as_ref(),
+            pool.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = 8 + CreatorFeeShare::INIT_SPACE
+    )]
+    pub creator_fee_share: AccountLoader<'info, CreatorFeeShare>,
+
+    pub creator: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_creator_fee_share(
+    ctx: Context<CreateCreatorFeeShareCtx>,
+    recipients: Vec<CreatorFeeShareRecipientParameters>,
+) -> Result<()> {
+    let creator_fee_share_recipients = to_creator_fee_share_recipients(&recipients)?;
+
+    let mut creator_fee_share = ctx.accounts.creator_fee_share.load_init()?;
+    creator_fee_share.initialize(ctx.accounts.pool.key(), creator_fee_share_recipients);
+
+    // the creator can't claim the trading fee anymore, it can only be distributed to the recipients
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.has_creator_fee_share = 1;
+
+    emit_cpi!(EvtCreateCreatorFeeShare {
+        pool: ctx.accounts.pool.key(),
+        creator_fee_share: ctx.accounts.creator_fee_share.key(),
+        recipients,
+    });
+
+    Ok(())
+}
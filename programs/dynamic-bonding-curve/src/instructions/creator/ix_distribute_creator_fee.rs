@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    const_pda,
+    state::{CreatorFeeShare, VirtualPool},
+    token::transfer_token_from_pool_authority,
+    EvtDistributeCreatorFee, PoolError,
+};
+
+/// Accounts for distribute creator trading fee, the base and quote token accounts of each recipient are passed in remaining accounts, in the order of the recipients
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DistributeCreatorFeeCtx<'info> {
+    /// CHECK: pool authority
+    #[account(
+        address = const_pda::pool_authority::ID
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = base_vault,
+        has_one = quote_vault,
+        has_one = base_mint,
+    )]
+    pub pool: AccountLoader<'info, VirtualPool>,
+
+    #[account(has_one = pool)]
+    pub creator_fee_share: AccountLoader<'info, CreatorFeeShare>,
+
+    /// The vault token account for base token
+    #[account(mut, token::token_program = token_base_program, token::mint = base_mint)]
+    pub base_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for quote token
+    #[account(mut, token::token_program = token_quote_program, token::mint = quote_mint)]
+    pub quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of base token
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of quote token
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token base program
+    pub token_base_program: Interface<'info, TokenInterface>,
+
+    /// Token quote program
+    pub token_quote_program: Interface<'info, TokenInterface>,
+}
+
+/// Permissionless, split the creator trading fee between the recipients of the creator fee share
+pub fn handle_distribute_creator_fee<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, DistributeCreatorFeeCtx<'info>>,
+) -> Result<()> {
+    let creator_fee_share = ctx.accounts.creator_fee_share.load()?;
+    let recipients = creator_fee_share.get_recipients();
+    require!(
+        ctx.remaining_accounts.len() == recipients.len() * 2,
+        PoolError::InvalidAccount
+    );
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    // creator quote fee is reserved for buyback and burn until the curve is complete
+    let max_quote_amount = if pool.is_creator_quote_fee_reserved_for_buyback()? {
+        0
+    } else {
+        u64::MAX
+    };
+    let (token_base_amount, token_quote_amount) =
+        pool.claim_creator_trading_fee(u64::MAX, max_quote_amount)?;
+    drop(pool);
+
+    let base_amounts = creator_fee_share.split_fee(token_base_amount)?;
+    let quote_amounts = creator_fee_share.split_fee(token_quote_amount)?;
+
+    for (i, (recipient, token_accounts)) in recipients
+        .iter()
+        .zip(ctx.remaining_accounts.chunks(2))
+        .enumerate()
+    {
+        let recipient_transfers = [
+            (
+                &token_accounts[0],
+                &ctx.accounts.base_mint,
+                &ctx.accounts.base_vault,
+                &ctx.accounts.token_base_program,
+                base_amounts[i],
+            ),
+            (
+                &token_accounts[1],
+                &ctx.accounts.quote_mint,
+                &ctx.accounts.quote_vault,
+                &ctx.accounts.token_quote_program,
+                quote_amounts[i],
+            ),
+        ];
+        for (token_account_info, mint, vault, token_program, amount) in recipient_transfers {
+            let token_account: InterfaceAccount<'info, TokenAccount> =
+                InterfaceAccount::try_from(token_account_info)?;
+            require!(
+                token_account.owner == recipient.recipient && token_account.mint == mint.key(),
+                PoolError::InvalidAccount
+            );
+
+            transfer_token_from_pool_authority(
+                ctx.accounts.pool_authority.to_account_info(),
+                mint,
+                vault,
+                token_account_info.clone(),
+                token_program,
+                amount,
+            )?;
+        }
+    }
+
+    emit_cpi!(EvtDistributeCreatorFee {
+        pool: ctx.accounts.pool.key(),
+        creator_fee_share: ctx.accounts.creator_fee_share.key(),
+        token_base_amount,
+        token_quote_amount,
+    });
+
+    Ok(())
+}
@@ -6,3 +6,7 @@ pub mod ix_withdraw_creator_surplus;
 pub use ix_withdraw_creator_surplus::*;
 pub mod ix_transfer_pool_creator;
 pub use ix_transfer_pool_creator::*;
+pub mod ix_create_creator_fee_share;
+pub use ix_create_creator_fee_share::*;
+pub mod ix_distribute_creator_fee;
+pub use ix_distribute_creator_fee::*;
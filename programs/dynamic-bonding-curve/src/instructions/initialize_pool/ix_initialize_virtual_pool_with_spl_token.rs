@@ -20,7 +20,7 @@ use crate::{
     process_create_token_metadata,
     state::{fee::VolatilityTracker, PoolConfig, PoolType, TokenType, VirtualPool},
     token::transfer_lamports_from_user,
-    EvtInitializePool, PoolError, ProcessCreateTokenMetadataParams,
+    validate_pool_creator, EvtInitializePool, PoolError, ProcessCreateTokenMetadataParams,
 };
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -43,7 +43,7 @@ pub fn min_key(left: &Pubkey, right: &Pubkey) -> [u8; 32] {
 #[derive(Accounts)]
 pub struct InitializeVirtualPoolWithSplTokenCtx<'info> {
     /// Which config the pool belongs to.
-    #[account(has_one = quote_mint)]
+    #[account(mut, has_one = quote_mint)]
     pub config: AccountLoader<'info, PoolConfig>,
 
     /// CHECK: pool authority
@@ -141,7 +141,7 @@ pub fn handle_initialize_virtual_pool_with_spl_token<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, InitializeVirtualPoolWithSplTokenCtx<'info>>,
     params: InitializePoolParameters,
 ) -> Result<()> {
-    let config = ctx.accounts.config.load()?;
+    let mut config = ctx.accounts.config.load_mut()?;
 
     require!(
         config.get_total_liquidity_locked_bps_at_n_seconds(SECONDS_PER_DAY)?
@@ -152,6 +152,19 @@ pub fn handle_initialize_virtual_pool_with_spl_token<'c: 'info, 'info>(
     // validate min base fee
     config.pool_fees.base_fee.validate_min_base_fee()?;
 
+    // the fee claimer retired the config
+    require!(!config.is_retired(), PoolError::ConfigRetired);
+
+    if config.is_creator_allowlist_enabled() {
+        validate_pool_creator(
+            &ctx.accounts.config.key(),
+            &config.fee_claimer,
+            &ctx.accounts.creator.key(),
+            &ctx.accounts.payer.key(),
+            ctx.remaining_accounts,
+        )?;
+    }
+
     let initial_base_supply = config.get_initial_base_supply()?;
 
     let token_type_value =
@@ -228,6 +241,9 @@ pub fn handle_initialize_virtual_pool_with_spl_token<'c: 'info, 'info>(
         )?;
     }
 
+    // the config can only be closed while no pool references it
+    config.increase_pool_count()?;
+
     // init pool
     let mut pool = ctx.accounts.pool.load_init()?;
 
@@ -245,6 +261,7 @@ pub fn handle_initialize_virtual_pool_with_spl_token<'c: 'info, 'info>(
         activation_point,
         initial_base_supply,
         PROTOCOL_LIQUIDITY_MIGRATION_FEE_BPS,
+        config.is_creator_quote_fee_bought_back().into(),
     );
 
     emit_cpi!(EvtInitializePool {
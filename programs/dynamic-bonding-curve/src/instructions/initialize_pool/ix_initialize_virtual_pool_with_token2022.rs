@@ -1,5 +1,5 @@
 use super::InitializePoolParameters;
-use super::{max_key, min_key};
+use super::{max_key, min_key, validate_pool_creator};
 use crate::constants::fee::PROTOCOL_LIQUIDITY_MIGRATION_FEE_BPS;
 use crate::constants::MIN_LOCKED_LIQUIDITY_BPS;
 use crate::token::transfer_lamports_from_user;
@@ -28,7 +28,7 @@ use anchor_spl::{
 #[derive(Accounts)]
 pub struct InitializeVirtualPoolWithToken2022Ctx<'info> {
     /// Which config the pool belongs to.
-    #[account(has_one = quote_mint)]
+    #[account(mut, has_one = quote_mint)]
     pub config: AccountLoader<'info, PoolConfig>,
 
     /// CHECK: pool authority
@@ -120,7 +120,7 @@ pub fn handle_initialize_virtual_pool_with_token2022<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, InitializeVirtualPoolWithToken2022Ctx<'info>>,
     params: InitializePoolParameters,
 ) -> Result<()> {
-    let config = ctx.accounts.config.load()?;
+    let mut config = ctx.accounts.config.load_mut()?;
 
     require!(
         config.get_total_liquidity_locked_bps_at_n_seconds(SECONDS_PER_DAY)?
@@ -131,6 +131,19 @@ pub fn handle_initialize_virtual_pool_with_token2022<'c: 'info, 'info>(
     // validate min base fee
     config.pool_fees.base_fee.validate_min_base_fee()?;
 
+    // the fee claimer retired the config
+    require!(!config.is_retired(), PoolError::ConfigRetired);
+
+    if config.is_creator_allowlist_enabled() {
+        validate_pool_creator(
+            &ctx.accounts.config.key(),
+            &config.fee_claimer,
+            &ctx.accounts.creator.key(),
+            &ctx.accounts.payer.key(),
+            ctx.remaining_accounts,
+        )?;
+    }
+
     let token_type_value =
         TokenType::try_from(config.token_type).map_err(|_| PoolError::InvalidTokenType)?;
     require!(
@@ -203,7 +216,6 @@ pub fn handle_initialize_virtual_pool_with_token2022<'c: 'info, 'info>(
         new_update_token_metadata_authority,
     )?;
 
-    let config = ctx.accounts.config.load()?;
     let initial_base_supply = config.get_initial_base_supply()?;
 
     // mint token
@@ -248,6 +260,9 @@ pub fn handle_initialize_virtual_pool_with_token2022<'c: 'info, 'info>(
         )?;
     }
 
+    // the config can only be closed while no pool references it
+    config.increase_pool_count()?;
+
     // init pool
     let mut pool = ctx.accounts.pool.load_init()?;
 
@@ -265,6 +280,7 @@ pub fn handle_initialize_virtual_pool_with_token2022<'c: 'info, 'info>(
         activation_point,
         initial_base_supply,
         PROTOCOL_LIQUIDITY_MIGRATION_FEE_BPS,
+        config.is_creator_quote_fee_bought_back().into(),
     );
 
     emit_cpi!(EvtInitializePool {
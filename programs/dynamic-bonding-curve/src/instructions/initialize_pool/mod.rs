@@ -4,3 +4,5 @@ pub mod ix_initialize_virtual_pool_with_token2022;
 pub use ix_initialize_virtual_pool_with_token2022::*;
 pub mod process_create_token_metadata;
 pub use process_create_token_metadata::*;
+pub mod validate_pool_creator;
+pub use validate_pool_creator::*;
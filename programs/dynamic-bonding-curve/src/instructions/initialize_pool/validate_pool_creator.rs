@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::{instructions::find_program_account, state::AllowedCreator, PoolError};
+
+/// Pools of a config with creator allowlist can only be created with the fee claimer signature, or by a creator allowed by the partner.
+/// The fee claimer can sign as creator or payer, the fee claimer signer or the AllowedCreator account can be at any position of remaining accounts
+pub fn validate_pool_creator<'c: 'info, 'info>(
+    config: &Pubkey,
+    fee_claimer: &Pubkey,
+    creator: &Pubkey,
+    payer: &Pubkey,
+    remaining_accounts: &'c [AccountInfo<'info>],
+) -> Result<()> {
+    if creator.eq(fee_claimer) || payer.eq(fee_claimer) {
+        return Ok(());
+    }
+
+    let is_signed_by_fee_claimer = remaining_accounts
+        .iter()
+        .any(|account_info| account_info.is_signer && account_info.key.eq(fee_claimer));
+    if is_signed_by_fee_claimer {
+        return Ok(());
+    }
+
+    find_program_account::<AllowedCreator>(remaining_accounts, false, |allowed_creator| {
+        allowed_creator.config.eq(config) && allowed_creator.creator.eq(creator)
+    })?
+    .ok_or(PoolError::UnauthorizedPoolCreator)?;
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use crate::state::{FeeClaimerTransfer, PoolConfig};
+use crate::{EvtUpdateFeeClaimer, PoolError};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptFeeClaimerCtx<'info> {
+    #[account(mut)]
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        has_one = config,
+        has_one = new_fee_claimer,
+        close = rent_receiver
+    )]
+    pub fee_claimer_transfer: AccountLoader<'info, FeeClaimerTransfer>,
+
+    pub new_fee_claimer: Signer<'info>,
+
+    /// CHECK: Account to receive rent fee, must be the payer of the transfer
+    #[account(
+        mut,
+        constraint = rent_receiver.key() == fee_claimer_transfer.load()?.payer @ PoolError::InvalidAccount,
+    )]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+pub fn handle_accept_fee_claimer(ctx: Context<AcceptFeeClaimerCtx>) -> Result<()> {
+    let new_leftover_receiver = ctx
+        .accounts
+        .fee_claimer_transfer
+        .load()?
+        .new_leftover_receiver;
+
+    let mut config = ctx.accounts.config.load_mut()?;
+    let old_fee_claimer = config.fee_claimer;
+    let old_leftover_receiver = config.leftover_receiver;
+    config.update_fee_claimer(ctx.accounts.new_fee_claimer.key(), new_leftover_receiver);
+
+    emit_cpi!(EvtUpdateFeeClaimer {
+        config: ctx.accounts.config.key(),
+        old_fee_claimer,
+        new_fee_claimer: config.fee_claimer,
+        old_leftover_receiver,
+        new_leftover_receiver: config.leftover_receiver,
+    });
+
+    Ok(())
+}
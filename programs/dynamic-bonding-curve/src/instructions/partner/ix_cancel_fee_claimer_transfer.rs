@@ -0,0 +1,38 @@
+use crate::state::{FeeClaimerTransfer, PoolConfig};
+use crate::{EvtCancelFeeClaimerTransfer, PoolError};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelFeeClaimerTransferCtx<'info> {
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        has_one = config,
+        close = rent_receiver
+    )]
+    pub fee_claimer_transfer: AccountLoader<'info, FeeClaimerTransfer>,
+
+    pub fee_claimer: Signer<'info>,
+
+    /// CHECK: Account to receive rent fee, must be the payer of the transfer
+    #[account(
+        mut,
+        constraint = rent_receiver.key() == fee_claimer_transfer.load()?.payer @ PoolError::InvalidAccount,
+    )]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+pub fn handle_cancel_fee_claimer_transfer(ctx: Context<CancelFeeClaimerTransferCtx>) -> Result<()> {
+    let fee_claimer_transfer = ctx.accounts.fee_claimer_transfer.load()?;
+
+    emit_cpi!(EvtCancelFeeClaimerTransfer {
+        config: ctx.accounts.config.key(),
+        fee_claimer: ctx.accounts.fee_claimer.key(),
+        new_fee_claimer: fee_claimer_transfer.new_fee_claimer,
+        new_leftover_receiver: fee_claimer_transfer.new_leftover_receiver,
+    });
+
+    Ok(())
+}
@@ -67,7 +67,18 @@ pub fn handle_claim_trading_fee(
     max_base_amount: u64,
     max_quote_amount: u64,
 ) -> Result<()> {
+    let config = ctx.accounts.config.load()?;
     let mut pool = ctx.accounts.pool.load_mut()?;
+
+    // partner quote fee is reserved for buyback and burn until the curve is complete
+    let max_quote_amount = if config.is_partner_quote_fee_bought_back()
+        && !pool.is_curve_complete(config.migration_quote_threshold)
+    {
+        0
+    } else {
+        max_quote_amount
+    };
+
     let (token_base_amount, token_quote_amount) =
         pool.claim_partner_trading_fee(max_base_amount, max_quote_amount)?;
 
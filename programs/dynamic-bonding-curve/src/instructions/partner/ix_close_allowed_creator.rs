@@ -0,0 +1,34 @@
+use crate::state::{AllowedCreator, PoolConfig};
+use crate::EvtCloseAllowedCreator;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseAllowedCreatorCtx<'info> {
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        has_one = config,
+        close = rent_receiver
+    )]
+    pub allowed_creator: AccountLoader<'info, AllowedCreator>,
+
+    pub fee_claimer: Signer<'info>,
+
+    /// CHECK: Account to receive rent fee
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+pub fn handle_close_allowed_creator(ctx: Context<CloseAllowedCreatorCtx>) -> Result<()> {
+    let creator = ctx.accounts.allowed_creator.load()?.creator;
+
+    emit_cpi!(EvtCloseAllowedCreator {
+        config: ctx.accounts.config.key(),
+        allowed_creator: ctx.accounts.allowed_creator.key(),
+        creator,
+    });
+
+    Ok(())
+}
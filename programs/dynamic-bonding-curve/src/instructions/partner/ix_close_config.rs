@@ -0,0 +1,100 @@
+use crate::state::{
+    AllowedCreator, FeeClaimerTransfer, PoolConfig, PoolConfigExtension, PresaleAllowlist,
+    SwapHook, VolumeFeeTiers,
+};
+use crate::{EvtCloseConfig, PoolError};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseConfigCtx<'info> {
+    #[account(
+        mut,
+        close = rent_receiver
+    )]
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    /// config extension, required when the config has one
+    #[account(
+        mut,
+        has_one = config,
+        close = rent_receiver
+    )]
+    pub config_extension: Option<AccountLoader<'info, PoolConfigExtension>>,
+
+    /// swap hook, required when the config has one
+    #[account(
+        mut,
+        has_one = config,
+        close = rent_receiver
+    )]
+    pub swap_hook: Option<AccountLoader<'info, SwapHook>>,
+
+    /// presale allowlist, required when the config has the allowlist phase
+    #[account(
+        mut,
+        has_one = config,
+        close = rent_receiver
+    )]
+    pub presale_allowlist: Option<AccountLoader<'info, PresaleAllowlist>>,
+
+    #[account(
+        mut,
+        has_one = config,
+        close = rent_receiver
+    )]
+    pub volume_fee_tiers: Option<AccountLoader<'info, VolumeFeeTiers>>,
+
+    /// pending fee claimer transfer, its rent goes back to its payer
+    #[account(
+        mut,
+        has_one = config,
+        constraint = fee_claimer_transfer.load()?.payer == rent_receiver.key() @ PoolError::InvalidAccount,
+        close = rent_receiver
+    )]
+    pub fee_claimer_transfer: Option<AccountLoader<'info, FeeClaimerTransfer>>,
+
+    pub fee_claimer: Signer<'info>,
+
+    /// CHECK: Account to receive rent fee
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+/// Close a config that no pool references and the accounts of the config. AllowedCreator accounts of the config can be closed by passing them as writable remaining accounts
+pub fn handle_close_config<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, CloseConfigCtx<'info>>,
+) -> Result<()> {
+    let config = ctx.accounts.config.load()?;
+    require!(config.is_closable(), PoolError::ConfigInUse);
+    require!(
+        !config.has_config_extension() || ctx.accounts.config_extension.is_some(),
+        PoolError::MissingConfigExtension
+    );
+    require!(
+        !config.is_swap_hook_enabled() || ctx.accounts.swap_hook.is_some(),
+        PoolError::MissingSwapHook
+    );
+    require!(
+        config.allowlist_duration == 0 || ctx.accounts.presale_allowlist.is_some(),
+        PoolError::MissingPresaleAllowlist
+    );
+
+    let config_key = ctx.accounts.config.key();
+    for account_info in ctx.remaining_accounts.iter() {
+        let allowed_creator = AccountLoader::<AllowedCreator>::try_from(account_info)?;
+        require!(
+            allowed_creator.load()?.config.eq(&config_key),
+            PoolError::InvalidAccount
+        );
+        allowed_creator.close(ctx.accounts.rent_receiver.to_account_info())?;
+    }
+
+    emit_cpi!(EvtCloseConfig {
+        config: config_key,
+        fee_claimer: ctx.accounts.fee_claimer.key(),
+        rent_receiver: ctx.accounts.rent_receiver.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use crate::state::{PoolConfig, VolumeFeeTiers};
+use crate::EvtCloseVolumeFeeTiers;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseVolumeFeeTiersCtx<'info> {
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        has_one = config,
+        close = rent_receiver
+    )]
+    pub volume_fee_tiers: AccountLoader<'info, VolumeFeeTiers>,
+
+    pub fee_claimer: Signer<'info>,
+
+    /// CHECK: Account to receive rent fee
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+/// Stop the volume fee discounts of the config
+pub fn handle_close_volume_fee_tiers(ctx: Context<CloseVolumeFeeTiersCtx>) -> Result<()> {
+    emit_cpi!(EvtCloseVolumeFeeTiers {
+        config: ctx.accounts.config.key(),
+        volume_fee_tiers: ctx.accounts.volume_fee_tiers.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use crate::constants::seeds::ALLOWED_CREATOR_PREFIX;
+use crate::state::{AllowedCreator, PoolConfig};
+use crate::{EvtCreateAllowedCreator, PoolError};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateAllowedCreatorCtx<'info> {
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    #[account(
+        init,
+        seeds = [
+            ALLOWED_CREATOR_PREFIX.as_ref(),
+            config.key().as_ref(),
+            creator.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = 8 + AllowedCreator::INIT_SPACE
+    )]
+    pub allowed_creator: AccountLoader<'info, AllowedCreator>,
+
+    /// CHECK: pool creator allowed by the partner
+    pub creator: UncheckedAccount<'info>,
+
+    pub fee_claimer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_allowed_creator(ctx: Context<CreateAllowedCreatorCtx>) -> Result<()> {
+    let config = ctx.accounts.config.load()?;
+    require!(
+        config.is_creator_allowlist_enabled(),
+        PoolError::CreatorAllowlistNotEnabled
+    );
+
+    let mut allowed_creator = ctx.accounts.allowed_creator.load_init()?;
+    allowed_creator.initialize(ctx.accounts.config.key(), ctx.accounts.creator.key());
+
+    emit_cpi!(EvtCreateAllowedCreator {
+        config: ctx.accounts.config.key(),
+        allowed_creator: ctx.accounts.allowed_creator.key(),
+        creator: ctx.accounts.creator.key(),
+    });
+
+    Ok(())
+}
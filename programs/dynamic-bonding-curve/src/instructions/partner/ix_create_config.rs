@@ -10,7 +10,13 @@ use crate::{
     activation_handler::ActivationType,
     constants::{
         fee::{MAX_POOL_CREATION_FEE, MIN_POOL_CREATION_FEE, PROTOCOL_LIQUIDITY_MIGRATION_FEE_BPS},
-        MAX_CURVE_POINT, MAX_LOCK_DURATION_IN_SECONDS, MAX_MIGRATED_POOL_FEE_BPS,
+        seeds::{
+            CONFIG_EXTENSION_PREFIX, PRESALE_ALLOWLIST_PREFIX, PROTOCOL_CONFIG_PREFIX,
+            SWAP_HOOK_PREFIX,
+        },
+        MAX_ALLOWLIST_DURATION_IN_SECONDS, MAX_ALLOWLIST_DURATION_IN_SLOTS,
+        MAX_BUY_CAP_DURATION_IN_SECONDS, MAX_BUY_CAP_DURATION_IN_SLOTS, MAX_CURVE_POINT,
+        MAX_FEE_STEPS, MAX_LOCK_DURATION_IN_SECONDS, MAX_MIGRATED_POOL_FEE_BPS,
         MAX_MIGRATION_FEE_PERCENTAGE, MAX_SQRT_PRICE, MIN_LOCKED_LIQUIDITY_BPS,
         MIN_MIGRATED_POOL_FEE_BPS, MIN_SQRT_PRICE,
     },
@@ -22,20 +28,23 @@ use crate::{
         get_migration_handler, CompoundingLiquidity, MigratedCollectFeeMode, MigrationHandler,
     },
     params::{
-        fee_parameters::{to_numerator, PoolFeeParameters},
+        config_violations::ConfigViolations,
+        fee_parameters::{to_numerator, BaseFeeParameters, PoolFeeParameters},
         liquidity_distribution::{
             get_base_token_for_swap, get_migration_threshold_price, LiquidityDistributionParameters,
         },
     },
+    price_curve::PriceCurve,
     safe_math::{SafeCast, SafeMath},
     state::{
-        CollectFeeMode, LiquidityVestingInfo, LockedVestingConfig, MigrationFeeOption,
-        MigrationOption, PoolConfig, TokenAuthorityOption, TokenType,
+        BaseFeeMode, BuybackFeeSource, CollectFeeMode, CurveType, FeeStep, LiquidityVestingInfo,
+        LockedVestingConfig, MigrationFeeOption, MigrationOption, PoolConfig, PoolConfigExtension,
+        PresaleAllowlist, ProtocolConfig, SwapHook, TokenAuthorityOption, TokenType,
     },
     token::{get_token_program_flags, is_supported_quote_mint},
     u128x128_math::Rounding,
     utils_math::safe_mul_div_cast_u128,
-    EvtCreateConfig, EvtCreateConfigV2, PoolError,
+    EvtCreateConfig, EvtCreateConfigV2, EvtCreatePresaleAllowlist, EvtCreateSwapHook, PoolError,
 };
 
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
@@ -67,11 +76,37 @@ pub struct ConfigParameters {
     pub migrated_pool_market_cap_fee_scheduler_params: MigratedPoolMarketCapFeeSchedulerParams,
     pub enable_first_swap_with_min_fee: bool,
     pub compounding_fee_bps: u16,
-    /// padding for future use
-    pub padding: [u8; 2],
+    /// max quote amount (included fee) a wallet can spend to buy base token in the buy cap window, 0 means no cap
+    pub max_buy_amount_per_wallet: u64,
+    /// duration of the buy cap window from activation point, in slot or timestamp depends on activation type
+    pub buy_cap_duration: u32,
+    /// duration of the allowlist phase from activation point, in slot or timestamp depends on activation type, 0 means no allowlist phase
+    pub allowlist_duration: u32,
+    /// merkle root of the allowlisted wallets and their allocations, only set when the allowlist phase is enabled. Stored in the presale allowlist
+    pub allowlist_merkle_root: [u8; 32],
+    /// fee steps of the FeeStepTable base fee mode, sorted by elapsed point and the first one starts at activation. Stored in the config extension
+    pub fee_steps: Vec<FeeStepParameters>,
+    /// curve type, the linear and exponential price curves have a single curve point: the end sqrt price and the base amount sold on the curve
+    pub curve_type: u8,
+    /// only the creators allowed by the partner (AllowedCreator account) or co-signed by the fee claimer can create pools
+    pub enable_creator_allowlist: bool,
+    /// share of the quote trading fee that is bought back and burned, only set in the QuoteTokenBuybackBurn collect fee mode. 0: partner, 1: creator, 2: partner and creator
+    pub buyback_fee_source: u8,
+    /// max quote fee the buyback can spend in a buyback period, only set in the QuoteTokenBuybackBurn collect fee mode. Stored in the config extension
+    pub buyback_max_quote_amount_per_period: u64,
+    /// duration of a buyback period, in slot or timestamp depends on activation type. Stored in the config extension
+    pub buyback_period: u64,
     pub curve: Vec<LiquidityDistributionParameters>,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, InitSpace)]
+pub struct FeeStepParameters {
+    /// points elapsed since activation from which the fee numerator is charged, in slot or timestamp depends on activation type
+    pub elapsed_point: u64,
+    pub fee_numerator: u64,
+}
+const_assert_eq!(FeeStepParameters::INIT_SPACE, 16);
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, InitSpace)]
 pub struct MigrationFee {
     pub fee_percentage: u8,
@@ -81,22 +116,28 @@ const_assert_eq!(MigrationFee::INIT_SPACE, 2);
 
 impl MigrationFee {
     pub fn validate(&self) -> Result<()> {
-        require!(
+        let mut violations = ConfigViolations::default();
+        self.check(&mut violations);
+        violations.into_result()
+    }
+
+    pub fn check(&self, violations: &mut ConfigViolations) {
+        violations.check(
             self.fee_percentage <= MAX_MIGRATION_FEE_PERCENTAGE,
-            PoolError::InvalidMigratorFeePercentage
+            PoolError::InvalidMigratorFeePercentage,
+            "migration_fee.fee_percentage",
+            format_args!("0 to {MAX_MIGRATION_FEE_PERCENTAGE}"),
+        );
+        violations.check(
+            if self.fee_percentage == 0 {
+                self.creator_fee_percentage == 0
+            } else {
+                self.creator_fee_percentage <= 100
+            },
+            PoolError::InvalidMigratorFeePercentage,
+            "migration_fee.creator_fee_percentage",
+            "0 to 100, 0 when fee_percentage is 0",
         );
-        if self.fee_percentage == 0 {
-            require!(
-                self.creator_fee_percentage == 0,
-                PoolError::InvalidMigratorFeePercentage
-            );
-        } else {
-            require!(
-                self.creator_fee_percentage <= 100,
-                PoolError::InvalidMigratorFeePercentage
-            );
-        }
-        Ok(())
     }
 }
 
@@ -146,78 +187,121 @@ impl MigratedPoolFeeValidator {
     }
 
     pub fn validate(&self) -> Result<()> {
-        require!(
+        let mut violations = ConfigViolations::default();
+        self.check(&mut violations);
+        violations.into_result()
+    }
+
+    pub fn check(&self, violations: &mut ConfigViolations) {
+        let violation_count = violations.len();
+
+        violations.check(
             self.pool_fee_bps >= MIN_MIGRATED_POOL_FEE_BPS
                 && self.pool_fee_bps <= MAX_MIGRATED_POOL_FEE_BPS,
-            PoolError::InvalidMigratedPoolFee
+            PoolError::InvalidMigratedPoolFee,
+            "migrated_pool_fee.pool_fee_bps",
+            format_args!("{MIN_MIGRATED_POOL_FEE_BPS} to {MAX_MIGRATED_POOL_FEE_BPS}"),
         );
 
         // validate collect fee mode
-        let migrated_collect_fee_mode = MigratedCollectFeeMode::try_from(self.collect_fee_mode)
-            .map_err(|_| PoolError::InvalidCollectFeeMode)?;
-
+        let migrated_collect_fee_mode =
+            MigratedCollectFeeMode::try_from(self.collect_fee_mode).ok();
+        violations.check(
+            migrated_collect_fee_mode.is_some(),
+            PoolError::InvalidCollectFeeMode,
+            "migrated_pool_fee.collect_fee_mode",
+            "0 (quote token), 1 (output token) or 2 (compounding)",
+        );
         match migrated_collect_fee_mode {
-            MigratedCollectFeeMode::Compounding => {
-                require!(
+            Some(MigratedCollectFeeMode::Compounding) => {
+                violations.check(
                     self.compounding_fee_bps > 0 && self.compounding_fee_bps <= MAX_BASIS_POINT,
-                    PoolError::InvalidMigratedPoolFee
+                    PoolError::InvalidMigratedPoolFee,
+                    "compounding_fee_bps",
+                    format_args!("1 to {MAX_BASIS_POINT} with compounding collect fee mode"),
                 );
             }
-            _ => {
-                require!(
+            Some(_) => {
+                violations.check(
                     self.compounding_fee_bps == 0,
-                    PoolError::InvalidMigratedPoolFee
+                    PoolError::InvalidMigratedPoolFee,
+                    "compounding_fee_bps",
+                    "0 unless the migrated collect fee mode is compounding (2)",
                 );
             }
+            None => {}
         }
         // validate migrated dynamic fee option
-        require!(
+        violations.check(
             DammV2DynamicFee::try_from(self.dynamic_fee).is_ok(),
-            PoolError::InvalidMigratedPoolFee
+            PoolError::InvalidMigratedPoolFee,
+            "migrated_pool_fee.dynamic_fee",
+            "0 (disabled) or 1 (enabled)",
         );
 
-        let migrated_base_fee_mode = DammV2BaseFeeMode::try_from(self.migrated_pool_base_fee_mode)
-            .map_err(|_| PoolError::TypeCastFailed)?;
-
-        match migrated_base_fee_mode {
+        match DammV2BaseFeeMode::try_from(self.migrated_pool_base_fee_mode) {
             // Old behavior is fixed fee bps for migrated pool
-            DammV2BaseFeeMode::FeeTimeSchedulerLinear
-            | DammV2BaseFeeMode::FeeTimeSchedulerExponential => {
-                require!(
+            Ok(
+                DammV2BaseFeeMode::FeeTimeSchedulerLinear
+                | DammV2BaseFeeMode::FeeTimeSchedulerExponential,
+            ) => {
+                violations.check(
                     self.number_of_period == 0
                         && self.sqrt_price_step_bps == 0
                         && self.scheduler_expiration_duration == 0
                         && self.reduction_factor == 0,
-                    PoolError::InvalidMigratedPoolFee
+                    PoolError::InvalidMigratedPoolFee,
+                    "migrated_pool_market_cap_fee_scheduler_params",
+                    "zero with time scheduler migrated pool base fee mode",
                 );
             }
-            DammV2BaseFeeMode::FeeMarketCapSchedulerExponential
-            | DammV2BaseFeeMode::FeeMarketCapSchedulerLinear => {
-                let cliff_fee_numerator = to_numerator(
-                    self.pool_fee_bps.into(),
-                    damm_v2::constants::FEE_DENOMINATOR.into(),
-                )?;
-
-                let market_cap_fee_scheduler = DammV2PodAlignedFeeMarketCapScheduler(
-                    damm_v2::accounts::PodAlignedFeeMarketCapScheduler {
-                        cliff_fee_numerator,
-                        base_fee_mode: self.migrated_pool_base_fee_mode,
-                        number_of_period: self.number_of_period,
-                        sqrt_price_step_bps: self.sqrt_price_step_bps.into(),
-                        scheduler_expiration_duration: self.scheduler_expiration_duration,
-                        reduction_factor: self.reduction_factor,
-                        padding: [0; 5],
+            Ok(
+                DammV2BaseFeeMode::FeeMarketCapSchedulerExponential
+                | DammV2BaseFeeMode::FeeMarketCapSchedulerLinear,
+            ) => {
+                // the market cap fee scheduler is built from the other fields, so it's only checked once they are valid
+                if violations.len() == violation_count {
+                    violations.check_result(
+                        self.validate_market_cap_fee_scheduler(),
+                        "migrated_pool_market_cap_fee_scheduler_params",
+                        "market cap fee scheduler accepted by DAMM v2, with a min fee above the DAMM v2 min fee",
+                    );
+                }
+            }
+            migrated_base_fee_mode => {
+                violations.check(
+                    false,
+                    if migrated_base_fee_mode.is_err() {
+                        PoolError::TypeCastFailed
+                    } else {
+                        PoolError::InvalidMigratedPoolFee
                     },
+                    "migrated_pool_base_fee_mode",
+                    "0, 1 (time scheduler), 3 or 4 (market cap scheduler)",
                 );
-
-                market_cap_fee_scheduler.validate()?;
-            }
-            _ => {
-                return Err(PoolError::InvalidMigratedPoolFee.into());
             }
         }
+    }
 
-        Ok(())
+    fn validate_market_cap_fee_scheduler(&self) -> Result<()> {
+        let cliff_fee_numerator = to_numerator(
+            self.pool_fee_bps.into(),
+            damm_v2::constants::FEE_DENOMINATOR.into(),
+        )?;
+
+        let market_cap_fee_scheduler = DammV2PodAlignedFeeMarketCapScheduler(
+            damm_v2::accounts::PodAlignedFeeMarketCapScheduler {
+                cliff_fee_numerator,
+                base_fee_mode: self.migrated_pool_base_fee_mode,
+                number_of_period: self.number_of_period,
+                sqrt_price_step_bps: self.sqrt_price_step_bps.into(),
+                scheduler_expiration_duration: self.scheduler_expiration_duration,
+                reduction_factor: self.reduction_factor,
+                padding: [0; 5],
+            },
+        );
+
+        market_cap_fee_scheduler.validate()
     }
 }
 
@@ -298,14 +382,27 @@ impl LockedVestingParams {
         *self != LockedVestingParams::default()
     }
     pub fn validate(&self) -> Result<()> {
-        if self.has_vesting() {
-            let total_amount = self.get_total_amount()?;
-            require!(
-                self.frequency != 0 && total_amount != 0,
-                PoolError::InvalidVestingParameters
-            );
+        let mut violations = ConfigViolations::default();
+        self.check(&mut violations);
+        violations.into_result()
+    }
+
+    pub fn check(&self, violations: &mut ConfigViolations) {
+        if !self.has_vesting() {
+            return;
         }
-        Ok(())
+        violations.check(
+            self.frequency != 0,
+            PoolError::InvalidVestingParameters,
+            "locked_vesting.frequency",
+            "greater than 0",
+        );
+        violations.check(
+            self.get_total_amount().is_ok_and(|amount| amount != 0),
+            PoolError::InvalidVestingParameters,
+            "locked_vesting",
+            "cliff_unlock_amount + amount_per_period * number_of_period greater than 0 and not overflowing u64",
+        );
     }
 }
 
@@ -367,6 +464,61 @@ impl LiquidityVestingInfoParams {
 }
 
 impl ConfigParameters {
+    /// Pool fees with the base fee derived from the fee steps in the FeeStepTable base fee mode
+    pub fn get_pool_fee_parameters(&self) -> Result<PoolFeeParameters> {
+        let base_fee = self.pool_fees.base_fee;
+        if base_fee.base_fee_mode != BaseFeeMode::FeeStepTable.into() {
+            require!(self.fee_steps.is_empty(), PoolError::InvalidFeeStepTable);
+            return Ok(self.pool_fees);
+        }
+
+        require!(
+            base_fee.cliff_fee_numerator == 0
+                && base_fee.first_factor == 0
+                && base_fee.second_factor == 0
+                && base_fee.third_factor == 0,
+            PoolError::InvalidFeeStepTable
+        );
+        require!(
+            !self.fee_steps.is_empty()
+                && self.fee_steps.len() <= MAX_FEE_STEPS
+                && self.fee_steps[0].elapsed_point == 0,
+            PoolError::InvalidFeeStepTable
+        );
+        for fee_steps in self.fee_steps.windows(2) {
+            require!(
+                fee_steps[0].elapsed_point < fee_steps[1].elapsed_point,
+                PoolError::InvalidFeeStepTable
+            );
+        }
+
+        let fee_numerators = self.fee_steps.iter().map(|fee_step| fee_step.fee_numerator);
+        // the fee step table handler validates the bounds of all step fees
+        let base_fee = BaseFeeParameters {
+            cliff_fee_numerator: fee_numerators.clone().max().unwrap_or_default(),
+            first_factor: u16::try_from(self.fee_steps.len())
+                .map_err(|_| PoolError::TypeCastFailed)?,
+            third_factor: fee_numerators.min().unwrap_or_default(),
+            ..base_fee
+        };
+
+        Ok(PoolFeeParameters {
+            base_fee,
+            ..self.pool_fees
+        })
+    }
+
+    pub fn get_fee_steps(&self) -> [FeeStep; MAX_FEE_STEPS] {
+        let mut fee_steps = [FeeStep::default(); MAX_FEE_STEPS];
+        for (fee_step, params) in fee_steps.iter_mut().zip(self.fee_steps.iter()) {
+            *fee_step = FeeStep {
+                elapsed_point: params.elapsed_point,
+                fee_numerator: params.fee_numerator,
+            };
+        }
+        fee_steps
+    }
+
     pub fn validate<'info>(
         &self,
         quote_mint: &InterfaceAccount<'info, Mint>,
@@ -377,37 +529,277 @@ impl ConfigParameters {
             is_supported_quote_mint(quote_mint)?,
             PoolError::InvalidQuoteMint
         );
+        if matches!(
+            MigrationOption::try_from(self.migration_option),
+            Ok(MigrationOption::MeteoraDamm)
+        ) {
+            require!(
+                *quote_mint.to_account_info().owner == anchor_spl::token::Token::id(),
+                PoolError::InvalidQuoteMint
+            );
+        }
 
-        let activation_type = ActivationType::try_from(self.activation_type)
-            .map_err(|_| PoolError::TypeCastFailed)?;
+        self.validate_parameters(current_timestamp)
+    }
 
-        // validate fee
-        self.pool_fees
-            .validate(self.collect_fee_mode, activation_type)?;
+    /// Validate the parameters that don't depend on the quote mint, fails with the error of the first violation
+    pub fn validate_parameters(&self, current_timestamp: u64) -> Result<()> {
+        let mut violations = ConfigViolations::default();
+        self.check_parameters(&mut violations, current_timestamp);
+        violations.into_result()
+    }
+
+    /// Push every violation of the parameters that don't depend on the quote mint, shared with the off-chain validators of the sdk.
+    /// The token supply isn't checked as it depends on the amounts computed from the curve
+    pub fn check_parameters(&self, violations: &mut ConfigViolations, current_timestamp: u64) {
+        let activation_type = ActivationType::try_from(self.activation_type).ok();
+        violations.check(
+            activation_type.is_some(),
+            PoolError::TypeCastFailed,
+            "activation_type",
+            "0 (slot) or 1 (timestamp)",
+        );
+        // validate collect fee mode
+        violations.check(
+            CollectFeeMode::try_from(self.collect_fee_mode).is_ok(),
+            PoolError::InvalidCollectFeeMode,
+            "collect_fee_mode",
+            "0 (quote token), 1 (output token) or 2 (quote token buyback burn)",
+        );
+        self.check_buyback(violations);
+
+        if let Some(activation_type) = activation_type {
+            self.check_pool_fees(violations, activation_type);
+            self.check_launch_phases(violations, activation_type);
+        }
 
         // validate creator trading fee percentage
-        require!(
+        violations.check(
             self.creator_trading_fee_percentage <= 100,
-            PoolError::InvalidCreatorTradingFeePercentage
+            PoolError::InvalidCreatorTradingFeePercentage,
+            "creator_trading_fee_percentage",
+            "0 to 100",
         );
+        self.check_migration(violations, current_timestamp);
 
-        self.migration_fee.validate()?;
+        violations.check(
+            TokenType::try_from(self.token_type).is_ok(),
+            PoolError::InvalidTokenType,
+            "token_type",
+            "0 (spl token) or 1 (token 2022)",
+        );
+        // validate token update authority
+        violations.check(
+            TokenAuthorityOption::try_from(self.token_update_authority).is_ok(),
+            PoolError::InvalidTokenAuthorityOption,
+            "token_update_authority",
+            "0 to 4",
+        );
+        // validate token decimals
+        violations.check(
+            self.token_decimal >= 6 && self.token_decimal <= 9,
+            PoolError::InvalidTokenDecimals,
+            "token_decimal",
+            "6 to 9",
+        );
+        violations.check(
+            self.migration_quote_threshold > 0,
+            PoolError::InvalidQuoteThreshold,
+            "migration_quote_threshold",
+            "greater than 0",
+        );
 
-        // validate collect fee mode
-        require!(
-            CollectFeeMode::try_from(self.collect_fee_mode).is_ok(),
-            PoolError::InvalidCollectFeeMode
+        // validate vesting params
+        self.locked_vesting.check(violations);
+
+        // validate pool creation fee
+        violations.check(
+            self.pool_creation_fee == 0
+                || (self.pool_creation_fee >= MIN_POOL_CREATION_FEE
+                    && self.pool_creation_fee <= MAX_POOL_CREATION_FEE),
+            PoolError::InvalidPoolCreationFee,
+            "pool_creation_fee",
+            format_args!("0 or {MIN_POOL_CREATION_FEE} to {MAX_POOL_CREATION_FEE} lamports"),
         );
-        // validate migration option and token type
-        let migration_option_value = MigrationOption::try_from(self.migration_option)
-            .map_err(|_| PoolError::InvalidMigrationOption)?;
 
-        // validate migrate fee option
-        let migration_fee_option = MigrationFeeOption::try_from(self.migration_fee_option)
-            .map_err(|_| PoolError::InvalidMigrationFeeOption)?;
+        self.check_curve(violations);
+    }
+
+    fn check_pool_fees(&self, violations: &mut ConfigViolations, activation_type: ActivationType) {
+        let base_fee = self.pool_fees.base_fee;
+        let is_fee_step_table = base_fee.base_fee_mode == u8::from(BaseFeeMode::FeeStepTable);
+        if is_fee_step_table {
+            violations.check(
+                base_fee.cliff_fee_numerator == 0
+                    && base_fee.first_factor == 0
+                    && base_fee.second_factor == 0
+                    && base_fee.third_factor == 0,
+                PoolError::InvalidFeeStepTable,
+                "pool_fees.base_fee",
+                "zero cliff_fee_numerator and factors with the fee step table base fee mode",
+            );
+            violations.check(
+                !self.fee_steps.is_empty()
+                    && self.fee_steps.len() <= MAX_FEE_STEPS
+                    && self.fee_steps[0].elapsed_point == 0
+                    && self
+                        .fee_steps
+                        .windows(2)
+                        .all(|fee_steps| fee_steps[0].elapsed_point < fee_steps[1].elapsed_point),
+                PoolError::InvalidFeeStepTable,
+                "fee_steps",
+                format_args!(
+                    "1 to {MAX_FEE_STEPS} steps with increasing elapsed_point, starting at 0"
+                ),
+            );
+        } else {
+            violations.check(
+                self.fee_steps.is_empty(),
+                PoolError::InvalidFeeStepTable,
+                "fee_steps",
+                "empty unless the base fee mode is fee step table (5)",
+            );
+        }
+
+        // the fee step table is checked through the base fee built from the steps
+        if let Ok(pool_fee_parameters) = self.get_pool_fee_parameters() {
+            pool_fee_parameters.check(
+                violations,
+                self.collect_fee_mode,
+                activation_type,
+                if is_fee_step_table {
+                    "fee_steps"
+                } else {
+                    "pool_fees.base_fee"
+                },
+            );
+        }
+    }
 
-        let token_type_value =
-            TokenType::try_from(self.token_type).map_err(|_| PoolError::InvalidTokenType)?;
+    fn check_launch_phases(
+        &self,
+        violations: &mut ConfigViolations,
+        activation_type: ActivationType,
+    ) {
+        let (max_buy_cap_duration, max_allowlist_duration) = match activation_type {
+            ActivationType::Slot => (
+                MAX_BUY_CAP_DURATION_IN_SLOTS,
+                MAX_ALLOWLIST_DURATION_IN_SLOTS,
+            ),
+            ActivationType::Timestamp => (
+                MAX_BUY_CAP_DURATION_IN_SECONDS,
+                MAX_ALLOWLIST_DURATION_IN_SECONDS,
+            ),
+        };
+
+        // validate wallet buy cap, both fields are set together
+        if self.max_buy_amount_per_wallet > 0 || self.buy_cap_duration > 0 {
+            violations.check(
+                self.max_buy_amount_per_wallet > 0,
+                PoolError::InvalidWalletBuyCapParameters,
+                "max_buy_amount_per_wallet",
+                "greater than 0 when buy_cap_duration is set",
+            );
+            violations.check(
+                self.buy_cap_duration > 0
+                    && u64::from(self.buy_cap_duration) <= max_buy_cap_duration,
+                PoolError::InvalidWalletBuyCapParameters,
+                "buy_cap_duration",
+                format_args!("1 to {max_buy_cap_duration} when max_buy_amount_per_wallet is set"),
+            );
+        }
+
+        // validate allowlist phase
+        violations.check(
+            u64::from(self.allowlist_duration) <= max_allowlist_duration,
+            PoolError::InvalidAllowlistParameters,
+            "allowlist_duration",
+            format_args!("0 to {max_allowlist_duration}"),
+        );
+        violations.check(
+            (self.allowlist_duration > 0) == (self.allowlist_merkle_root != [0u8; 32]),
+            PoolError::InvalidAllowlistParameters,
+            "allowlist_merkle_root",
+            "set only when allowlist_duration is set",
+        );
+    }
+
+    // the spend per period bounds what a sandwich around the permissionless buyback can extract
+    fn check_buyback(&self, violations: &mut ConfigViolations) {
+        let is_buyback_burn_enabled =
+            self.collect_fee_mode == u8::from(CollectFeeMode::QuoteTokenBuybackBurn);
+        let (fee_source_range, amount_range) = if is_buyback_burn_enabled {
+            (
+                "0 (partner), 1 (creator) or 2 (partner and creator)",
+                "greater than 0 in the quote token buyback burn collect fee mode",
+            )
+        } else {
+            (
+                "0 when buyback burn is disabled",
+                "0 when buyback burn is disabled",
+            )
+        };
+
+        violations.check(
+            if is_buyback_burn_enabled {
+                BuybackFeeSource::try_from(self.buyback_fee_source).is_ok()
+            } else {
+                self.buyback_fee_source == 0
+            },
+            PoolError::InvalidBuybackParameters,
+            "buyback_fee_source",
+            fee_source_range,
+        );
+        violations.check(
+            (self.buyback_max_quote_amount_per_period > 0) == is_buyback_burn_enabled,
+            PoolError::InvalidBuybackParameters,
+            "buyback_max_quote_amount_per_period",
+            amount_range,
+        );
+        violations.check(
+            (self.buyback_period > 0) == is_buyback_burn_enabled,
+            PoolError::InvalidBuybackParameters,
+            "buyback_period",
+            amount_range,
+        );
+    }
+
+    fn check_migration(&self, violations: &mut ConfigViolations, current_timestamp: u64) {
+        self.migration_fee.check(violations);
+
+        let sum_liquidity_percentage = u16::from(self.partner_liquidity_percentage)
+            + u16::from(self.partner_permanent_locked_liquidity_percentage)
+            + u16::from(self.creator_liquidity_percentage)
+            + u16::from(self.creator_permanent_locked_liquidity_percentage)
+            + u16::from(self.partner_liquidity_vesting_info.vesting_percentage)
+            + u16::from(self.creator_liquidity_vesting_info.vesting_percentage);
+        violations.check(
+            sum_liquidity_percentage == 100,
+            PoolError::InvalidFeePercentage,
+            "partner_liquidity_percentage",
+            "sum of the partner and creator liquidity, permanent locked liquidity and liquidity vesting percentages equal to 100",
+        );
+
+        // validate migration option and migrate fee option
+        let migration_option = MigrationOption::try_from(self.migration_option).ok();
+        violations.check(
+            migration_option.is_some(),
+            PoolError::InvalidMigrationOption,
+            "migration_option",
+            "0 (DAMM v1) or 1 (DAMM v2)",
+        );
+        let migration_fee_option = MigrationFeeOption::try_from(self.migration_fee_option).ok();
+        violations.check(
+            migration_fee_option.is_some(),
+            PoolError::InvalidMigrationFeeOption,
+            "migration_fee_option",
+            "0 to 6",
+        );
+        let (Some(migration_option), Some(migration_fee_option)) =
+            (migration_option, migration_fee_option)
+        else {
+            return;
+        };
 
         let migrated_pool_fee_validator = MigratedPoolFeeValidator::new(
             &self.migrated_pool_fee,
@@ -415,123 +807,170 @@ impl ConfigParameters {
             &self.migrated_pool_market_cap_fee_scheduler_params,
             self.migrated_pool_base_fee_mode,
         );
+        let is_customizable = migration_fee_option == MigrationFeeOption::Customizable;
 
-        match migration_option_value {
+        match migration_option {
             MigrationOption::MeteoraDamm => {
-                require!(
-                    token_type_value == TokenType::SplToken,
-                    PoolError::InvalidTokenType
+                // an invalid token type is already reported
+                violations.check(
+                    TokenType::try_from(self.token_type).is_err()
+                        || self.token_type == u8::from(TokenType::SplToken),
+                    PoolError::InvalidTokenType,
+                    "token_type",
+                    "0 (spl token) with DAMM v1 migration",
                 );
-                require!(
-                    *quote_mint.to_account_info().owner == anchor_spl::token::Token::id(),
-                    PoolError::InvalidQuoteMint
+                violations.check(
+                    !is_customizable,
+                    PoolError::InvalidMigrationFeeOption,
+                    "migration_fee_option",
+                    "0 to 5 with DAMM v1 migration",
                 );
-
-                require!(
-                    migration_fee_option != MigrationFeeOption::Customizable
-                        && migrated_pool_fee_validator.is_none(),
-                    PoolError::InvalidMigrationFeeOption
+                violations.check(
+                    migrated_pool_fee_validator.is_none(),
+                    PoolError::InvalidMigrationFeeOption,
+                    "migrated_pool_fee",
+                    "zero migrated pool fee, compounding fee and market cap fee scheduler with DAMM v1 migration",
                 );
                 // validate vesting
-                require!(
+                violations.check(
                     self.partner_liquidity_vesting_info.is_zero(),
-                    PoolError::InvalidVestingParameters
+                    PoolError::InvalidVestingParameters,
+                    "partner_liquidity_vesting_info",
+                    "zero with DAMM v1 migration",
                 );
-                require!(
+                violations.check(
                     self.creator_liquidity_vesting_info.is_zero(),
-                    PoolError::InvalidVestingParameters
+                    PoolError::InvalidVestingParameters,
+                    "creator_liquidity_vesting_info",
+                    "zero with DAMM v1 migration",
                 );
             }
             MigrationOption::DammV2 => {
-                if migration_fee_option == MigrationFeeOption::Customizable {
-                    migrated_pool_fee_validator.validate()?;
+                if is_customizable {
+                    migrated_pool_fee_validator.check(violations);
                 } else {
-                    require!(
+                    violations.check(
                         migrated_pool_fee_validator.is_none(),
-                        PoolError::InvalidMigratedPoolFee
+                        PoolError::InvalidMigratedPoolFee,
+                        "migrated_pool_fee",
+                        "zero migrated pool fee, compounding fee and market cap fee scheduler unless migration_fee_option is customizable (6)",
                     );
                 }
                 // validate vesting
-                self.partner_liquidity_vesting_info
-                    .validate(current_timestamp)?;
-                self.creator_liquidity_vesting_info
-                    .validate(current_timestamp)?;
+                for (liquidity_vesting_info, field) in [
+                    (
+                        &self.partner_liquidity_vesting_info,
+                        "partner_liquidity_vesting_info",
+                    ),
+                    (
+                        &self.creator_liquidity_vesting_info,
+                        "creator_liquidity_vesting_info",
+                    ),
+                ] {
+                    violations.check_result(
+                        liquidity_vesting_info.validate(current_timestamp),
+                        field,
+                        format_args!(
+                            "vesting with non zero frequency and number_of_periods, bps_per_period up to {MAX_BASIS_POINT} in total, ending within {MAX_LOCK_DURATION_IN_SECONDS} seconds"
+                        ),
+                    );
+                }
             }
         }
+    }
 
-        // validate token update authority
-        require!(
-            TokenAuthorityOption::try_from(self.token_update_authority).is_ok(),
-            PoolError::InvalidTokenAuthorityOption
-        );
-
-        // validate token decimals
-        require!(
-            self.token_decimal >= 6 && self.token_decimal <= 9,
-            PoolError::InvalidTokenDecimals
-        );
-
-        let sum_liquidity_percentage = self
-            .partner_liquidity_percentage
-            .safe_add(self.partner_permanent_locked_liquidity_percentage)?
-            .safe_add(self.creator_liquidity_percentage)?
-            .safe_add(self.creator_permanent_locked_liquidity_percentage)?
-            .safe_add(self.partner_liquidity_vesting_info.vesting_percentage)?
-            .safe_add(self.creator_liquidity_vesting_info.vesting_percentage)?;
-        require!(
-            sum_liquidity_percentage == 100,
-            PoolError::InvalidFeePercentage
-        );
-
-        require!(
-            self.migration_quote_threshold > 0,
-            PoolError::InvalidQuoteThreshold
-        );
-
-        // validate vesting params
-        self.locked_vesting.validate()?;
+    /// Check the start price and the liquidity distribution of the curve
+    fn check_curve(&self, violations: &mut ConfigViolations) {
+        let violation_count = violations.len();
 
-        // validate pool creation fee
-        if self.pool_creation_fee > 0 {
-            require!(
-                self.pool_creation_fee >= MIN_POOL_CREATION_FEE
-                    && self.pool_creation_fee <= MAX_POOL_CREATION_FEE,
-                PoolError::InvalidPoolCreationFee
-            )
-        }
-
-        // validate price and liquidity
-        require!(
+        violations.check(
             self.sqrt_start_price >= MIN_SQRT_PRICE && self.sqrt_start_price < MAX_SQRT_PRICE,
-            PoolError::InvalidCurve
-        );
-        let curve_length = self.curve.len();
-        require!(
-            curve_length > 0 && curve_length <= MAX_CURVE_POINT,
-            PoolError::InvalidCurve
+            PoolError::InvalidCurve,
+            "sqrt_start_price",
+            format_args!("{MIN_SQRT_PRICE} to {MAX_SQRT_PRICE} (exclusive)"),
         );
-        require!(
-            self.curve[0].sqrt_price > self.sqrt_start_price
-                && self.curve[0].liquidity > 0
-                && self.curve[0].sqrt_price <= MAX_SQRT_PRICE,
-            PoolError::InvalidCurve
+        violations.check(
+            !self.curve.is_empty() && self.curve.len() <= MAX_CURVE_POINT,
+            PoolError::InvalidCurve,
+            "curve",
+            format_args!("1 to {MAX_CURVE_POINT} points"),
         );
 
-        for i in 1..curve_length {
-            require!(
-                self.curve[i].sqrt_price > self.curve[i - 1].sqrt_price
-                    && self.curve[i].liquidity > 0,
-                PoolError::InvalidCurve
+        // the prices of the curve are increasing up to the max price
+        let mut lower_sqrt_price = self.sqrt_start_price;
+        for (i, point) in self.curve.iter().enumerate() {
+            violations.check(
+                point.sqrt_price > lower_sqrt_price && point.sqrt_price <= MAX_SQRT_PRICE,
+                PoolError::InvalidCurve,
+                format_args!("curve[{i}].sqrt_price"),
+                format_args!(
+                    "greater than {} and up to {MAX_SQRT_PRICE}",
+                    if i == 0 {
+                        "sqrt_start_price"
+                    } else {
+                        "the previous point sqrt_price"
+                    }
+                ),
             );
+            violations.check(
+                point.liquidity > 0,
+                PoolError::InvalidCurve,
+                format_args!("curve[{i}].liquidity"),
+                "greater than 0",
+            );
+            lower_sqrt_price = point.sqrt_price;
         }
 
-        // the last price in curve must be smaller than or equal max price
-        require!(
-            self.curve[curve_length - 1].sqrt_price <= MAX_SQRT_PRICE,
-            PoolError::InvalidCurve
-        );
+        match CurveType::try_from(self.curve_type) {
+            Ok(CurveType::ConstantProduct) => {}
+            Ok(_) => {
+                violations.check(
+                    self.curve.len() == 1,
+                    PoolError::InvalidCurve,
+                    "curve",
+                    "1 point for the linear and exponential price curves",
+                );
+                violations.check(
+                    self.curve
+                        .first()
+                        .is_none_or(|point| point.liquidity <= u64::MAX.into()),
+                    PoolError::InvalidCurve,
+                    "curve[0].liquidity",
+                    format_args!("up to {} for the base amount sold on the curve", u64::MAX),
+                );
+                // the price curve is built from the point checked above, the exponential curve also needs a price range wider than the precision of the logarithm
+                if violations.len() == violation_count {
+                    violations.check_result(
+                        self.get_price_curve().map(|_| ()),
+                        "curve[0].sqrt_price",
+                        "far enough from sqrt_start_price for the precision of the exponential price curve",
+                    );
+                }
+            }
+            Err(_) => violations.check(
+                false,
+                PoolError::InvalidCurveType,
+                "curve_type",
+                "0 (constant product), 1 (linear price) or 2 (exponential price)",
+            ),
+        }
+    }
 
-        Ok(())
+    /// Price curve of the parameters, none for the constant product curve
+    pub fn get_price_curve(&self) -> Result<Option<PriceCurve>> {
+        let curve_type =
+            CurveType::try_from(self.curve_type).map_err(|_| PoolError::InvalidCurveType)?;
+        if curve_type == CurveType::ConstantProduct {
+            return Ok(None);
+        }
+        require!(self.curve.len() == 1, PoolError::InvalidCurve);
+        let price_curve = PriceCurve::new(
+            curve_type,
+            self.sqrt_start_price,
+            self.curve[0].sqrt_price,
+            self.curve[0].liquidity,
+        )?;
+        Ok(Some(price_curve))
     }
 }
 
@@ -557,6 +996,69 @@ pub struct CreateConfigCtx<'info> {
     pub payer: Signer<'info>,
 
     pub system_program: Program<'info, System>,
+
+    /// config extension, only required when the sell base fee is set
+    #[account(
+        init,
+        seeds = [
+            CONFIG_EXTENSION_PREFIX.as_ref(),
+            config.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = 8 + PoolConfigExtension::INIT_SPACE
+    )]
+    pub config_extension: Option<AccountLoader<'info, PoolConfigExtension>>,
+
+    /// CHECK: protocol config of the fee claimer, it may not be created
+    #[account(
+        seeds = [
+            PROTOCOL_CONFIG_PREFIX.as_ref(),
+            fee_claimer.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub protocol_config: UncheckedAccount<'info>,
+
+    /// CHECK: protocol config of all partners, it may not be created
+    #[account(
+        seeds = [
+            PROTOCOL_CONFIG_PREFIX.as_ref(),
+            Pubkey::default().as_ref(),
+        ],
+        bump,
+    )]
+    pub global_protocol_config: UncheckedAccount<'info>,
+
+    /// swap hook, only required when the hook program is set
+    #[account(
+        init,
+        seeds = [
+            SWAP_HOOK_PREFIX.as_ref(),
+            config.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = 8 + SwapHook::INIT_SPACE
+    )]
+    pub swap_hook: Option<AccountLoader<'info, SwapHook>>,
+
+    /// CHECK: hook program, invoked after every swap on pools created from the config
+    #[account(executable)]
+    pub hook_program: Option<UncheckedAccount<'info>>,
+
+    /// presale allowlist, only required when the allowlist phase is enabled
+    #[account(
+        init,
+        seeds = [
+            PRESALE_ALLOWLIST_PREFIX.as_ref(),
+            config.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = 8 + PresaleAllowlist::INIT_SPACE
+    )]
+    pub presale_allowlist: Option<AccountLoader<'info, PresaleAllowlist>>,
 }
 
 pub fn handle_create_config(
@@ -569,7 +1071,6 @@ pub fn handle_create_config(
     )?;
 
     let ConfigParameters {
-        pool_fees,
         collect_fee_mode,
         migration_option,
         activation_type,
@@ -596,19 +1097,38 @@ pub fn handle_create_config(
         migrated_pool_market_cap_fee_scheduler_params,
         enable_first_swap_with_min_fee,
         compounding_fee_bps,
+        max_buy_amount_per_wallet,
+        buy_cap_duration,
+        allowlist_duration,
+        allowlist_merkle_root,
+        curve_type,
+        enable_creator_allowlist,
+        buyback_fee_source,
+        buyback_max_quote_amount_per_period,
+        buyback_period,
         ..
     } = config_parameters.clone();
+    let pool_fees = config_parameters.get_pool_fee_parameters()?;
+    let price_curve = config_parameters.get_price_curve()?;
 
-    let migration_sqrt_price =
-        get_migration_threshold_price(migration_quote_threshold, sqrt_start_price, &curve)?;
+    let migration_sqrt_price = match price_curve.as_ref() {
+        Some(price_curve) => price_curve.get_migration_sqrt_price(migration_quote_threshold)?,
+        None => get_migration_threshold_price(migration_quote_threshold, sqrt_start_price, &curve)?,
+    };
     // migration price must be smaller than max sqrt price
     require!(
         migration_sqrt_price < MAX_SQRT_PRICE,
         PoolError::InvalidCurve
     );
 
-    let swap_base_amount_256 =
-        get_base_token_for_swap(sqrt_start_price, migration_sqrt_price, &curve)?;
+    let swap_base_amount_256 = match price_curve.as_ref() {
+        Some(price_curve) => price_curve.get_delta_amount_base(
+            sqrt_start_price,
+            migration_sqrt_price,
+            Rounding::Up,
+        )?,
+        None => get_base_token_for_swap(sqrt_start_price, migration_sqrt_price, &curve)?,
+    };
     let swap_base_amount: u64 = swap_base_amount_256
         .try_into()
         .map_err(|_| PoolError::TypeCastFailed)?;
@@ -664,11 +1184,17 @@ pub fn handle_create_config(
             post_migration_token_supply,
         }) = token_supply
         {
-            let swap_base_amount_buffer = PoolConfig::get_swap_amount_with_buffer(
-                swap_base_amount,
-                sqrt_start_price,
-                &curve,
-            )?;
+            let swap_base_amount_buffer = match price_curve.as_ref() {
+                Some(price_curve) => PoolConfig::get_swap_amount_with_buffer_on_price_curve(
+                    swap_base_amount,
+                    price_curve,
+                )?,
+                None => PoolConfig::get_swap_amount_with_buffer(
+                    swap_base_amount,
+                    sqrt_start_price,
+                    &curve,
+                )?,
+            };
 
             let minimum_base_supply_with_buffer = PoolConfig::get_total_token_supply(
                 swap_base_amount_buffer,
@@ -703,6 +1229,15 @@ pub fn handle_create_config(
         dynamic_fee: migrated_dynamic_fee,
     } = migrated_pool_fee;
 
+    // protocol fee shares negotiated with the fee claimer, otherwise the ones of all partners, otherwise the default ones
+    let protocol_config = match ProtocolConfig::try_load(&ctx.accounts.protocol_config)? {
+        Some(protocol_config) => {
+            require!(ctx.accounts.fee_claimer.is_signer, PoolError::Unauthorized);
+            protocol_config
+        }
+        None => ProtocolConfig::try_load(&ctx.accounts.global_protocol_config)?.unwrap_or_default(),
+    };
+
     let mut config = ctx.accounts.config.load_init()?;
     config.init(
         &ctx.accounts.quote_mint.key(),
@@ -743,8 +1278,81 @@ pub fn handle_create_config(
         migrated_pool_market_cap_fee_scheduler_params,
         &curve,
         enable_first_swap_with_min_fee.into(),
+        buy_cap_duration,
+        allowlist_duration,
+        pool_fees.sell_base_fee.is_some().into(),
+        curve_type,
+        enable_creator_allowlist.into(),
+        buyback_fee_source,
+        &protocol_config,
     )?;
 
+    match (
+        pool_fees.sell_base_fee.is_some()
+            || !config_parameters.fee_steps.is_empty()
+            || buy_cap_duration > 0
+            || config.is_buyback_burn_enabled(),
+        ctx.accounts.config_extension.as_ref(),
+    ) {
+        (true, Some(config_extension)) => {
+            let mut config_extension = config_extension.load_init()?;
+            config_extension.initialize(
+                ctx.accounts.config.key(),
+                pool_fees
+                    .sell_base_fee
+                    .map(|sell_base_fee| sell_base_fee.to_base_fee_config())
+                    .unwrap_or_default(),
+                config_parameters.get_fee_steps(),
+                max_buy_amount_per_wallet,
+                buyback_max_quote_amount_per_period,
+                buyback_period,
+            );
+        }
+        (false, None) => {}
+        _ => return Err(PoolError::MissingConfigExtension.into()),
+    }
+
+    // the allowlist is created with the config, so the allowlist phase never blocks buys without it
+    match (
+        allowlist_duration > 0,
+        ctx.accounts.presale_allowlist.as_ref(),
+    ) {
+        (true, Some(presale_allowlist)) => {
+            let mut presale_allowlist_state = presale_allowlist.load_init()?;
+            presale_allowlist_state.initialize(ctx.accounts.config.key(), allowlist_merkle_root);
+
+            emit_cpi!(EvtCreatePresaleAllowlist {
+                config: ctx.accounts.config.key(),
+                presale_allowlist: presale_allowlist.key(),
+                merkle_root: allowlist_merkle_root,
+            });
+        }
+        (false, None) => {}
+        _ => return Err(PoolError::MissingPresaleAllowlist.into()),
+    }
+
+    // the hook can only be registered on config creation, so pools never get a hook they were not created with
+    match (
+        ctx.accounts.swap_hook.as_ref(),
+        ctx.accounts.hook_program.as_ref(),
+    ) {
+        (Some(swap_hook), Some(hook_program)) => {
+            // the program can't invoke itself through the hook
+            require!(hook_program.key() != crate::ID, PoolError::InvalidSwapHook);
+            let mut swap_hook_state = swap_hook.load_init()?;
+            swap_hook_state.initialize(ctx.accounts.config.key(), hook_program.key());
+            config.swap_hook_flag = 1;
+
+            emit_cpi!(EvtCreateSwapHook {
+                config: ctx.accounts.config.key(),
+                swap_hook: swap_hook.key(),
+                hook_program: hook_program.key(),
+            });
+        }
+        (None, None) => {}
+        _ => return Err(PoolError::InvalidSwapHook.into()),
+    }
+
     // re-validate total locked liquidity
     require!(
         config.get_total_liquidity_locked_bps_at_n_seconds(SECONDS_PER_DAY)?
@@ -0,0 +1,86 @@
+use crate::constants::{
+    fee::MAX_BASIS_POINT, seeds::VOLUME_FEE_TIERS_PREFIX, MAX_VOLUME_FEE_TIERS,
+};
+use crate::state::{PoolConfig, VolumeFeeTier, VolumeFeeTiers};
+use crate::{EvtCreateVolumeFeeTiers, PoolError};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy)]
+pub struct VolumeFeeTierParameters {
+    /// min cumulative quote volume of the wallet in the config to reach the tier
+    pub min_volume: u64,
+    /// discount on the total fee, in bps. The discounted fee is floored at the min base fee
+    pub fee_discount_bps: u16,
+}
+
+/// Validate the tiers and pad them to MAX_VOLUME_FEE_TIERS, min volume and discount must be strictly increasing
+pub fn to_volume_fee_tiers(
+    tiers: &[VolumeFeeTierParameters],
+) -> Result<[VolumeFeeTier; MAX_VOLUME_FEE_TIERS]> {
+    require!(
+        !tiers.is_empty() && tiers.len() <= MAX_VOLUME_FEE_TIERS,
+        PoolError::InvalidVolumeFeeTiers
+    );
+
+    let mut volume_fee_tiers = [VolumeFeeTier::default(); MAX_VOLUME_FEE_TIERS];
+    for (i, tier) in tiers.iter().enumerate() {
+        let is_increasing = i == 0
+            || (tier.min_volume > tiers[i - 1].min_volume
+                && tier.fee_discount_bps > tiers[i - 1].fee_discount_bps);
+        require!(
+            is_increasing
+                && tier.fee_discount_bps > 0
+                && u64::from(tier.fee_discount_bps) < MAX_BASIS_POINT,
+            PoolError::InvalidVolumeFeeTiers
+        );
+        volume_fee_tiers[i] = VolumeFeeTier {
+            min_volume: tier.min_volume,
+            fee_discount_bps: tier.fee_discount_bps,
+            ..Default::default()
+        };
+    }
+    Ok(volume_fee_tiers)
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateVolumeFeeTiersCtx<'info> {
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    #[account(
+        init,
+        seeds = [
+            VOLUME_FEE_TIERS_PREFIX.as_ref(),
+            config.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = 8 + VolumeFeeTiers::INIT_SPACE
+    )]
+    pub volume_fee_tiers: AccountLoader<'info, VolumeFeeTiers>,
+
+    pub fee_claimer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_volume_fee_tiers(
+    ctx: Context<CreateVolumeFeeTiersCtx>,
+    tiers: Vec<VolumeFeeTierParameters>,
+) -> Result<()> {
+    let volume_fee_tiers = to_volume_fee_tiers(&tiers)?;
+
+    let mut volume_fee_tiers_state = ctx.accounts.volume_fee_tiers.load_init()?;
+    volume_fee_tiers_state.initialize(ctx.accounts.config.key(), volume_fee_tiers);
+
+    emit_cpi!(EvtCreateVolumeFeeTiers {
+        config: ctx.accounts.config.key(),
+        volume_fee_tiers: ctx.accounts.volume_fee_tiers.key(),
+        tiers,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use crate::constants::seeds::FEE_CLAIMER_TRANSFER_PREFIX;
+use crate::state::{FeeClaimerTransfer, PoolConfig};
+use crate::{EvtProposeFeeClaimer, PoolError};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeFeeClaimerCtx<'info> {
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    #[account(
+        init,
+        seeds = [
+            FEE_CLAIMER_TRANSFER_PREFIX.as_ref(),
+            config.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = 8 + FeeClaimerTransfer::INIT_SPACE
+    )]
+    pub fee_claimer_transfer: AccountLoader<'info, FeeClaimerTransfer>,
+
+    /// CHECK: new fee claimer, must sign to accept the transfer
+    #[account(
+        constraint = new_fee_claimer.key().ne(fee_claimer.key) @ PoolError::InvalidFeeClaimerTransfer,
+    )]
+    pub new_fee_claimer: UncheckedAccount<'info>,
+
+    /// CHECK: new leftover receiver
+    #[account(
+        constraint = new_leftover_receiver.key() != Pubkey::default() @ PoolError::InvalidFeeClaimerTransfer,
+    )]
+    pub new_leftover_receiver: UncheckedAccount<'info>,
+
+    pub fee_claimer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_propose_fee_claimer(ctx: Context<ProposeFeeClaimerCtx>) -> Result<()> {
+    let mut fee_claimer_transfer = ctx.accounts.fee_claimer_transfer.load_init()?;
+    fee_claimer_transfer.initialize(
+        ctx.accounts.config.key(),
+        ctx.accounts.new_fee_claimer.key(),
+        ctx.accounts.new_leftover_receiver.key(),
+        ctx.accounts.payer.key(),
+    );
+
+    emit_cpi!(EvtProposeFeeClaimer {
+        config: ctx.accounts.config.key(),
+        fee_claimer: ctx.accounts.fee_claimer.key(),
+        new_fee_claimer: ctx.accounts.new_fee_claimer.key(),
+        new_leftover_receiver: ctx.accounts.new_leftover_receiver.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use crate::state::PoolConfig;
+use crate::EvtRetireConfig;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RetireConfigCtx<'info> {
+    #[account(mut)]
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    pub fee_claimer: Signer<'info>,
+}
+
+pub fn handle_retire_config(ctx: Context<RetireConfigCtx>) -> Result<()> {
+    let mut config = ctx.accounts.config.load_mut()?;
+    config.retire();
+
+    emit_cpi!(EvtRetireConfig {
+        config: ctx.accounts.config.key(),
+        fee_claimer: ctx.accounts.fee_claimer.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use crate::state::{PoolConfig, VolumeFeeTiers};
+use crate::{to_volume_fee_tiers, EvtUpdateVolumeFeeTiers, VolumeFeeTierParameters};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateVolumeFeeTiersCtx<'info> {
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    #[account(mut, has_one = config)]
+    pub volume_fee_tiers: AccountLoader<'info, VolumeFeeTiers>,
+
+    pub fee_claimer: Signer<'info>,
+}
+
+/// Replace the tiers of the config, the volume tracked by the wallets is kept
+pub fn handle_update_volume_fee_tiers(
+    ctx: Context<UpdateVolumeFeeTiersCtx>,
+    tiers: Vec<VolumeFeeTierParameters>,
+) -> Result<()> {
+    let volume_fee_tiers = to_volume_fee_tiers(&tiers)?;
+
+    let mut volume_fee_tiers_state = ctx.accounts.volume_fee_tiers.load_mut()?;
+    volume_fee_tiers_state.update(volume_fee_tiers);
+
+    emit_cpi!(EvtUpdateVolumeFeeTiers {
+        config: ctx.accounts.config.key(),
+        volume_fee_tiers: ctx.accounts.volume_fee_tiers.key(),
+        tiers,
+    });
+
+    Ok(())
+}
@@ -8,3 +8,23 @@ pub mod ix_create_partner_metadata;
 pub use ix_create_partner_metadata::*;
 pub mod ix_claim_partner_pool_creation_fee;
 pub use ix_claim_partner_pool_creation_fee::*;
+pub mod ix_create_volume_fee_tiers;
+pub use ix_create_volume_fee_tiers::*;
+pub mod ix_update_volume_fee_tiers;
+pub use ix_update_volume_fee_tiers::*;
+pub mod ix_close_volume_fee_tiers;
+pub use ix_close_volume_fee_tiers::*;
+pub mod ix_propose_fee_claimer;
+pub use ix_propose_fee_claimer::*;
+pub mod ix_cancel_fee_claimer_transfer;
+pub use ix_cancel_fee_claimer_transfer::*;
+pub mod ix_accept_fee_claimer;
+pub use ix_accept_fee_claimer::*;
+pub mod ix_retire_config;
+pub use ix_retire_config::*;
+pub mod ix_close_config;
+pub use ix_close_config::*;
+pub mod ix_create_allowed_creator;
+pub use ix_create_allowed_creator::*;
+pub mod ix_close_allowed_creator;
+pub use ix_close_allowed_creator::*;
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    activation_handler::get_current_point,
+    const_pda,
+    state::{PoolConfig, PoolConfigExtension, PoolObservations, VirtualPool},
+    EvtBuybackAndBurn, EvtCurveComplete, PoolError,
+};
+
+use super::process_curve_complete;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct BuybackParameters {
+    /// Max quote fee to spend in the buyback, the buyback also never spends more than the amount left in the buyback period of the config
+    pub max_quote_amount: u64,
+    /// Price limit of the buyback, 0 means no limit. The buyback stops once the price reaches the limit, and the unused quote fee is kept for the next buyback
+    pub sqrt_price_limit: u128,
+}
+
+/// Accounts for buyback and burn
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BuybackAndBurnCtx<'info> {
+    /// CHECK: pool authority
+    #[account(
+        address = const_pda::pool_authority::ID
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    /// config extension, holds the buyback spend per period
+    #[account(has_one = config)]
+    pub config_extension: AccountLoader<'info, PoolConfigExtension>,
+
+    #[account(
+        mut,
+        has_one = base_vault,
+        has_one = base_mint,
+        has_one = config,
+    )]
+    pub pool: AccountLoader<'info, VirtualPool>,
+
+    /// pool observations, updated by the buyback when passed
+    #[account(mut, has_one = pool)]
+    pub pool_observations: Option<AccountLoader<'info, PoolObservations>>,
+
+    /// The vault token account for base token
+    #[account(mut, token::token_program = token_base_program, token::mint = base_mint)]
+    pub base_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of base token
+    #[account(mut)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token base program
+    pub token_base_program: Interface<'info, TokenInterface>,
+}
+
+/// Permissionless crank, buy back base token from the curve with the unclaimed partner and/or creator quote fee and burn it.
+/// The spend per buyback period is capped by the config, so a sandwich around the crank can only move a bounded amount
+pub fn handle_buyback_and_burn(
+    ctx: Context<BuybackAndBurnCtx>,
+    params: BuybackParameters,
+) -> Result<()> {
+    let BuybackParameters {
+        max_quote_amount,
+        sqrt_price_limit,
+    } = params;
+    require!(max_quote_amount > 0, PoolError::AmountIsZero);
+    let sqrt_price_limit = (sqrt_price_limit != 0).then_some(sqrt_price_limit);
+
+    let config = ctx.accounts.config.load()?;
+    let config_extension = ctx.accounts.config_extension.load()?;
+    let mut pool = ctx.accounts.pool.load_mut()?;
+
+    require!(
+        config.is_buyback_burn_enabled(),
+        PoolError::BuybackBurnNotEnabled
+    );
+
+    require!(
+        !pool.is_curve_complete(config.migration_quote_threshold),
+        PoolError::PoolIsCompleted
+    );
+
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    let current_point = get_current_point(config.activation_type)?;
+    pool.update_pre_swap(&config, current_timestamp)?;
+
+    let buyback_result = pool.get_buyback_result(
+        &config,
+        &config_extension,
+        max_quote_amount,
+        sqrt_price_limit,
+        current_point,
+    )?;
+    require!(buyback_result.base_amount > 0, PoolError::AmountIsZero);
+
+    let old_sqrt_price = pool.sqrt_price;
+    pool.apply_buyback_result(
+        &config,
+        &config_extension,
+        &buyback_result,
+        current_point,
+        current_timestamp,
+    )?;
+    if let Some(pool_observations) = ctx.accounts.pool_observations.as_ref() {
+        pool_observations
+            .load_mut()?
+            .update(old_sqrt_price, pool.sqrt_price, current_timestamp)?;
+    }
+
+    let seeds = pool_authority_seeds!(const_pda::pool_authority::BUMP);
+    anchor_spl::token_interface::burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_base_program.to_account_info(),
+            anchor_spl::token_interface::Burn {
+                mint: ctx.accounts.base_mint.to_account_info(),
+                from: ctx.accounts.base_vault.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            },
+            &[&seeds[..]],
+        ),
+        buyback_result.base_amount,
+    )?;
+
+    emit_cpi!(EvtBuybackAndBurn {
+        pool: ctx.accounts.pool.key(),
+        quote_amount: buyback_result.quote_amount,
+        burned_base_amount: buyback_result.base_amount,
+        total_burned_base_amount: pool.total_burned_base_amount,
+        next_sqrt_price: buyback_result.next_sqrt_price,
+    });
+
+    // the buyback can push the curve to the migration threshold
+    if pool.is_curve_complete(config.migration_quote_threshold) {
+        ctx.accounts.base_vault.reload()?;
+        process_curve_complete(
+            &config,
+            &mut pool,
+            ctx.accounts.base_vault.amount,
+            current_timestamp,
+        )?;
+
+        emit_cpi!(EvtCurveComplete {
+            pool: ctx.accounts.pool.key(),
+            config: ctx.accounts.config.key(),
+            base_reserve: pool.base_reserve,
+            quote_reserve: pool.quote_reserve,
+        })
+    }
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::{constants::seeds::REFERRER_PREFIX, state::Referrer, EvtClaimReferralFee, PoolError};
+
+/// Accounts for referrer to claim the referral fee accrued in a mint
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimReferralFeeCtx<'info> {
+    #[account(
+        seeds = [
+            REFERRER_PREFIX.as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump,
+        has_one = owner
+    )]
+    pub referrer: AccountLoader<'info, Referrer>,
+
+    /// The referrer token account of the mint, that accrues the referral fee
+    #[account(mut, token::authority = referrer, token::mint = token_mint, token::token_program = token_program)]
+    pub referrer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The receiver token account of the referral fee
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of the referral fee
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub owner: Signer<'info>,
+
+    /// Token program of the mint
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_claim_referral_fee(ctx: Context<ClaimReferralFeeCtx>) -> Result<()> {
+    let token_amount = ctx.accounts.referrer_token_account.amount;
+    require!(token_amount > 0, PoolError::AmountIsZero);
+
+    // the referrer signs for its token account
+    let owner_key = ctx.accounts.owner.key();
+    let bump = [ctx.bumps.referrer];
+    let seeds = [REFERRER_PREFIX.as_ref(), owner_key.as_ref(), &bump[..]];
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.referrer_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.token_account.to_account_info(),
+                authority: ctx.accounts.referrer.to_account_info(),
+            },
+            &[&seeds[..]],
+        ),
+        token_amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    emit_cpi!(EvtClaimReferralFee {
+        referrer: ctx.accounts.referrer.key(),
+        owner: ctx.accounts.owner.key(),
+        token_mint: ctx.accounts.token_mint.key(),
+        token_amount,
+    });
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::{
+    constants::seeds::POOL_OBSERVATIONS_PREFIX,
+    state::{PoolObservations, VirtualPool},
+    EvtCreatePoolObservations,
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreatePoolObservationsCtx<'info> {
+    pub pool: AccountLoader<'info, VirtualPool>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            POOL_OBSERVATIONS_PREFIX.as_ref(),
+            pool.key().as_ref(),
+        ],
+        bump,
+        space = 8 + PoolObservations::INIT_SPACE
+    )]
+    pub pool_observations: AccountLoader<'info, PoolObservations>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless, the pool observations are updated by the swaps of the pool that pass them in remaining accounts
+pub fn handle_create_pool_observations(ctx: Context<CreatePoolObservationsCtx>) -> Result<()> {
+    let pool = ctx.accounts.pool.load()?;
+
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    let mut pool_observations = ctx.accounts.pool_observations.load_init()?;
+    pool_observations.initialize(ctx.accounts.pool.key(), pool.sqrt_price, current_timestamp);
+
+    emit_cpi!(EvtCreatePoolObservations {
+        pool: ctx.accounts.pool.key(),
+        pool_observations: ctx.accounts.pool_observations.key(),
+    });
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use crate::{constants::seeds::REFERRER_PREFIX, state::Referrer, EvtCreateReferrer};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateReferrerCtx<'info> {
+    #[account(
+        init,
+        payer = owner,
+        seeds = [
+            REFERRER_PREFIX.as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump,
+        space = 8 + Referrer::INIT_SPACE
+    )]
+    pub referrer: AccountLoader<'info, Referrer>,
+
+    /// The wallet that refers swaps and claims the referral fee
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_referrer(ctx: Context<CreateReferrerCtx>) -> Result<()> {
+    let mut referrer = ctx.accounts.referrer.load_init()?;
+    referrer.initialize(ctx.accounts.owner.key());
+
+    emit_cpi!(EvtCreateReferrer {
+        referrer: ctx.accounts.referrer.key(),
+        owner: ctx.accounts.owner.key(),
+    });
+    Ok(())
+}
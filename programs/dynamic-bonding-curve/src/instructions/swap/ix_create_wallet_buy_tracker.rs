@@ -0,0 +1,35 @@
+use crate::{
+    constants::seeds::WALLET_BUY_TRACKER_PREFIX,
+    state::{VirtualPool, WalletBuyTracker},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateWalletBuyTrackerCtx<'info> {
+    pub pool: AccountLoader<'info, VirtualPool>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [
+            WALLET_BUY_TRACKER_PREFIX.as_ref(),
+            pool.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump,
+        space = 8 + WalletBuyTracker::INIT_SPACE
+    )]
+    pub wallet_buy_tracker: AccountLoader<'info, WalletBuyTracker>,
+
+    /// The wallet that will buy in the pool
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_wallet_buy_tracker(ctx: Context<CreateWalletBuyTrackerCtx>) -> Result<()> {
+    let mut wallet_buy_tracker = ctx.accounts.wallet_buy_tracker.load_init()?;
+    wallet_buy_tracker.initialize(ctx.accounts.pool.key(), ctx.accounts.owner.key());
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use crate::{
+    constants::seeds::WALLET_VOLUME_TRACKER_PREFIX,
+    state::{PoolConfig, WalletVolumeTracker},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateWalletVolumeTrackerCtx<'info> {
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [
+            WALLET_VOLUME_TRACKER_PREFIX.as_ref(),
+            config.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump,
+        space = 8 + WalletVolumeTracker::INIT_SPACE
+    )]
+    pub wallet_volume_tracker: AccountLoader<'info, WalletVolumeTracker>,
+
+    /// The wallet that will swap in the pools of the config
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_wallet_volume_tracker(
+    ctx: Context<CreateWalletVolumeTrackerCtx>,
+) -> Result<()> {
+    let mut wallet_volume_tracker = ctx.accounts.wallet_volume_tracker.load_init()?;
+    wallet_volume_tracker.initialize(ctx.accounts.config.key(), ctx.accounts.owner.key());
+    Ok(())
+}
@@ -4,6 +4,9 @@ use crate::instruction::InitializeVirtualPoolWithSplToken;
 use crate::instruction::InitializeVirtualPoolWithToken2022;
 use crate::instruction::Swap as SwapInstruction;
 use crate::instruction::Swap2 as Swap2Instruction;
+use crate::instruction::Swap3 as Swap3Instruction;
+use crate::instruction::SwapBatch as SwapBatchInstruction;
+use crate::instruction::SwapWithAllowlistProof as SwapWithAllowlistProofInstruction;
 use crate::math::safe_math::SafeMath;
 use crate::state::MigrationProgress;
 use crate::swap::swap_exact_in::process_swap_exact_in;
@@ -13,10 +16,18 @@ use crate::swap::{ProcessSwapParams, ProcessSwapResult};
 use crate::{
     activation_handler::get_current_point,
     const_pda,
+    constants::SWAP_HOOK_DISCRIMINATOR,
+    cpi_checker::cpi_with_account_lamport_and_owner_checking,
     params::swap::TradeDirection,
     state::fee::FeeMode,
-    state::{PoolConfig, VirtualPool},
-    token::{transfer_token_from_pool_authority, transfer_token_from_user},
+    state::{
+        PoolConfig, PoolConfigExtension, PoolObservations, PresaleAllowlist, Referrer, SwapHook,
+        SwapResult2, VirtualPool, VolumeFeeTiers, WalletBuyTracker, WalletVolumeTracker,
+    },
+    token::{
+        create_native_sol_token_account, is_native_mint, transfer_token_from_pool_authority,
+        transfer_token_from_user, unwrap_native_sol_to_user, wrap_native_sol_from_user,
+    },
     EvtSwap, PoolError,
 };
 use crate::{EvtCurveComplete, EvtSwap2};
@@ -24,7 +35,12 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::instruction::{
     get_processed_sibling_instruction, get_stack_height, Instruction,
 };
+use anchor_lang::solana_program::program::invoke;
 use anchor_lang::solana_program::sysvar;
+use anchor_lang::system_program;
+use anchor_lang::ZeroCopy;
+use anchor_spl::associated_token;
+use anchor_spl::token::accessor;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
@@ -46,6 +62,51 @@ pub struct SwapParameters2 {
     pub swap_mode: u8,
 }
 
+// same as SwapParameters2, with the price limit, the expiry and the native SOL option
+#[derive(AnchorSerialize, AnchorDeserialize, Default)]
+pub struct SwapParameters3 {
+    /// When it's exact in, partial fill, this will be amount_in. When it's exact out, this will be amount_out
+    pub amount_0: u64,
+    /// When it's exact in, partial fill, this will be minimum_amount_out. When it's exact out, this will be maximum_amount_in
+    pub amount_1: u64,
+    /// Swap mode, refer [SwapMode]
+    pub swap_mode: u8,
+    /// Price limit of the swap, 0 means no limit. When it's exact in, partial fill, the swap stops once the price reaches the limit and the unused input is refunded. When it's exact out, the swap fails if the price crosses the limit
+    pub sqrt_price_limit: u128,
+    /// Last point (slot or timestamp, depends on the activation type of the pool) that the swap can be executed, 0 means no expiry
+    pub expiry_point: u64,
+    /// Only for pool with native SOL as quote token. When it's true, the quote input is paid in lamports from the payer. If the payer's native SOL associated token account doesn't exist, it's created and closed to the payer after the swap, so the quote output is received in lamports. Sells fail if the account already exists. The system program and the associated token program must be passed in remaining accounts
+    pub use_native_sol: bool,
+}
+
+impl SwapParameters3 {
+    pub fn get_swap_parameters_2(&self) -> SwapParameters2 {
+        SwapParameters2 {
+            amount_0: self.amount_0,
+            amount_1: self.amount_1,
+            swap_mode: self.swap_mode,
+        }
+    }
+}
+
+/// Merkle proof that the payer is allowlisted with the allocation, for the allowlist phase
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AllowlistProof {
+    /// max quote amount (included fee) the payer can spend to buy base token in the allowlist phase
+    pub allocation: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// Data passed to the hook program after a swap, prefixed with [SWAP_HOOK_DISCRIMINATOR]
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SwapHookData {
+    pub pool: Pubkey,
+    pub trader: Pubkey,
+    /// Trade direction of the swap, refer [TradeDirection]
+    pub trade_direction: u8,
+    pub swap_result: SwapResult2,
+}
+
 #[repr(u8)]
 #[derive(
     Clone,
@@ -79,13 +140,13 @@ pub struct SwapCtx<'info> {
     #[account(mut, has_one = base_vault, has_one = quote_vault, has_one = config)]
     pub pool: AccountLoader<'info, VirtualPool>,
 
-    /// The user token account for input token
+    /// CHECK: The user token account for input token, validated by the token program on transfer. For native SOL swap, it's created if it doesn't exist
     #[account(mut)]
-    pub input_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub input_token_account: UncheckedAccount<'info>,
 
-    /// The user token account for output token
+    /// CHECK: The user token account for output token, validated by the token program on transfer. For native SOL swap, it's created if it doesn't exist
     #[account(mut)]
-    pub output_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub output_token_account: UncheckedAccount<'info>,
 
     /// The vault token account for base token
     #[account(mut, token::token_program = token_base_program, token::mint = base_mint)]
@@ -117,28 +178,23 @@ pub struct SwapCtx<'info> {
 
 impl<'info> SwapCtx<'info> {
     /// Get the trading direction of the current swap. Eg: USDT -> USDC
-    pub fn get_trade_direction(&self) -> TradeDirection {
-        if self.input_token_account.mint == self.base_mint.key() {
-            return TradeDirection::BaseToQuote;
+    pub fn get_trade_direction(&self) -> Result<TradeDirection> {
+        // the input token account can only be missing for native SOL buys
+        if !self.input_token_account.data_is_empty()
+            && accessor::mint(&self.input_token_account)? == self.base_mint.key()
+        {
+            return Ok(TradeDirection::BaseToQuote);
         }
-        TradeDirection::QuoteToBase
+        Ok(TradeDirection::QuoteToBase)
     }
 }
 
 pub fn handle_swap_wrapper<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, SwapCtx<'info>>,
-    params: SwapParameters2,
+    params: SwapParameters3,
+    allowlist_proof: Option<AllowlistProof>,
 ) -> Result<()> {
-    let SwapParameters2 {
-        amount_0,
-        amount_1,
-        swap_mode,
-        ..
-    } = params;
-
-    let swap_mode = SwapMode::try_from(swap_mode).map_err(|_| PoolError::TypeCastFailed)?;
-
-    let trade_direction = ctx.accounts.get_trade_direction();
+    let trade_direction = ctx.accounts.get_trade_direction()?;
     let (
         token_in_mint,
         token_out_mint,
@@ -165,30 +221,75 @@ pub fn handle_swap_wrapper<'c: 'info, 'info>(
         ),
     };
 
-    require!(amount_0 > 0, PoolError::AmountIsZero);
-
-    let has_referral = ctx.accounts.referral_token_account.is_some();
+    let quote_token_account = match trade_direction {
+        TradeDirection::BaseToQuote => &ctx.accounts.output_token_account,
+        TradeDirection::QuoteToBase => &ctx.accounts.input_token_account,
+    };
+    // the payer's native SOL token account is only closed after the swap when the swap creates it
+    let is_native_sol_account_created = if params.use_native_sol {
+        require!(
+            is_native_mint(&ctx.accounts.quote_mint.key()),
+            PoolError::InvalidNativeSolSwap
+        );
+        let is_native_sol_account_created = quote_token_account.data_is_empty();
+        // the existing account can't be closed, so the quote output would be left as wrapped SOL
+        require!(
+            is_native_sol_account_created || trade_direction == TradeDirection::QuoteToBase,
+            PoolError::NativeSolTokenAccountExists
+        );
+        if is_native_sol_account_created {
+            create_native_sol_token_account(
+                ctx.accounts.payer.to_account_info(),
+                quote_token_account.to_account_info(),
+                ctx.accounts.quote_mint.to_account_info(),
+                ctx.accounts.token_quote_program.to_account_info(),
+                get_system_program(ctx.remaining_accounts)?,
+                get_associated_token_program(ctx.remaining_accounts)?,
+            )?;
+        }
+        is_native_sol_account_created
+    } else {
+        false
+    };
 
     let config = ctx.accounts.config.load()?;
     let mut pool = ctx.accounts.pool.load_mut()?;
 
-    let current_point = get_current_point(config.activation_type)?;
-
-    // another validation to prevent snipers to craft multiple swap instructions in 1 tx
-    // (if we dont do this, they are able to concat 16 swap instructions in 1 tx)
-    let rate_limiter = config.pool_fees.base_fee.get_fee_rate_limiter();
-    if let Ok(rate_limiter) = &rate_limiter {
-        if rate_limiter.is_rate_limiter_applied(
-            current_point,
-            pool.activation_point,
-            trade_direction,
-        )? {
-            validate_single_swap_instruction(&ctx.accounts.pool.key(), ctx.remaining_accounts)?;
+    // without a referral token account, the referral fee is sent to the referrer's token account of the fee mint
+    let referrer = if ctx.accounts.referral_token_account.is_none() {
+        find_program_account::<Referrer>(ctx.remaining_accounts, true, |_| true)?
+    } else {
+        None
+    };
+    let referrer_token_account = match referrer.as_ref() {
+        Some(referrer) => {
+            let fee_mint =
+                if FeeMode::get_fee_mode(config.collect_fee_mode, trade_direction, false)?
+                    .fees_on_base_token
+                {
+                    ctx.accounts.base_mint.key()
+                } else {
+                    ctx.accounts.quote_mint.key()
+                };
+            Some(find_referrer_token_account(
+                ctx.remaining_accounts,
+                &referrer.key(),
+                &fee_mint,
+            )?)
         }
-    }
+        None => None,
+    };
+    let referral_token_account = ctx
+        .accounts
+        .referral_token_account
+        .as_ref()
+        .map(|referral_token_account| referral_token_account.to_account_info())
+        .or(referrer_token_account);
+    let has_referral = referral_token_account.is_some();
 
     let eligible_for_first_swap_with_min_fee = config.is_first_swap_with_min_fee_enabled()
         && pool.is_first_swap()
+        && referrer.is_none()
         && validate_contain_initialize_pool_ix_and_no_cpi(
             &ctx.accounts.pool.key(),
             &ctx.accounts.referral_token_account,
@@ -196,52 +297,41 @@ pub fn handle_swap_wrapper<'c: 'info, 'info>(
         )
         .is_ok();
 
-    // validate if it is over threshold
-    require!(
-        !pool.is_curve_complete(config.migration_quote_threshold),
-        PoolError::PoolIsCompleted
-    );
-
-    // update for dynamic fee reference
-    let current_timestamp = Clock::get()?.unix_timestamp as u64;
-    pool.update_pre_swap(&config, current_timestamp)?;
-
-    let fee_mode = &FeeMode::get_fee_mode(config.collect_fee_mode, trade_direction, has_referral)?;
-
-    let process_swap_params = ProcessSwapParams {
-        pool: &mut pool,
-        config: &config,
+    let ExecuteSwapResult {
+        swap_result_2,
+        swap_in_parameters,
         fee_mode,
+        current_timestamp,
+    } = execute_swap(ExecuteSwapParams {
+        config: &config,
+        pool: &mut pool,
+        pool_key: &ctx.accounts.pool.key(),
+        payer: ctx.accounts.payer.key,
         trade_direction,
-        current_point,
-        amount_0,
-        amount_1,
+        swap_parameters: &params,
+        has_referral,
         eligible_for_first_swap_with_min_fee,
-    };
-
-    let ProcessSwapResult {
-        swap_result: swap_result_2,
-        swap_in_parameters,
-    } = match swap_mode {
-        SwapMode::ExactIn => process_swap_exact_in(process_swap_params)?,
-        SwapMode::PartialFill => process_swap_partial_fill(process_swap_params)?,
-        SwapMode::ExactOut => process_swap_exact_out(process_swap_params)?,
-    };
-
+        allowlist_proof: allowlist_proof.as_ref(),
+        remaining_accounts: ctx.remaining_accounts,
+    })?;
     let swap_result = swap_result_2.get_swap_result();
-    pool.apply_swap_result(
-        &config,
-        &swap_result,
-        fee_mode,
-        trade_direction,
-        current_timestamp,
-    )?;
+
+    // wrap the quote input from the payer's lamports
+    if params.use_native_sol && trade_direction == TradeDirection::QuoteToBase {
+        wrap_native_sol_from_user(
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.input_token_account.to_account_info(),
+            ctx.accounts.token_quote_program.to_account_info(),
+            get_system_program(ctx.remaining_accounts)?,
+            swap_result_2.included_fee_input_amount,
+        )?;
+    }
 
     // send to reserve
     transfer_token_from_user(
         &ctx.accounts.payer,
         token_in_mint,
-        &ctx.accounts.input_token_account,
+        ctx.accounts.input_token_account.to_account_info(),
         input_vault_account,
         input_program,
         swap_result_2.included_fee_input_amount,
@@ -257,14 +347,23 @@ pub fn handle_swap_wrapper<'c: 'info, 'info>(
         swap_result.output_amount,
     )?;
 
+    // unwrap the payer's quote token account, the quote output (if any) is received in lamports
+    if is_native_sol_account_created {
+        unwrap_native_sol_to_user(
+            ctx.accounts.payer.to_account_info(),
+            quote_token_account.to_account_info(),
+            ctx.accounts.token_quote_program.to_account_info(),
+        )?;
+    }
+
     // send to referral
-    if let Some(referral_token_account) = ctx.accounts.referral_token_account.as_ref() {
+    if let Some(referral_token_account) = referral_token_account {
         if fee_mode.fees_on_base_token {
             transfer_token_from_pool_authority(
                 ctx.accounts.pool_authority.to_account_info(),
                 &ctx.accounts.base_mint,
                 &ctx.accounts.base_vault,
-                referral_token_account.to_account_info(),
+                referral_token_account,
                 &ctx.accounts.token_base_program,
                 swap_result.referral_fee,
             )?;
@@ -273,13 +372,17 @@ pub fn handle_swap_wrapper<'c: 'info, 'info>(
                 ctx.accounts.pool_authority.to_account_info(),
                 &ctx.accounts.quote_mint,
                 &ctx.accounts.quote_vault,
-                referral_token_account.to_account_info(),
+                referral_token_account,
                 &ctx.accounts.token_quote_program,
                 swap_result.referral_fee,
             )?;
         }
     }
 
+    if let Some(referrer) = referrer.as_ref() {
+        referrer.load_mut()?.record_referred_swap()?;
+    }
+
     emit_cpi!(EvtSwap {
         pool: ctx.accounts.pool.key(),
         config: ctx.accounts.config.key(),
@@ -296,7 +399,7 @@ pub fn handle_swap_wrapper<'c: 'info, 'info>(
         config: ctx.accounts.config.key(),
         trade_direction: trade_direction.into(),
         has_referral,
-        swap_parameters: params,
+        swap_parameters: params.get_swap_parameters_2(),
         swap_result: swap_result_2,
         quote_reserve_amount: pool.quote_reserve,
         migration_threshold: config.migration_quote_threshold,
@@ -305,33 +408,12 @@ pub fn handle_swap_wrapper<'c: 'info, 'info>(
 
     if pool.is_curve_complete(config.migration_quote_threshold) {
         ctx.accounts.base_vault.reload()?;
-        // validate if base reserve is enough token for migration
-        let base_vault_balance = ctx.accounts.base_vault.amount;
-
-        let required_base_balance = config
-            .migration_base_threshold
-            .safe_add(pool.get_protocol_and_trading_base_fee()?)?
-            .safe_add(
-                config
-                    .locked_vesting_config
-                    .to_locked_vesting_params()
-                    .get_total_amount()?,
-            )?;
-
-        require!(
-            base_vault_balance >= required_base_balance,
-            PoolError::InsufficientLiquidityForMigration
-        );
-
-        // set finish time and migration progress
-        pool.finish_curve_timestamp = current_timestamp;
-
-        let locked_vesting_params = config.locked_vesting_config.to_locked_vesting_params();
-        if locked_vesting_params.has_vesting() {
-            pool.set_migration_progress(MigrationProgress::PostBondingCurve.into());
-        } else {
-            pool.set_migration_progress(MigrationProgress::LockedVesting.into());
-        }
+        process_curve_complete(
+            &config,
+            &mut pool,
+            ctx.accounts.base_vault.amount,
+            current_timestamp,
+        )?;
 
         emit_cpi!(EvtCurveComplete {
             pool: ctx.accounts.pool.key(),
@@ -341,6 +423,304 @@ pub fn handle_swap_wrapper<'c: 'info, 'info>(
         })
     }
 
+    if config.is_swap_hook_enabled() {
+        // release the pool, so it can be passed to the hook program
+        drop(pool);
+        invoke_swap_hook(
+            &ctx.accounts.config.to_account_info(),
+            &ctx.accounts.pool.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            trade_direction,
+            swap_result_2,
+            ctx.remaining_accounts,
+        )?;
+    }
+
+    Ok(())
+}
+
+pub(crate) struct ExecuteSwapParams<'a, 'c: 'info, 'info> {
+    pub config: &'a PoolConfig,
+    pub pool: &'a mut VirtualPool,
+    pub pool_key: &'a Pubkey,
+    pub payer: &'a Pubkey,
+    pub trade_direction: TradeDirection,
+    pub swap_parameters: &'a SwapParameters3,
+    pub has_referral: bool,
+    pub eligible_for_first_swap_with_min_fee: bool,
+    pub allowlist_proof: Option<&'a AllowlistProof>,
+    pub remaining_accounts: &'c [AccountInfo<'info>],
+}
+
+pub(crate) struct ExecuteSwapResult {
+    pub swap_result_2: SwapResult2,
+    pub swap_in_parameters: SwapParameters,
+    pub fee_mode: FeeMode,
+    pub current_timestamp: u64,
+}
+
+/// Validate the swap and apply it to the pool state, token transfers are left to the caller
+pub(crate) fn execute_swap<'a, 'c: 'info, 'info>(
+    params: ExecuteSwapParams<'a, 'c, 'info>,
+) -> Result<ExecuteSwapResult> {
+    let ExecuteSwapParams {
+        config,
+        pool,
+        pool_key,
+        payer,
+        trade_direction,
+        swap_parameters,
+        has_referral,
+        eligible_for_first_swap_with_min_fee,
+        allowlist_proof,
+        remaining_accounts,
+    } = params;
+
+    let SwapParameters3 {
+        amount_0,
+        amount_1,
+        swap_mode,
+        sqrt_price_limit,
+        expiry_point,
+        ..
+    } = *swap_parameters;
+
+    let swap_mode = SwapMode::try_from(swap_mode).map_err(|_| PoolError::TypeCastFailed)?;
+    let sqrt_price_limit = (sqrt_price_limit != 0).then_some(sqrt_price_limit);
+
+    require!(amount_0 > 0, PoolError::AmountIsZero);
+
+    let current_point = get_current_point(config.activation_type)?;
+
+    let config_extension = if config.has_config_extension() {
+        find_program_account::<PoolConfigExtension>(
+            remaining_accounts,
+            false,
+            |config_extension| config_extension.config.eq(&pool.config),
+        )?
+    } else {
+        None
+    };
+    let fee_config = config.get_fee_config(
+        trade_direction,
+        config_extension
+            .as_ref()
+            .map(|config_extension| config_extension.load())
+            .transpose()?
+            .as_deref(),
+        current_point,
+        pool.activation_point,
+    )?;
+    let config = fee_config.as_deref().unwrap_or(config);
+
+    // reject the swap if the transaction lands too late
+    require!(
+        expiry_point == 0 || current_point <= expiry_point,
+        PoolError::SwapExpired
+    );
+
+    // another validation to prevent snipers to craft multiple swap instructions in 1 tx
+    // (if we dont do this, they are able to concat 16 swap instructions in 1 tx)
+    let rate_limiter = config.pool_fees.base_fee.get_fee_rate_limiter();
+    if let Ok(rate_limiter) = &rate_limiter {
+        if rate_limiter.is_rate_limiter_applied(
+            current_point,
+            pool.activation_point,
+            trade_direction,
+        )? {
+            validate_single_swap_instruction(pool_key, remaining_accounts)?;
+        }
+    }
+
+    // validate if it is over threshold
+    require!(
+        !pool.is_curve_complete(config.migration_quote_threshold),
+        PoolError::PoolIsCompleted
+    );
+
+    // halt swaps until the volatility decays under the circuit breaker threshold
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    require!(
+        !pool.is_circuit_breaker_triggered(config, current_timestamp)?,
+        PoolError::CircuitBreakerTriggered
+    );
+
+    // update for dynamic fee reference
+    pool.update_pre_swap(config, current_timestamp)?;
+
+    let mut fee_mode =
+        FeeMode::get_fee_mode(config.collect_fee_mode, trade_direction, has_referral)?;
+
+    // wallets with high volume in the config's pools get the discount of their volume tier
+    let wallet_volume_tracker = find_program_account::<WalletVolumeTracker>(
+        remaining_accounts,
+        true,
+        |wallet_volume_tracker| {
+            wallet_volume_tracker.config.eq(&pool.config) && wallet_volume_tracker.owner.eq(payer)
+        },
+    )?;
+    if let Some(wallet_volume_tracker) = wallet_volume_tracker.as_ref() {
+        let volume_fee_tiers = find_program_account::<VolumeFeeTiers>(
+            remaining_accounts,
+            false,
+            |volume_fee_tiers| volume_fee_tiers.config.eq(&pool.config),
+        )?;
+        if let Some(volume_fee_tiers) = volume_fee_tiers {
+            let tier_fee_discount_bps = volume_fee_tiers
+                .load()?
+                .get_fee_discount_bps(wallet_volume_tracker.load()?.total_volume);
+            fee_mode.fee_discount_bps = config.get_total_fee_discount_bps(tier_fee_discount_bps)?;
+        }
+    }
+
+    let process_swap_params = ProcessSwapParams {
+        pool: &mut *pool,
+        config,
+        fee_mode: &fee_mode,
+        trade_direction,
+        current_point,
+        amount_0,
+        amount_1,
+        eligible_for_first_swap_with_min_fee,
+        sqrt_price_limit,
+    };
+
+    let ProcessSwapResult {
+        swap_result: swap_result_2,
+        swap_in_parameters,
+    } = match swap_mode {
+        SwapMode::ExactIn => process_swap_exact_in(process_swap_params)?,
+        SwapMode::PartialFill => process_swap_partial_fill(process_swap_params)?,
+        SwapMode::ExactOut => process_swap_exact_out(process_swap_params)?,
+    };
+
+    // only allowlisted wallets can buy in the allowlist phase, up to their allocation
+    if config.is_allowlist_phase_applied(current_point, pool.activation_point, trade_direction)? {
+        let allowlist_proof = allowlist_proof.ok_or(PoolError::AllowlistPhaseActive)?;
+        let presale_allowlist = find_program_account::<PresaleAllowlist>(
+            remaining_accounts,
+            false,
+            |presale_allowlist| presale_allowlist.config.eq(&pool.config),
+        )?
+        .ok_or(PoolError::MissingPresaleAllowlist)?;
+        let presale_allowlist = presale_allowlist.load()?;
+        presale_allowlist.verify_proof(
+            payer,
+            allowlist_proof.allocation,
+            &allowlist_proof.proof,
+        )?;
+
+        let wallet_buy_tracker = find_program_account::<WalletBuyTracker>(
+            remaining_accounts,
+            false,
+            |wallet_buy_tracker| {
+                wallet_buy_tracker.pool.eq(pool_key) && wallet_buy_tracker.owner.eq(payer)
+            },
+        )?
+        .ok_or(PoolError::MissingWalletBuyTracker)?;
+        wallet_buy_tracker
+            .load_mut()?
+            .accumulate_allowlist_buy_amount(
+                swap_result_2.included_fee_input_amount,
+                allowlist_proof.allocation,
+            )?;
+    }
+
+    // validate the max quote amount a wallet can spend in the buy cap window
+    if config.is_wallet_buy_cap_applied(current_point, pool.activation_point, trade_direction)? {
+        let config_extension = find_program_account::<PoolConfigExtension>(
+            remaining_accounts,
+            false,
+            |config_extension| config_extension.config.eq(&pool.config),
+        )?
+        .ok_or(PoolError::MissingConfigExtension)?;
+        let wallet_buy_tracker = find_program_account::<WalletBuyTracker>(
+            remaining_accounts,
+            false,
+            |wallet_buy_tracker| {
+                wallet_buy_tracker.pool.eq(pool_key) && wallet_buy_tracker.owner.eq(payer)
+            },
+        )?
+        .ok_or(PoolError::MissingWalletBuyTracker)?;
+        wallet_buy_tracker.load_mut()?.accumulate_buy_amount(
+            swap_result_2.included_fee_input_amount,
+            config_extension.load()?.max_buy_amount_per_wallet,
+        )?;
+    }
+
+    let swap_result = swap_result_2.get_swap_result();
+
+    if let Some(wallet_volume_tracker) = wallet_volume_tracker {
+        let quote_volume = match trade_direction {
+            TradeDirection::QuoteToBase => swap_result_2.included_fee_input_amount,
+            TradeDirection::BaseToQuote => swap_result.output_amount,
+        };
+        wallet_volume_tracker
+            .load_mut()?
+            .accumulate_volume(quote_volume)?;
+    }
+
+    let old_sqrt_price = pool.sqrt_price;
+    pool.apply_swap_result(
+        config,
+        &swap_result,
+        &fee_mode,
+        trade_direction,
+        current_timestamp,
+    )?;
+
+    // the pool observations are optional, swaps without them leave a gap that restarts the observations
+    let pool_observations =
+        find_program_account::<PoolObservations>(remaining_accounts, true, |pool_observations| {
+            pool_observations.pool.eq(pool_key)
+        })?;
+    if let Some(pool_observations) = pool_observations {
+        pool_observations
+            .load_mut()?
+            .update(old_sqrt_price, pool.sqrt_price, current_timestamp)?;
+    }
+
+    Ok(ExecuteSwapResult {
+        swap_result_2,
+        swap_in_parameters,
+        fee_mode,
+        current_timestamp,
+    })
+}
+
+/// Validate the base reserve for migration and move the pool to the next migration progress once the curve is complete
+pub(crate) fn process_curve_complete(
+    config: &PoolConfig,
+    pool: &mut VirtualPool,
+    base_vault_balance: u64,
+    current_timestamp: u64,
+) -> Result<()> {
+    // validate if base reserve is enough token for migration
+    let required_base_balance = config
+        .migration_base_threshold
+        .safe_add(pool.get_protocol_and_trading_base_fee()?)?
+        .safe_add(
+            config
+                .locked_vesting_config
+                .to_locked_vesting_params()
+                .get_total_amount()?,
+        )?;
+
+    require!(
+        base_vault_balance >= required_base_balance,
+        PoolError::InsufficientLiquidityForMigration
+    );
+
+    // set finish time and migration progress
+    pool.finish_curve_timestamp = current_timestamp;
+
+    let locked_vesting_params = config.locked_vesting_config.to_locked_vesting_params();
+    if locked_vesting_params.has_vesting() {
+        pool.set_migration_progress(MigrationProgress::PostBondingCurve.into());
+    } else {
+        pool.set_migration_progress(MigrationProgress::LockedVesting.into());
+    }
+
     Ok(())
 }
 
@@ -408,13 +788,152 @@ pub fn validate_single_swap_instruction<'c, 'info>(
     Ok(())
 }
 
+/// Find the account of type `T` that matches the predicate in remaining accounts. It can be at any position of remaining accounts,
+/// because the first one is reserved for instruction sysvar. Only accounts owned by the program, and writable ones if `writable` is set, are considered
+pub fn find_program_account<'c: 'info, 'info, T: ZeroCopy + Owner>(
+    remaining_accounts: &'c [AccountInfo<'info>],
+    writable: bool,
+    predicate: impl Fn(&T) -> bool,
+) -> Result<Option<AccountLoader<'info, T>>> {
+    for account_info in remaining_accounts.iter() {
+        if !account_info.owner.eq(&crate::ID) || (writable && !account_info.is_writable) {
+            continue;
+        }
+        let Ok(account_loader) = AccountLoader::<T>::try_from(account_info) else {
+            continue;
+        };
+        let is_matched = predicate(&*account_loader.load()?);
+        if is_matched {
+            return Ok(Some(account_loader));
+        }
+    }
+    Ok(None)
+}
+
+/// Token account of the fee mint owned by the referrer, that receives the referral fee
+pub fn find_referrer_token_account<'c: 'info, 'info>(
+    remaining_accounts: &'c [AccountInfo<'info>],
+    referrer: &Pubkey,
+    fee_mint: &Pubkey,
+) -> Result<AccountInfo<'info>> {
+    remaining_accounts
+        .iter()
+        .find(|account_info| {
+            account_info.is_writable
+                && InterfaceAccount::<TokenAccount>::try_from(*account_info).is_ok_and(
+                    |token_account| {
+                        token_account.owner.eq(referrer) && token_account.mint.eq(fee_mint)
+                    },
+                )
+        })
+        .cloned()
+        .ok_or_else(|| PoolError::MissingReferrerTokenAccount.into())
+}
+
+pub fn get_system_program<'c: 'info, 'info>(
+    remaining_accounts: &'c [AccountInfo<'info>],
+) -> Result<AccountInfo<'info>> {
+    remaining_accounts
+        .iter()
+        .find(|account_info| account_info.key.eq(&system_program::ID))
+        .cloned()
+        .ok_or_else(|| PoolError::MissingSystemProgram.into())
+}
+
+pub fn get_associated_token_program<'c: 'info, 'info>(
+    remaining_accounts: &'c [AccountInfo<'info>],
+) -> Result<AccountInfo<'info>> {
+    remaining_accounts
+        .iter()
+        .find(|account_info| account_info.key.eq(&associated_token::ID))
+        .cloned()
+        .ok_or_else(|| PoolError::MissingAssociatedTokenProgram.into())
+}
+
+/// Invoke the hook program registered for the config. The swap accounts are passed as read only and
+/// nothing is passed as signer, so the hook can't move the trader's funds.
+/// Accounts after the hook program in remaining accounts are forwarded to the hook program as read only
+pub(crate) fn invoke_swap_hook<'c: 'info, 'info>(
+    config: &AccountInfo<'info>,
+    pool: &AccountInfo<'info>,
+    trader: &AccountInfo<'info>,
+    trade_direction: TradeDirection,
+    swap_result: SwapResult2,
+    remaining_accounts: &'c [AccountInfo<'info>],
+) -> Result<()> {
+    let hook_program = find_program_account::<SwapHook>(remaining_accounts, false, |swap_hook| {
+        swap_hook.config.eq(config.key)
+    })?
+    .ok_or(PoolError::MissingSwapHook)?
+    .load()?
+    .hook_program;
+    let hook_program_index = remaining_accounts
+        .iter()
+        .position(|account_info| account_info.key.eq(&hook_program))
+        .ok_or(PoolError::MissingSwapHook)?;
+    let hook_program_account = &remaining_accounts[hook_program_index];
+    require!(hook_program_account.executable, PoolError::InvalidSwapHook);
+    let forwarded_accounts = &remaining_accounts[hook_program_index.safe_add(1)?..];
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*config.key, false),
+        AccountMeta::new_readonly(*pool.key, false),
+        AccountMeta::new_readonly(*trader.key, false),
+    ];
+    accounts.extend(
+        forwarded_accounts
+            .iter()
+            .map(|account_info| AccountMeta::new_readonly(*account_info.key, false)),
+    );
+
+    let mut data = SWAP_HOOK_DISCRIMINATOR.to_vec();
+    SwapHookData {
+        pool: *pool.key,
+        trader: *trader.key,
+        trade_direction: trade_direction.into(),
+        swap_result,
+    }
+    .serialize(&mut data)?;
+
+    let instruction = Instruction {
+        program_id: hook_program,
+        accounts,
+        data,
+    };
+
+    let mut account_infos = vec![
+        config.clone(),
+        pool.clone(),
+        trader.clone(),
+        hook_program_account.clone(),
+    ];
+    account_infos.extend_from_slice(forwarded_accounts);
+
+    cpi_with_account_lamport_and_owner_checking(
+        || {
+            invoke(&instruction, &account_infos)?;
+            Ok(())
+        },
+        trader.clone(),
+    )
+}
+
 fn is_instruction_include_pool_swap(instruction: &Instruction, pool: &Pubkey) -> bool {
     let instruction_discriminator = &instruction.data[..8];
     if instruction_discriminator.eq(SwapInstruction::DISCRIMINATOR)
         || instruction_discriminator.eq(Swap2Instruction::DISCRIMINATOR)
+        || instruction_discriminator.eq(Swap3Instruction::DISCRIMINATOR)
+        || instruction_discriminator.eq(SwapWithAllowlistProofInstruction::DISCRIMINATOR)
     {
         return instruction.accounts[2].pubkey.eq(pool);
     }
+    // pools of batch swap are passed in remaining accounts
+    if instruction_discriminator.eq(SwapBatchInstruction::DISCRIMINATOR) {
+        return instruction
+            .accounts
+            .iter()
+            .any(|account| account.pubkey.eq(pool));
+    }
     false
 }
 
@@ -0,0 +1,203 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    const_pda,
+    constants::MAX_SWAP_BATCH_LEGS,
+    params::swap::TradeDirection,
+    safe_math::SafeMath,
+    state::{PoolConfig, VirtualPool},
+    token::{transfer_token_from_pool_authority, transfer_token_from_user},
+    EvtCurveComplete, EvtSwap2, PoolError,
+};
+
+use super::{
+    execute_swap, invoke_swap_hook, process_curve_complete, ExecuteSwapParams, ExecuteSwapResult,
+    SwapParameters3,
+};
+
+/// Number of remaining accounts of each leg: config, pool, base_vault, quote_vault, base_mint, user base token account and base token program
+const SWAP_BATCH_LEG_ACCOUNTS_LEN: usize = 7;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SwapBatchLeg {
+    /// Trade direction of the leg, refer [TradeDirection]
+    pub trade_direction: u8,
+    pub params: SwapParameters3,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SwapBatchCtx<'info> {
+    /// CHECK: pool authority
+    #[account(
+        address = const_pda::pool_authority::ID,
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    /// The user token account for quote token, shared by all legs
+    #[account(mut, token::token_program = token_quote_program, token::mint = quote_mint)]
+    pub quote_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of quote token, shared by all legs
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The user performing the swaps
+    pub payer: Signer<'info>,
+
+    /// Token quote program
+    pub token_quote_program: Interface<'info, TokenInterface>,
+}
+
+// remaining accounts: instruction sysvar, then SWAP_BATCH_LEG_ACCOUNTS_LEN accounts for each leg, then optional accounts (eg: wallet buy trackers)
+pub fn handle_swap_batch<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, SwapBatchCtx<'info>>,
+    legs: Vec<SwapBatchLeg>,
+) -> Result<()> {
+    require!(
+        !legs.is_empty() && legs.len() <= MAX_SWAP_BATCH_LEGS,
+        PoolError::InvalidSwapBatchLegs
+    );
+
+    let leg_accounts_len = legs.len().safe_mul(SWAP_BATCH_LEG_ACCOUNTS_LEN)?;
+    let leg_accounts = ctx
+        .remaining_accounts
+        .get(1..=leg_accounts_len)
+        .ok_or(PoolError::InvalidSwapBatchLegs)?;
+
+    let mut pool_keys: Vec<Pubkey> = Vec::with_capacity(legs.len());
+
+    for (leg, accounts) in legs
+        .into_iter()
+        .zip(leg_accounts.chunks_exact(SWAP_BATCH_LEG_ACCOUNTS_LEN))
+    {
+        let config_loader = AccountLoader::<PoolConfig>::try_from(&accounts[0])?;
+        let pool_loader = AccountLoader::<VirtualPool>::try_from(&accounts[1])?;
+        let mut base_vault = InterfaceAccount::<TokenAccount>::try_from(&accounts[2])?;
+        let quote_vault = InterfaceAccount::<TokenAccount>::try_from(&accounts[3])?;
+        let base_mint = InterfaceAccount::<Mint>::try_from(&accounts[4])?;
+        let base_token_account = InterfaceAccount::<TokenAccount>::try_from(&accounts[5])?;
+        let token_base_program = Interface::<TokenInterface>::try_from(&accounts[6])?;
+
+        // a pool can only be swapped once in a batch, so the rate limiter single swap validation still holds
+        let pool_key = pool_loader.key();
+        require!(
+            !pool_keys.contains(&pool_key),
+            PoolError::InvalidSwapBatchLegs
+        );
+        pool_keys.push(pool_key);
+
+        // native SOL is only supported in swap2, because the quote token account is shared by all legs
+        require!(!leg.params.use_native_sol, PoolError::InvalidSwapBatchLegs);
+
+        let trade_direction =
+            TradeDirection::try_from(leg.trade_direction).map_err(|_| PoolError::TypeCastFailed)?;
+
+        let config = config_loader.load()?;
+        let mut pool = pool_loader.load_mut()?;
+
+        require!(
+            pool.config == config_loader.key()
+                && pool.base_vault == base_vault.key()
+                && pool.quote_vault == quote_vault.key()
+                && pool.base_mint == base_mint.key()
+                && config.quote_mint == ctx.accounts.quote_mint.key(),
+            PoolError::InvalidAccount
+        );
+
+        let ExecuteSwapResult {
+            swap_result_2,
+            current_timestamp,
+            ..
+        } = execute_swap(ExecuteSwapParams {
+            config: &config,
+            pool: &mut pool,
+            pool_key: &pool_key,
+            payer: ctx.accounts.payer.key,
+            trade_direction,
+            swap_parameters: &leg.params,
+            has_referral: false,
+            eligible_for_first_swap_with_min_fee: false,
+            allowlist_proof: None,
+            remaining_accounts: ctx.remaining_accounts,
+        })?;
+
+        match trade_direction {
+            TradeDirection::BaseToQuote => {
+                transfer_token_from_user(
+                    &ctx.accounts.payer,
+                    &base_mint,
+                    base_token_account.to_account_info(),
+                    &base_vault,
+                    &token_base_program,
+                    swap_result_2.included_fee_input_amount,
+                )?;
+                transfer_token_from_pool_authority(
+                    ctx.accounts.pool_authority.to_account_info(),
+                    &ctx.accounts.quote_mint,
+                    &quote_vault,
+                    ctx.accounts.quote_token_account.to_account_info(),
+                    &ctx.accounts.token_quote_program,
+                    swap_result_2.output_amount,
+                )?;
+            }
+            TradeDirection::QuoteToBase => {
+                transfer_token_from_user(
+                    &ctx.accounts.payer,
+                    &ctx.accounts.quote_mint,
+                    ctx.accounts.quote_token_account.to_account_info(),
+                    &quote_vault,
+                    &ctx.accounts.token_quote_program,
+                    swap_result_2.included_fee_input_amount,
+                )?;
+                transfer_token_from_pool_authority(
+                    ctx.accounts.pool_authority.to_account_info(),
+                    &base_mint,
+                    &base_vault,
+                    base_token_account.to_account_info(),
+                    &token_base_program,
+                    swap_result_2.output_amount,
+                )?;
+            }
+        }
+
+        emit_cpi!(EvtSwap2 {
+            pool: pool_key,
+            config: config_loader.key(),
+            trade_direction: trade_direction.into(),
+            has_referral: false,
+            swap_parameters: leg.params.get_swap_parameters_2(),
+            swap_result: swap_result_2,
+            quote_reserve_amount: pool.quote_reserve,
+            migration_threshold: config.migration_quote_threshold,
+            current_timestamp,
+        });
+
+        if pool.is_curve_complete(config.migration_quote_threshold) {
+            base_vault.reload()?;
+            process_curve_complete(&config, &mut pool, base_vault.amount, current_timestamp)?;
+
+            emit_cpi!(EvtCurveComplete {
+                pool: pool_key,
+                config: config_loader.key(),
+                base_reserve: pool.base_reserve,
+                quote_reserve: pool.quote_reserve,
+            })
+        }
+
+        if config.is_swap_hook_enabled() {
+            // release the pool, so it can be passed to the hook program
+            drop(pool);
+            invoke_swap_hook(
+                &accounts[0],
+                &accounts[1],
+                &ctx.accounts.payer.to_account_info(),
+                trade_direction,
+                swap_result_2,
+                ctx.remaining_accounts,
+            )?;
+        }
+    }
+
+    Ok(())
+}
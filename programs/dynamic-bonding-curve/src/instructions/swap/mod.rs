@@ -1,5 +1,19 @@
 pub mod ix_swap;
 pub use ix_swap::*;
+pub mod ix_create_wallet_buy_tracker;
+pub use ix_create_wallet_buy_tracker::*;
+pub mod ix_swap_batch;
+pub use ix_swap_batch::*;
+pub mod ix_create_referrer;
+pub use ix_create_referrer::*;
+pub mod ix_claim_referral_fee;
+pub use ix_claim_referral_fee::*;
+pub mod ix_create_wallet_volume_tracker;
+pub use ix_create_wallet_volume_tracker::*;
+pub mod ix_buyback_and_burn;
+pub use ix_buyback_and_burn::*;
+pub mod ix_create_pool_observations;
+pub use ix_create_pool_observations::*;
 mod swap_exact_in;
 mod swap_exact_out;
 mod swap_partial_fill;
@@ -23,4 +37,5 @@ struct ProcessSwapParams<'a> {
     amount_0: u64,
     amount_1: u64,
     eligible_for_first_swap_with_min_fee: bool,
+    sqrt_price_limit: Option<u128>,
 }
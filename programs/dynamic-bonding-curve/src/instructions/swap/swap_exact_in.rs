@@ -14,17 +14,30 @@ pub fn process_swap_exact_in(params: ProcessSwapParams<'_>) -> Result<ProcessSwa
         trade_direction,
         current_point,
         eligible_for_first_swap_with_min_fee,
-        ..
+        sqrt_price_limit,
     } = params;
 
-    let swap_result = pool.get_swap_result_from_exact_input(
-        config,
-        amount_in,
-        fee_mode,
-        trade_direction,
-        current_point,
-        eligible_for_first_swap_with_min_fee,
-    )?;
+    // with a price limit, the swap stops at the limit and the unused input is refunded as partial fill does
+    let swap_result = if sqrt_price_limit.is_some() {
+        pool.get_swap_result_from_partial_input(
+            config,
+            amount_in,
+            fee_mode,
+            trade_direction,
+            current_point,
+            eligible_for_first_swap_with_min_fee,
+            sqrt_price_limit,
+        )?
+    } else {
+        pool.get_swap_result_from_exact_input(
+            config,
+            amount_in,
+            fee_mode,
+            trade_direction,
+            current_point,
+            eligible_for_first_swap_with_min_fee,
+        )?
+    };
 
     require!(
         swap_result.output_amount >= minimum_amount_out,
@@ -32,10 +45,11 @@ pub fn process_swap_exact_in(params: ProcessSwapParams<'_>) -> Result<ProcessSwa
     );
 
     Ok(ProcessSwapResult {
-        swap_result,
+        // For backward compatibility because we are emitting EvtSwap and EvtSwap2
         swap_in_parameters: SwapParameters {
-            amount_in,
+            amount_in: swap_result.included_fee_input_amount,
             minimum_amount_out,
         },
+        swap_result,
     })
 }
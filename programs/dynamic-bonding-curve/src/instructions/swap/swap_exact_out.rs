@@ -1,4 +1,5 @@
 use crate::{
+    params::swap::TradeDirection,
     swap::{ProcessSwapParams, ProcessSwapResult},
     PoolError, SwapParameters,
 };
@@ -14,7 +15,7 @@ pub fn process_swap_exact_out(params: ProcessSwapParams<'_>) -> Result<ProcessSw
         amount_0: amount_out,
         amount_1: maximum_amount_in,
         eligible_for_first_swap_with_min_fee,
-        ..
+        sqrt_price_limit,
     } = params;
 
     let swap_result = pool.get_swap_result_from_exact_output(
@@ -26,6 +27,14 @@ pub fn process_swap_exact_out(params: ProcessSwapParams<'_>) -> Result<ProcessSw
         eligible_for_first_swap_with_min_fee,
     )?;
 
+    if let Some(sqrt_price_limit) = sqrt_price_limit {
+        let is_within_limit = match trade_direction {
+            TradeDirection::BaseToQuote => swap_result.next_sqrt_price >= sqrt_price_limit,
+            TradeDirection::QuoteToBase => swap_result.next_sqrt_price <= sqrt_price_limit,
+        };
+        require!(is_within_limit, PoolError::ExceededSlippage);
+    }
+
     let included_fee_input_amount = swap_result.included_fee_input_amount;
 
     require!(
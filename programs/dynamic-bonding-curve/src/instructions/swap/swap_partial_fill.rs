@@ -14,6 +14,7 @@ pub fn process_swap_partial_fill(params: ProcessSwapParams<'_>) -> Result<Proces
         trade_direction,
         current_point,
         eligible_for_first_swap_with_min_fee,
+        sqrt_price_limit,
     } = params;
 
     let swap_result = pool.get_swap_result_from_partial_input(
@@ -23,6 +24,7 @@ pub fn process_swap_partial_fill(params: ProcessSwapParams<'_>) -> Result<Proces
         trade_direction,
         current_point,
         eligible_for_first_swap_with_min_fee,
+        sqrt_price_limit,
     )?;
 
     require!(
@@ -24,6 +24,7 @@ pub use access_control::*;
 pub mod base_fee;
 pub mod curve;
 pub mod migration_handler;
+pub mod price_curve;
 pub mod tests;
 
 pub mod params;
@@ -47,6 +48,23 @@ pub mod dynamic_bonding_curve {
         Ok(())
     }
 
+    #[access_control(is_admin(ctx.accounts.signer.key))]
+    pub fn create_protocol_config(
+        ctx: Context<CreateProtocolConfigCtx>,
+        partner: Pubkey,
+        params: ProtocolConfigParameters,
+    ) -> Result<()> {
+        instructions::handle_create_protocol_config(ctx, partner, params)
+    }
+
+    #[access_control(is_admin(ctx.accounts.signer.key))]
+    pub fn update_protocol_config(
+        ctx: Context<UpdateProtocolConfigCtx>,
+        params: ProtocolConfigParameters,
+    ) -> Result<()> {
+        instructions::handle_update_protocol_config(ctx, params)
+    }
+
     #[access_control(is_admin(ctx.accounts.signer.key))]
     pub fn close_claim_protocol_fee_operator(
         ctx: Context<CloseClaimProtocolFeeOperatorCtx>,
@@ -112,6 +130,63 @@ pub mod dynamic_bonding_curve {
         instructions::handle_partner_withdraw_surplus(ctx)
     }
 
+    #[access_control(is_partner_fee_claimer(&ctx.accounts.config, ctx.accounts.fee_claimer.key))]
+    pub fn create_volume_fee_tiers(
+        ctx: Context<CreateVolumeFeeTiersCtx>,
+        tiers: Vec<VolumeFeeTierParameters>,
+    ) -> Result<()> {
+        instructions::handle_create_volume_fee_tiers(ctx, tiers)
+    }
+
+    #[access_control(is_partner_fee_claimer(&ctx.accounts.config, ctx.accounts.fee_claimer.key))]
+    pub fn update_volume_fee_tiers(
+        ctx: Context<UpdateVolumeFeeTiersCtx>,
+        tiers: Vec<VolumeFeeTierParameters>,
+    ) -> Result<()> {
+        instructions::handle_update_volume_fee_tiers(ctx, tiers)
+    }
+
+    #[access_control(is_partner_fee_claimer(&ctx.accounts.config, ctx.accounts.fee_claimer.key))]
+    pub fn close_volume_fee_tiers(ctx: Context<CloseVolumeFeeTiersCtx>) -> Result<()> {
+        instructions::handle_close_volume_fee_tiers(ctx)
+    }
+
+    #[access_control(is_partner_fee_claimer(&ctx.accounts.config, ctx.accounts.fee_claimer.key))]
+    pub fn propose_fee_claimer(ctx: Context<ProposeFeeClaimerCtx>) -> Result<()> {
+        instructions::handle_propose_fee_claimer(ctx)
+    }
+
+    #[access_control(is_partner_fee_claimer(&ctx.accounts.config, ctx.accounts.fee_claimer.key))]
+    pub fn cancel_fee_claimer_transfer(ctx: Context<CancelFeeClaimerTransferCtx>) -> Result<()> {
+        instructions::handle_cancel_fee_claimer_transfer(ctx)
+    }
+
+    pub fn accept_fee_claimer(ctx: Context<AcceptFeeClaimerCtx>) -> Result<()> {
+        instructions::handle_accept_fee_claimer(ctx)
+    }
+
+    #[access_control(is_partner_fee_claimer(&ctx.accounts.config, ctx.accounts.fee_claimer.key))]
+    pub fn retire_config(ctx: Context<RetireConfigCtx>) -> Result<()> {
+        instructions::handle_retire_config(ctx)
+    }
+
+    #[access_control(is_partner_fee_claimer(&ctx.accounts.config, ctx.accounts.fee_claimer.key))]
+    pub fn close_config<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CloseConfigCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_close_config(ctx)
+    }
+
+    #[access_control(is_partner_fee_claimer(&ctx.accounts.config, ctx.accounts.fee_claimer.key))]
+    pub fn create_allowed_creator(ctx: Context<CreateAllowedCreatorCtx>) -> Result<()> {
+        instructions::handle_create_allowed_creator(ctx)
+    }
+
+    #[access_control(is_partner_fee_claimer(&ctx.accounts.config, ctx.accounts.fee_claimer.key))]
+    pub fn close_allowed_creator(ctx: Context<CloseAllowedCreatorCtx>) -> Result<()> {
+        instructions::handle_close_allowed_creator(ctx)
+    }
+
     /// POOL CREATOR FUNCTIONS ////
     pub fn initialize_virtual_pool_with_spl_token<'c: 'info, 'info>(
         ctx: Context<'_, '_, 'c, 'info, InitializeVirtualPoolWithSplTokenCtx<'info>>,
@@ -150,6 +225,14 @@ pub mod dynamic_bonding_curve {
         instructions::handle_creator_withdraw_surplus(ctx)
     }
 
+    #[access_control(is_pool_creator(&ctx.accounts.pool, ctx.accounts.creator.key))]
+    pub fn create_creator_fee_share(
+        ctx: Context<CreateCreatorFeeShareCtx>,
+        recipients: Vec<CreatorFeeShareRecipientParameters>,
+    ) -> Result<()> {
+        instructions::handle_create_creator_fee_share(ctx, recipients)
+    }
+
     #[access_control(is_pool_creator(&ctx.accounts.virtual_pool, ctx.accounts.creator.key))]
     pub fn transfer_pool_creator<'c: 'info, 'info>(
         ctx: Context<'_, '_, 'c, 'info, TransferPoolCreatorCtx>,
@@ -169,12 +252,13 @@ pub mod dynamic_bonding_curve {
     ) -> Result<()> {
         instructions::handle_swap_wrapper(
             ctx,
-            SwapParameters2 {
+            SwapParameters3 {
                 amount_0: params.amount_in,
                 amount_1: params.minimum_amount_out,
                 swap_mode: SwapMode::ExactIn.into(),
                 ..Default::default()
             },
+            None,
         )
     }
 
@@ -182,7 +266,73 @@ pub mod dynamic_bonding_curve {
         ctx: Context<'_, '_, 'c, 'info, SwapCtx<'info>>,
         params: SwapParameters2,
     ) -> Result<()> {
-        instructions::handle_swap_wrapper(ctx, params)
+        instructions::handle_swap_wrapper(
+            ctx,
+            SwapParameters3 {
+                amount_0: params.amount_0,
+                amount_1: params.amount_1,
+                swap_mode: params.swap_mode,
+                ..Default::default()
+            },
+            None,
+        )
+    }
+
+    // same as swap2, with the price limit, the expiry and the native SOL option
+    pub fn swap3<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SwapCtx<'info>>,
+        params: SwapParameters3,
+    ) -> Result<()> {
+        instructions::handle_swap_wrapper(ctx, params, None)
+    }
+
+    // same as swap3, with a merkle proof that the payer is allowlisted with the allocation for the allowlist phase
+    pub fn swap_with_allowlist_proof<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SwapCtx<'info>>,
+        params: SwapParameters3,
+        allowlist_proof: AllowlistProof,
+    ) -> Result<()> {
+        instructions::handle_swap_wrapper(ctx, params, Some(allowlist_proof))
+    }
+
+    pub fn swap_batch<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SwapBatchCtx<'info>>,
+        legs: Vec<SwapBatchLeg>,
+    ) -> Result<()> {
+        instructions::handle_swap_batch(ctx, legs)
+    }
+
+    pub fn create_wallet_buy_tracker(ctx: Context<CreateWalletBuyTrackerCtx>) -> Result<()> {
+        instructions::handle_create_wallet_buy_tracker(ctx)
+    }
+
+    pub fn create_referrer(ctx: Context<CreateReferrerCtx>) -> Result<()> {
+        instructions::handle_create_referrer(ctx)
+    }
+
+    pub fn claim_referral_fee(ctx: Context<ClaimReferralFeeCtx>) -> Result<()> {
+        instructions::handle_claim_referral_fee(ctx)
+    }
+
+    pub fn create_wallet_volume_tracker(ctx: Context<CreateWalletVolumeTrackerCtx>) -> Result<()> {
+        instructions::handle_create_wallet_volume_tracker(ctx)
+    }
+
+    pub fn buyback_and_burn(
+        ctx: Context<BuybackAndBurnCtx>,
+        params: BuybackParameters,
+    ) -> Result<()> {
+        instructions::handle_buyback_and_burn(ctx, params)
+    }
+
+    pub fn create_pool_observations(ctx: Context<CreatePoolObservationsCtx>) -> Result<()> {
+        instructions::handle_create_pool_observations(ctx)
+    }
+
+    pub fn distribute_creator_fee<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, DistributeCreatorFeeCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_distribute_creator_fee(ctx)
     }
 
     /// PERMISSIONLESS FUNCTIONS ///
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::Result;
 use num_traits::cast::FromPrimitive;
-use ruint::aliases::U256;
+use ruint::aliases::{U256, U512};
 
 use crate::{
     safe_math::SafeMath,
@@ -94,3 +94,27 @@ pub fn sqrt_u256(radicand: U256) -> Option<U256> {
     }
     Some(result)
 }
+
+pub fn sqrt_u512(radicand: U512) -> Option<U512> {
+    if radicand == U512::ZERO {
+        return Some(U512::ZERO);
+    }
+    // Compute bit, the largest power of 4 <= n
+    let max_shift = U512::ZERO.leading_zeros() - 1;
+    let shift = (max_shift - radicand.leading_zeros()) & !1;
+    let mut bit = U512::ONE.checked_shl(shift)?;
+
+    let mut n = radicand;
+    let mut result = U512::ZERO;
+    while bit != U512::ZERO {
+        let result_with_bit = result.checked_add(bit)?;
+        if n >= result_with_bit {
+            n = n.checked_sub(result_with_bit)?;
+            result = result.checked_shr(1)?.checked_add(bit)?;
+        } else {
+            result = result.checked_shr(1)?;
+        }
+        (bit, _) = bit.overflowing_shr(2);
+    }
+    Some(result)
+}
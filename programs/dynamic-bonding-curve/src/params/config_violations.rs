@@ -0,0 +1,77 @@
+use std::fmt::Display;
+
+use anchor_lang::{error::Error, prelude::*};
+
+use crate::PoolError;
+
+/// A field of the config parameters that create_config rejects
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigViolation {
+    /// path of the field in ConfigParameters, e.g. `migrated_pool_fee.pool_fee_bps` or `curve[2].sqrt_price`
+    pub field: String,
+    /// values accepted by create_config for the field
+    pub allowed_range: String,
+}
+
+/// Violations of the config parameters in the order of the checks, create_config fails with the error of the first one while the sdk reports all of them.
+/// The field and the allowed range are only formatted on a violation, so a valid config doesn't pay for the formatting on-chain
+#[derive(Default)]
+pub struct ConfigViolations {
+    violations: Vec<ConfigViolation>,
+    first_error: Option<Error>,
+}
+
+impl ConfigViolations {
+    pub fn check(
+        &mut self,
+        is_valid: bool,
+        error: PoolError,
+        field: impl Display,
+        allowed_range: impl Display,
+    ) {
+        if !is_valid {
+            self.push(error.into(), field, allowed_range);
+        }
+    }
+
+    /// Check the result of a nested validator, the violation keeps the error of the validator
+    pub fn check_result(
+        &mut self,
+        result: Result<()>,
+        field: impl Display,
+        allowed_range: impl Display,
+    ) {
+        if let Err(error) = result {
+            self.push(error, field, allowed_range);
+        }
+    }
+
+    fn push(&mut self, error: Error, field: impl Display, allowed_range: impl Display) {
+        if self.first_error.is_none() {
+            self.first_error = Some(error);
+        }
+        self.violations.push(ConfigViolation {
+            field: field.to_string(),
+            allowed_range: allowed_range.to_string(),
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.violations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    pub fn into_result(self) -> Result<()> {
+        match self.first_error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    pub fn into_violations(self) -> Vec<ConfigViolation> {
+        self.violations
+    }
+}
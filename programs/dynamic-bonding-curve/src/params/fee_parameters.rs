@@ -1,11 +1,14 @@
 //! Fees module includes information about fee charges
+use std::fmt;
+
 use crate::activation_handler::ActivationType;
 use crate::base_fee::get_base_fee_handler;
-use crate::constants::fee::MAX_BASIS_POINT;
+use crate::constants::fee::{MAX_BASIS_POINT, MAX_FEE_NUMERATOR, MIN_FEE_NUMERATOR};
 use crate::constants::{dynamic_fee::*, BASIS_POINT_MAX, U24_MAX};
 use crate::error::PoolError;
+use crate::params::config_violations::ConfigViolations;
 use crate::safe_math::SafeMath;
-use crate::state::{BaseFeeConfig, DynamicFeeConfig, PoolFeesConfig};
+use crate::state::{BaseFeeConfig, BaseFeeMode, DynamicFeeConfig, PoolFeesConfig, RateLimiterMode};
 use anchor_lang::prelude::*;
 
 /// Information regarding fee charges
@@ -15,6 +18,10 @@ pub struct PoolFeeParameters {
     pub base_fee: BaseFeeParameters,
     /// dynamic fee
     pub dynamic_fee: Option<DynamicFeeParameters>,
+    /// Base fee of sells (BaseToQuote), base_fee is then only applied to buys. Stored in the config extension
+    pub sell_base_fee: Option<BaseFeeParameters>,
+    /// Trade direction that the rate limiter applies to, only used in rate limiter base fee mode. 0: QuoteToBase, 1: BaseToQuote, 2: Both
+    pub rate_limiter_mode: u8,
 }
 
 #[derive(Copy, Clone, Debug, AnchorSerialize, AnchorDeserialize, InitSpace, Default)]
@@ -27,13 +34,19 @@ pub struct BaseFeeParameters {
 }
 
 impl BaseFeeParameters {
-    fn validate(&self, collect_fee_mode: u8, activation_type: ActivationType) -> Result<()> {
+    fn validate(
+        &self,
+        collect_fee_mode: u8,
+        activation_type: ActivationType,
+        rate_limiter_mode: u8,
+    ) -> Result<()> {
         let base_fee_handler = get_base_fee_handler(
             self.cliff_fee_numerator,
             self.first_factor,
             self.second_factor,
             self.third_factor,
             self.base_fee_mode,
+            rate_limiter_mode,
         )?;
         base_fee_handler.validate(collect_fee_mode, activation_type)?;
         Ok(())
@@ -51,31 +64,103 @@ impl BaseFeeParameters {
     }
 }
 
+/// Allowed range of a base fee, only formatted on a violation
+struct BaseFeeRange(u8);
+
+impl fmt::Display for BaseFeeRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match BaseFeeMode::try_from(self.0) {
+            Ok(base_fee_mode) => write!(
+                f,
+                "{base_fee_mode:?} parameters with fee numerators from {MIN_FEE_NUMERATOR} to {MAX_FEE_NUMERATOR}"
+            ),
+            Err(_) => write!(f, "base_fee_mode 0 to 5"),
+        }
+    }
+}
+
 impl PoolFeeParameters {
     /// Validate that the fees are reasonable
     pub fn validate(&self, collect_fee_mode: u8, activation_type: ActivationType) -> Result<()> {
-        self.base_fee.validate(collect_fee_mode, activation_type)?;
+        let mut violations = ConfigViolations::default();
+        self.check(
+            &mut violations,
+            collect_fee_mode,
+            activation_type,
+            "pool_fees.base_fee",
+        );
+        violations.into_result()
+    }
 
-        if let Some(dynamic_fee) = self.dynamic_fee {
-            dynamic_fee.validate()?;
+    /// Push the violations of the fees, base_fee_field is the field of the config parameters the base fee is built from
+    pub fn check(
+        &self,
+        violations: &mut ConfigViolations,
+        collect_fee_mode: u8,
+        activation_type: ActivationType,
+        base_fee_field: &str,
+    ) {
+        violations.check(
+            self.base_fee.base_fee_mode == BaseFeeMode::RateLimiter.into()
+                || self.rate_limiter_mode == RateLimiterMode::default().into(),
+            PoolError::InvalidFeeRateLimiter,
+            "pool_fees.rate_limiter_mode",
+            "0 unless the base fee mode is rate limiter (2)",
+        );
+        violations.check_result(
+            self.base_fee
+                .validate(collect_fee_mode, activation_type, self.rate_limiter_mode),
+            base_fee_field,
+            BaseFeeRange(self.base_fee.base_fee_mode),
+        );
+
+        if let Some(sell_base_fee) = self.sell_base_fee {
+            // sells are rate limited through rate_limiter_mode of base_fee instead, and the config extension only holds the fee steps of base_fee
+            let is_supported_mode = sell_base_fee.base_fee_mode != BaseFeeMode::RateLimiter.into()
+                && sell_base_fee.base_fee_mode != BaseFeeMode::FeeStepTable.into();
+            violations.check(
+                is_supported_mode,
+                PoolError::InvalidSellBaseFee,
+                "pool_fees.sell_base_fee.base_fee_mode",
+                "0, 1, 3 or 4, rate limiter and fee step table aren't supported for sells",
+            );
+            if is_supported_mode {
+                violations.check_result(
+                    sell_base_fee.validate(
+                        collect_fee_mode,
+                        activation_type,
+                        RateLimiterMode::default().into(),
+                    ),
+                    "pool_fees.sell_base_fee",
+                    BaseFeeRange(sell_base_fee.base_fee_mode),
+                );
+            }
         }
 
-        Ok(())
+        if let Some(dynamic_fee) = self.dynamic_fee {
+            dynamic_fee.check(violations);
+        }
     }
 
     pub fn to_pool_fees_config(&self) -> PoolFeesConfig {
         let &PoolFeeParameters {
             base_fee,
             dynamic_fee,
+            rate_limiter_mode,
+            ..
         } = self;
+        let base_fee = BaseFeeConfig {
+            rate_limiter_mode,
+            ..base_fee.to_base_fee_config()
+        };
         if let Some(dynamic_fee) = dynamic_fee {
             PoolFeesConfig {
-                base_fee: base_fee.to_base_fee_config(),
+                base_fee,
                 dynamic_fee: dynamic_fee.to_dynamic_fee_config(),
             }
         } else {
             PoolFeesConfig {
-                base_fee: base_fee.to_base_fee_config(),
+                base_fee,
                 ..Default::default()
             }
         }
@@ -91,6 +176,8 @@ pub struct DynamicFeeParameters {
     pub reduction_factor: u16,
     pub max_volatility_accumulator: u32,
     pub variable_fee_control: u32,
+    /// swaps are halted while the volatility accumulator is over this threshold, 0 to disable
+    pub circuit_breaker_volatility_accumulator: u32,
 }
 
 impl DynamicFeeParameters {
@@ -104,44 +191,70 @@ impl DynamicFeeParameters {
             bin_step_u128: self.bin_step_u128,
             max_volatility_accumulator: self.max_volatility_accumulator,
             variable_fee_control: self.variable_fee_control,
+            circuit_breaker_volatility_accumulator: self.circuit_breaker_volatility_accumulator,
             ..Default::default()
         }
     }
 
     pub fn validate(&self) -> Result<()> {
+        let mut violations = ConfigViolations::default();
+        self.check(&mut violations);
+        violations.into_result()
+    }
+
+    pub fn check(&self, violations: &mut ConfigViolations) {
         // force all bin_step as 1 bps for first version
-        require!(
+        violations.check(
             self.bin_step == BIN_STEP_BPS_DEFAULT,
-            PoolError::InvalidInput
+            PoolError::InvalidInput,
+            "pool_fees.dynamic_fee.bin_step",
+            BIN_STEP_BPS_DEFAULT,
         );
-        require!(
+        violations.check(
             self.bin_step_u128 == BIN_STEP_BPS_U128_DEFAULT,
-            PoolError::InvalidInput
+            PoolError::InvalidInput,
+            "pool_fees.dynamic_fee.bin_step_u128",
+            BIN_STEP_BPS_U128_DEFAULT,
         );
 
         // filter period < t < decay period
-        require!(
+        violations.check(
             self.filter_period < self.decay_period,
-            PoolError::InvalidInput
+            PoolError::InvalidInput,
+            "pool_fees.dynamic_fee.filter_period",
+            "lower than decay_period",
         );
 
         // reduction factor decide the decay rate of variable fee, max reduction_factor is BASIS_POINT_MAX = 100% reduction
-        require!(
+        violations.check(
             self.reduction_factor <= BASIS_POINT_MAX as u16,
-            PoolError::InvalidInput
+            PoolError::InvalidInput,
+            "pool_fees.dynamic_fee.reduction_factor",
+            format_args!("0 to {BASIS_POINT_MAX}"),
         );
 
         // prevent program overflow
-        require!(
+        violations.check(
             self.variable_fee_control <= U24_MAX,
-            PoolError::InvalidInput
+            PoolError::InvalidInput,
+            "pool_fees.dynamic_fee.variable_fee_control",
+            format_args!("0 to {U24_MAX}"),
         );
-        require!(
+        violations.check(
             self.max_volatility_accumulator <= U24_MAX,
-            PoolError::InvalidInput
+            PoolError::InvalidInput,
+            "pool_fees.dynamic_fee.max_volatility_accumulator",
+            format_args!("0 to {U24_MAX}"),
         );
 
-        Ok(())
+        // the volatility accumulator is capped at max_volatility_accumulator, so a higher threshold is never reached
+        violations.check(
+            self.circuit_breaker_volatility_accumulator == 0
+                || self.circuit_breaker_volatility_accumulator < self.max_volatility_accumulator,
+            PoolError::InvalidInput,
+            "pool_fees.dynamic_fee.circuit_breaker_volatility_accumulator",
+            "0 or lower than max_volatility_accumulator",
+        );
     }
 }
 
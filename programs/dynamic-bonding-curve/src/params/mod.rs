@@ -1,3 +1,4 @@
+pub mod config_violations;
 pub mod fee_parameters;
 pub mod liquidity_distribution;
 pub mod swap;
@@ -0,0 +1,500 @@
+use anchor_lang::prelude::*;
+use ruint::aliases::{U256, U512};
+
+use crate::{
+    constants::MAX_SQRT_PRICE,
+    safe_math::SafeMath,
+    state::CurveType,
+    u128x128_math::{mul_div_u256, Rounding},
+    utils_math::{safe_mul_div_cast_u128, sqrt_u512},
+    PoolError,
+};
+
+/// ln(2) in Q64
+const LN2_Q64: u128 = 12786308645202655659;
+
+/// Upper bound of the error of the rounded down log2 in Q64, the computed error is always lower than 7
+const LOG2_MAX_ERROR: u128 = 8;
+
+/// 2^(2^-i) in Q63 rounded down, for i from 1 to 64
+const POW2_FRACTIONS_Q63: [u64; 64] = [
+    13043817825332782212,
+    10968499650544839023,
+    10058158527438640870,
+    9631725603661576980,
+    9425337585438007767,
+    9323807973325342579,
+    9273454034941487980,
+    9248379135337035440,
+    9235867122425417552,
+    9229617465154500136,
+    9226494222562136016,
+    9224932997620185949,
+    9224152484218207414,
+    9223762252282022472,
+    9223567142504825507,
+    9223469589163912706,
+    9223420812880372951,
+    9223396424835331638,
+    9223384230836993048,
+    9223378133843869260,
+    9223375085348818742,
+    9223373561101671327,
+    9223372798978192080,
+    9223372417916476072,
+    9223372227385623972,
+    9223372132120199398,
+    9223372084487487480,
+    9223372060671131613,
+    9223372048762953702,
+    9223372042808864753,
+    9223372039831820280,
+    9223372038343298044,
+    9223372037599036925,
+    9223372037226906366,
+    9223372037040841087,
+    9223372036947808447,
+    9223372036901292127,
+    9223372036878033967,
+    9223372036866404887,
+    9223372036860590347,
+    9223372036857683077,
+    9223372036856229442,
+    9223372036855502625,
+    9223372036855139216,
+    9223372036854957512,
+    9223372036854866660,
+    9223372036854821234,
+    9223372036854798521,
+    9223372036854787164,
+    9223372036854781486,
+    9223372036854778647,
+    9223372036854777227,
+    9223372036854776517,
+    9223372036854776162,
+    9223372036854775985,
+    9223372036854775896,
+    9223372036854775852,
+    9223372036854775830,
+    9223372036854775819,
+    9223372036854775813,
+    9223372036854775810,
+    9223372036854775809,
+    9223372036854775808,
+    9223372036854775808,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PriceCurveKind {
+    Linear {
+        /// P_end - P_start in Q128
+        delta_price: U256,
+    },
+    Exponential {
+        /// log2(√P_end / √P_start) in Q64, rounded down
+        log2_sqrt_price_ratio: u128,
+        /// ln(√P_end / √P_start) in Q64, rounded down
+        ln_sqrt_price_ratio: u128,
+    },
+}
+
+/// Curve of a config which curve type is not constant product. The price moves from sqrt_start_price
+/// to end_sqrt_price while base_amount of base token is sold, x being the base amount sold on the curve:
+///
+/// * Linear price: `P(x) = P_start + x * (P_end - P_start) / base_amount`
+/// * Exponential price: `P(x) = P_start * (P_end / P_start) ^ (x / base_amount)`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceCurve {
+    pub curve_type: CurveType,
+    pub sqrt_start_price: u128,
+    pub end_sqrt_price: u128,
+    pub base_amount: u64,
+    kind: PriceCurveKind,
+}
+
+impl PriceCurve {
+    pub fn new(
+        curve_type: CurveType,
+        sqrt_start_price: u128,
+        end_sqrt_price: u128,
+        base_amount: u128,
+    ) -> Result<Self> {
+        require!(
+            sqrt_start_price > 0
+                && end_sqrt_price > sqrt_start_price
+                && end_sqrt_price <= MAX_SQRT_PRICE
+                && base_amount > 0,
+            PoolError::InvalidCurve
+        );
+        let base_amount = u64::try_from(base_amount).map_err(|_| PoolError::InvalidCurve)?;
+
+        let kind = match curve_type {
+            CurveType::ConstantProduct => return Err(PoolError::InvalidCurveType.into()),
+            CurveType::LinearPrice => PriceCurveKind::Linear {
+                delta_price: get_price(end_sqrt_price)?.safe_sub(get_price(sqrt_start_price)?)?,
+            },
+            CurveType::ExponentialPrice => {
+                let log2_sqrt_price_ratio = log2_q64(end_sqrt_price, Rounding::Down)?
+                    .saturating_sub(log2_q64(sqrt_start_price, Rounding::Up)?);
+                let ln_sqrt_price_ratio = safe_mul_div_cast_u128(
+                    log2_sqrt_price_ratio,
+                    LN2_Q64,
+                    1 << 64,
+                    Rounding::Down,
+                )?;
+                // the price range is too narrow for the precision of the logarithm
+                require!(ln_sqrt_price_ratio > 0, PoolError::InvalidCurve);
+                PriceCurveKind::Exponential {
+                    log2_sqrt_price_ratio,
+                    ln_sqrt_price_ratio,
+                }
+            }
+        };
+
+        Ok(PriceCurve {
+            curve_type,
+            sqrt_start_price,
+            end_sqrt_price,
+            base_amount,
+            kind,
+        })
+    }
+
+    /// Gets the base amount sold on the curve between two prices
+    ///
+    /// * Linear price: `Δx = base_amount * (P_upper - P_lower) / (P_end - P_start)`
+    /// * Exponential price: `Δx = base_amount * log2(√P_upper / √P_lower) / log2(√P_end / √P_start)`
+    pub fn get_delta_amount_base(
+        &self,
+        lower_sqrt_price: u128,
+        upper_sqrt_price: u128,
+        round: Rounding,
+    ) -> Result<U256> {
+        let base_amount = U256::from(self.base_amount);
+        let result = match self.kind {
+            PriceCurveKind::Linear { delta_price } => {
+                let delta_price_in_range =
+                    get_price(upper_sqrt_price)?.safe_sub(get_price(lower_sqrt_price)?)?;
+                mul_div_u256(base_amount, delta_price_in_range, delta_price, round)
+            }
+            PriceCurveKind::Exponential {
+                log2_sqrt_price_ratio,
+                ..
+            } => {
+                let log2_sqrt_price_ratio_in_range = match round {
+                    Rounding::Up => log2_q64(upper_sqrt_price, Rounding::Up)?
+                        .safe_sub(log2_q64(lower_sqrt_price, Rounding::Down)?)?,
+                    Rounding::Down => log2_q64(upper_sqrt_price, Rounding::Down)?
+                        .saturating_sub(log2_q64(lower_sqrt_price, Rounding::Up)?),
+                };
+                mul_div_u256(
+                    base_amount,
+                    U256::from(log2_sqrt_price_ratio_in_range),
+                    U256::from(log2_sqrt_price_ratio),
+                    round,
+                )
+            }
+        };
+        Ok(result.ok_or_else(|| PoolError::MathOverflow)?)
+    }
+
+    /// Gets the quote amount paid on the curve between two prices, the integral of the price over the base amount
+    ///
+    /// * Linear price: `Δy = base_amount * (P_upper² - P_lower²) / (2 * (P_end - P_start))`
+    /// * Exponential price: `Δy = base_amount * (P_upper - P_lower) / (2 * ln(√P_end / √P_start))`
+    pub fn get_delta_amount_quote(
+        &self,
+        lower_sqrt_price: u128,
+        upper_sqrt_price: u128,
+        round: Rounding,
+    ) -> Result<U256> {
+        let lower_price = get_price(lower_sqrt_price)?;
+        let upper_price = get_price(upper_sqrt_price)?;
+        match self.kind {
+            PriceCurveKind::Linear { delta_price } => {
+                // (P_upper + P_lower) * base_amount * (P_upper - P_lower) / (2 * (P_end - P_start) * 2^128)
+                let numerator_1 = U512::from(upper_price).safe_add(U512::from(lower_price))?;
+                let numerator_2 = U512::from(self.base_amount)
+                    .safe_mul(U512::from(upper_price.safe_sub(lower_price)?))?;
+                let denominator = U512::from(delta_price).safe_shl(129)?;
+                mul_div_u512(numerator_1, numerator_2, denominator, round)
+            }
+            PriceCurveKind::Exponential {
+                ln_sqrt_price_ratio,
+                ..
+            } => {
+                // base_amount * (P_upper - P_lower) / (2 * ln(√P_end / √P_start) * 2^64)
+                let denominator = U256::from(ln_sqrt_price_ratio).safe_shl(65)?;
+                let result = mul_div_u256(
+                    U256::from(self.base_amount),
+                    upper_price.safe_sub(lower_price)?,
+                    denominator,
+                    round,
+                )
+                .ok_or_else(|| PoolError::MathOverflow)?;
+                Ok(result)
+            }
+        }
+    }
+
+    /// Gets the next sqrt price given an input amount of base or quote token
+    pub fn get_next_sqrt_price_from_input(
+        &self,
+        sqrt_price: u128,
+        amount_in: u64,
+        base_for_quote: bool,
+    ) -> Result<u128> {
+        // round to make sure that we don't pass the target price
+        if base_for_quote {
+            self.get_next_sqrt_price_from_base_amount_rounding_up(sqrt_price, amount_in, false)
+        } else {
+            self.get_next_sqrt_price_from_quote_amount(sqrt_price, amount_in, true, Rounding::Down)
+        }
+    }
+
+    /// Gets the next sqrt price given an output amount of base or quote token
+    pub fn get_next_sqrt_price_from_output(
+        &self,
+        sqrt_price: u128,
+        amount_out: u64,
+        base_for_quote: bool,
+    ) -> Result<u128> {
+        if base_for_quote {
+            self.get_next_sqrt_price_from_quote_amount(
+                sqrt_price,
+                amount_out,
+                false,
+                Rounding::Down,
+            )
+        } else {
+            self.get_next_sqrt_price_from_base_amount_rounding_up(sqrt_price, amount_out, true)
+        }
+    }
+
+    /// Gets the next sqrt price after the base amount is sold (is_increase) or bought back by the curve
+    ///
+    /// * Linear price: `P' = P ± Δx * (P_end - P_start) / base_amount`
+    /// * Exponential price: `√P' = √P * 2 ^ (± Δx * log2(√P_end / √P_start) / base_amount)`
+    fn get_next_sqrt_price_from_base_amount_rounding_up(
+        &self,
+        sqrt_price: u128,
+        amount: u64,
+        is_increase: bool,
+    ) -> Result<u128> {
+        if amount == 0 {
+            return Ok(sqrt_price);
+        }
+        match self.kind {
+            PriceCurveKind::Linear { delta_price } => {
+                let price = get_price(sqrt_price)?;
+                let amount = U256::from(amount);
+                let base_amount = U256::from(self.base_amount);
+                let next_price = if is_increase {
+                    let delta = mul_div_u256(amount, delta_price, base_amount, Rounding::Up)
+                        .ok_or_else(|| PoolError::MathOverflow)?;
+                    price.safe_add(delta)?
+                } else {
+                    let delta = mul_div_u256(amount, delta_price, base_amount, Rounding::Down)
+                        .ok_or_else(|| PoolError::MathOverflow)?;
+                    price.safe_sub(delta)?
+                };
+                let next_sqrt_price =
+                    sqrt_u512_with_rounding(U512::from(next_price), Rounding::Up)?;
+                Ok(next_sqrt_price
+                    .try_into()
+                    .map_err(|_| PoolError::TypeCastFailed)?)
+            }
+            PriceCurveKind::Exponential {
+                log2_sqrt_price_ratio,
+                ..
+            } => {
+                let rounding = if is_increase {
+                    Rounding::Up
+                } else {
+                    Rounding::Down
+                };
+                let exponent = safe_mul_div_cast_u128(
+                    amount.into(),
+                    log2_sqrt_price_ratio,
+                    self.base_amount.into(),
+                    rounding,
+                )?;
+                mul_pow2_q64(sqrt_price, exponent, is_increase, Rounding::Up)
+            }
+        }
+    }
+
+    /// Gets the next sqrt price after the quote amount is paid to (is_increase) or taken from the curve
+    ///
+    /// * Linear price: `P'² = P² ± Δy * 2 * (P_end - P_start) / base_amount`
+    /// * Exponential price: `P' = P ± Δy * 2 * ln(√P_end / √P_start) / base_amount`
+    fn get_next_sqrt_price_from_quote_amount(
+        &self,
+        sqrt_price: u128,
+        amount: u64,
+        is_increase: bool,
+        round: Rounding,
+    ) -> Result<u128> {
+        if amount == 0 {
+            return Ok(sqrt_price);
+        }
+        // the price delta is rounded in the same direction as the next price
+        let delta_round = if is_increase == (round == Rounding::Up) {
+            Rounding::Up
+        } else {
+            Rounding::Down
+        };
+        let price = get_price(sqrt_price)?;
+        let next_sqrt_price = match self.kind {
+            PriceCurveKind::Linear { delta_price } => {
+                let price = U512::from(price);
+                let numerator = U512::from(amount)
+                    .safe_mul(U512::from(delta_price))?
+                    .safe_shl(129)?;
+                let delta = div_u512(numerator, U512::from(self.base_amount), delta_round)?;
+                let price_square = price.safe_mul(price)?;
+                let next_price_square = if is_increase {
+                    price_square.safe_add(delta)?
+                } else {
+                    price_square.safe_sub(delta)?
+                };
+                let next_price = sqrt_u512_with_rounding(next_price_square, round)?;
+                sqrt_u512_with_rounding(next_price, round)?
+                    .try_into()
+                    .map_err(|_| PoolError::TypeCastFailed)?
+            }
+            PriceCurveKind::Exponential {
+                ln_sqrt_price_ratio,
+                ..
+            } => {
+                let numerator = U512::from(amount)
+                    .safe_mul(U512::from(ln_sqrt_price_ratio))?
+                    .safe_shl(65)?;
+                let delta = div_u512(numerator, U512::from(self.base_amount), delta_round)?;
+                let price = U512::from(price);
+                let next_price = if is_increase {
+                    price.safe_add(delta)?
+                } else {
+                    price.safe_sub(delta)?
+                };
+                sqrt_u512_with_rounding(next_price, round)?
+                    .try_into()
+                    .map_err(|_| PoolError::TypeCastFailed)?
+            }
+        };
+        Ok(next_sqrt_price)
+    }
+
+    /// Sqrt price at which migration_quote_threshold quote token is paid to the curve.
+    /// Rounded up so that the quote amount paid to reach it is never lower than the threshold
+    pub fn get_migration_sqrt_price(&self, migration_quote_threshold: u64) -> Result<u128> {
+        let migration_sqrt_price = self.get_next_sqrt_price_from_quote_amount(
+            self.sqrt_start_price,
+            migration_quote_threshold,
+            true,
+            Rounding::Up,
+        )?;
+        require!(
+            migration_sqrt_price <= self.end_sqrt_price,
+            PoolError::InvalidCurve
+        );
+        Ok(migration_sqrt_price)
+    }
+}
+
+/// P = √P², in Q128
+fn get_price(sqrt_price: u128) -> Result<U256> {
+    Ok(U256::from(sqrt_price).safe_mul(U256::from(sqrt_price))?)
+}
+
+fn sqrt_u512_with_rounding(radicand: U512, round: Rounding) -> Result<U512> {
+    let root = sqrt_u512(radicand).ok_or_else(|| PoolError::MathOverflow)?;
+    if round == Rounding::Up && root.safe_mul(root)? < radicand {
+        Ok(root.safe_add(U512::from(1))?)
+    } else {
+        Ok(root)
+    }
+}
+
+fn div_u512(numerator: U512, denominator: U512, round: Rounding) -> Result<U512> {
+    match round {
+        Rounding::Up => Ok(numerator.div_ceil(denominator)),
+        Rounding::Down => Ok(numerator.safe_div(denominator)?),
+    }
+}
+
+/// (x * y) / denominator
+fn mul_div_u512(x: U512, y: U512, denominator: U512, rounding: Rounding) -> Result<U256> {
+    let result = div_u512(x.safe_mul(y)?, denominator, rounding)?;
+    require!(result <= U512::from(U256::MAX), PoolError::MathOverflow);
+    Ok(U256::from(result))
+}
+
+/// log2 of a positive integer, in Q64
+///
+/// The mantissa is squared once per fractional bit, the rounded down result is lower than the exact value by less than LOG2_MAX_ERROR
+pub fn log2_q64(value: u128, rounding: Rounding) -> Result<u128> {
+    require!(value > 0, PoolError::MathOverflow);
+    let msb = 127 - value.leading_zeros();
+    // normalize the mantissa to [1, 2) in Q63
+    let mut mantissa = if msb >= 63 {
+        value >> (msb - 63)
+    } else {
+        value << (63 - msb)
+    };
+    let mut result = u128::from(msb) << 64;
+    for i in (0..64).rev() {
+        // mantissa < 2^64 so the square never overflows
+        mantissa = (mantissa * mantissa) >> 63;
+        if mantissa >= 1 << 64 {
+            mantissa >>= 1;
+            result |= 1 << i;
+        }
+    }
+    match rounding {
+        Rounding::Up => Ok(result.safe_add(LOG2_MAX_ERROR)?),
+        Rounding::Down => Ok(result),
+    }
+}
+
+/// value * 2^exponent if is_increase, value / 2^exponent otherwise, exponent in Q64
+pub fn mul_pow2_q64(
+    value: u128,
+    exponent: u128,
+    is_increase: bool,
+    rounding: Rounding,
+) -> Result<u128> {
+    // split the exponent into an integer and a fraction in [0, 1), using 2^-e = 2^(n - e) / 2^n when decreasing
+    let (integer, fraction) = if is_increase {
+        (exponent >> 64, exponent & u128::from(u64::MAX))
+    } else {
+        let integer = exponent.safe_add(u64::MAX.into())? >> 64;
+        (integer, integer.safe_shl(64)?.safe_sub(exponent)?)
+    };
+
+    // 2^fraction in Q63, the factors are bounded by the rounded down constants plus one when rounding up
+    let mut factor: u128 = 1 << 63;
+    for (i, pow2_fraction) in POW2_FRACTIONS_Q63.iter().enumerate() {
+        if fraction & (1 << (63 - i)) == 0 {
+            continue;
+        }
+        factor = match rounding {
+            Rounding::Up => (factor * (u128::from(*pow2_fraction) + 1)).div_ceil(1 << 63),
+            Rounding::Down => (factor * u128::from(*pow2_fraction)) >> 63,
+        };
+    }
+
+    let integer: usize = integer.try_into().map_err(|_| PoolError::MathOverflow)?;
+    let product = U256::from(value).safe_mul(U256::from(factor))?;
+    let (product, shift) = if is_increase {
+        (product.safe_shl(integer)?, 63)
+    } else {
+        (product, integer.safe_add(63)?)
+    };
+    let denominator = U256::from(1).safe_shl(shift)?;
+    let result = match rounding {
+        Rounding::Up => product.div_ceil(denominator),
+        Rounding::Down => product.safe_div(denominator)?,
+    };
+    Ok(result.try_into().map_err(|_| PoolError::TypeCastFailed)?)
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+/// Creator allowed by the partner to create pools from a config with creator allowlist
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct AllowedCreator {
+    /// config
+    pub config: Pubkey,
+    /// pool creator
+    pub creator: Pubkey,
+    /// padding for future use
+    pub padding: [u64; 4],
+}
+
+const_assert_eq!(AllowedCreator::INIT_SPACE, 96);
+
+impl AllowedCreator {
+    pub fn initialize(&mut self, config: Pubkey, creator: Pubkey) {
+        self.config = config;
+        self.creator = creator;
+    }
+}
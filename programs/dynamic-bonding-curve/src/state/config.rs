@@ -22,6 +22,7 @@ use crate::{
         liquidity_distribution::{get_base_token_for_swap, LiquidityDistributionParameters},
         swap::TradeDirection,
     },
+    price_curve::PriceCurve,
     safe_math::{SafeCast, SafeMath},
     u128x128_math::Rounding,
     utils_math::{safe_mul_div_cast_u128, safe_mul_div_cast_u64},
@@ -34,7 +35,8 @@ use damm_v2::types::DynamicFeeParameters as DammV2DynamicFeeParameters;
 use damm_v2::types::PoolFeeParameters as DammV2PoolFeeParameters;
 use damm_v2::types::VestingParameters as DammV2VestingParameters;
 
-use super::fee::{FeeOnAmountResult, VolatilityTracker};
+use super::fee::{FeeOnAmountResult, TradeFeeNumerator, VolatilityTracker};
+use super::{CollectFeeMode, PoolConfigExtension, ProtocolConfig};
 
 /// base fee mode
 #[repr(u8)]
@@ -56,6 +58,35 @@ pub enum BaseFeeMode {
     FeeSchedulerExponential,
     // TODO
     RateLimiter,
+    // fee = cliff_fee_numerator - passed_period * reduction_factor
+    // passed_period = (sqrt_price - sqrt_start_price) * 10_000 / sqrt_start_price / sqrt_price_step_bps
+    FeeMarketCapSchedulerLinear,
+    // fee = cliff_fee_numerator * (1-reduction_factor/10_000)^passed_period
+    FeeMarketCapSchedulerExponential,
+    // fee = fee_numerator of the last step reached by the elapsed points since activation, steps are stored in the config extension
+    FeeStepTable,
+}
+
+/// trade direction that the rate limiter escalates fee on, the reference amount is always in quote token
+#[repr(u8)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    IntoPrimitive,
+    TryFromPrimitive,
+    AnchorDeserialize,
+    AnchorSerialize,
+)]
+pub enum RateLimiterMode {
+    // buys, reference amount in quote input
+    #[default]
+    QuoteToBase,
+    // sells, reference amount in quote output
+    BaseToQuote,
+    Both,
 }
 
 #[zero_copy]
@@ -81,7 +112,10 @@ impl PoolFeesConfig {
         activation_point: u64,
         included_fee_amount: u64,
         trade_direction: TradeDirection,
-    ) -> Result<u64> {
+        init_sqrt_price: u128,
+        current_sqrt_price: u128,
+        fee_discount_bps: u16,
+    ) -> Result<TradeFeeNumerator> {
         let base_fee_handler = self.base_fee.get_base_fee_handler()?;
 
         let base_fee_numerator = base_fee_handler.get_base_fee_numerator_from_included_fee_amount(
@@ -89,9 +123,11 @@ impl PoolFeesConfig {
             activation_point,
             trade_direction,
             included_fee_amount,
+            init_sqrt_price,
+            current_sqrt_price,
         )?;
 
-        self.get_total_fee_numerator(base_fee_numerator, volatility_tracker)
+        self.get_total_fee_numerator(base_fee_numerator, volatility_tracker, fee_discount_bps)
     }
 
     pub fn get_total_fee_numerator_from_excluded_fee_amount(
@@ -101,7 +137,10 @@ impl PoolFeesConfig {
         activation_point: u64,
         excluded_fee_amount: u64,
         trade_direction: TradeDirection,
-    ) -> Result<u64> {
+        init_sqrt_price: u128,
+        current_sqrt_price: u128,
+        fee_discount_bps: u16,
+    ) -> Result<TradeFeeNumerator> {
         let base_fee_handler = self.base_fee.get_base_fee_handler()?;
 
         let base_fee_numerator = base_fee_handler.get_base_fee_numerator_from_excluded_fee_amount(
@@ -109,16 +148,19 @@ impl PoolFeesConfig {
             activation_point,
             trade_direction,
             excluded_fee_amount,
+            init_sqrt_price,
+            current_sqrt_price,
         )?;
 
-        self.get_total_fee_numerator(base_fee_numerator, volatility_tracker)
+        self.get_total_fee_numerator(base_fee_numerator, volatility_tracker, fee_discount_bps)
     }
 
     fn get_total_fee_numerator(
         &self,
         base_fee_numerator: u64,
         volatility_tracker: &VolatilityTracker,
-    ) -> Result<u64> {
+        fee_discount_bps: u16,
+    ) -> Result<TradeFeeNumerator> {
         let total_fee_numerator = self
             .dynamic_fee
             .get_variable_fee_numerator(volatility_tracker)?
@@ -131,7 +173,43 @@ impl PoolFeesConfig {
             total_fee_numerator.try_into().unwrap()
         };
 
-        Ok(total_fee_numerator)
+        if fee_discount_bps == 0 {
+            return Ok(TradeFeeNumerator {
+                fee_numerator: total_fee_numerator,
+                fee_discount_bps: 0,
+            });
+        }
+
+        // volume tier discount, floored at the min base fee
+        let discounted_fee_numerator = safe_mul_div_cast_u64(
+            total_fee_numerator,
+            MAX_BASIS_POINT.safe_sub(fee_discount_bps.into())?,
+            MAX_BASIS_POINT,
+            Rounding::Up,
+        )?;
+        let min_base_fee_numerator = self.get_min_base_fee_numerator()?;
+        if discounted_fee_numerator >= min_base_fee_numerator {
+            return Ok(TradeFeeNumerator {
+                fee_numerator: discounted_fee_numerator,
+                fee_discount_bps,
+            });
+        }
+
+        // the floor only applies a part of the discount, rounded down so the protocol fee is never over its share of the undiscounted fee
+        let fee_discount_bps = if min_base_fee_numerator >= total_fee_numerator {
+            0
+        } else {
+            safe_mul_div_cast_u64(
+                total_fee_numerator.safe_sub(min_base_fee_numerator)?,
+                MAX_BASIS_POINT,
+                total_fee_numerator,
+                Rounding::Down,
+            )?
+        };
+        Ok(TradeFeeNumerator {
+            fee_numerator: min_base_fee_numerator,
+            fee_discount_bps,
+        })
     }
 
     pub fn get_fee_on_amount(
@@ -139,28 +217,21 @@ impl PoolFeesConfig {
         trade_fee_numerator: u64,
         amount: u64,
         has_referral: bool,
+        protocol_fee_percent: u8,
+        referral_fee_percent: u8,
+        fee_discount_bps: u16,
     ) -> Result<FeeOnAmountResult> {
-        let (amount, trading_fee) =
+        let (amount, fee_amount) =
             PoolFeesConfig::get_excluded_fee_amount(trade_fee_numerator, amount)?;
 
-        let protocol_fee = safe_mul_div_cast_u64(
-            trading_fee,
-            PROTOCOL_FEE_PERCENT.into(),
-            100,
-            Rounding::Down,
+        let (trading_fee, protocol_fee, referral_fee) = self.split_fees(
+            fee_amount,
+            has_referral,
+            protocol_fee_percent,
+            referral_fee_percent,
+            fee_discount_bps,
         )?;
 
-        // update trading fee
-        let trading_fee: u64 = trading_fee.safe_sub(protocol_fee)?;
-
-        let referral_fee = if has_referral {
-            safe_mul_div_cast_u64(protocol_fee, HOST_FEE_PERCENT.into(), 100, Rounding::Down)?
-        } else {
-            0
-        };
-
-        let protocol_fee = protocol_fee.safe_sub(referral_fee)?;
-
         Ok(FeeOnAmountResult {
             amount,
             protocol_fee,
@@ -198,15 +269,35 @@ impl PoolFeesConfig {
         Ok((included_fee_amount, fee_amount))
     }
 
-    pub fn split_fees(&self, fee_amount: u64, has_referral: bool) -> Result<(u64, u64, u64)> {
-        let protocol_fee =
-            safe_mul_div_cast_u64(fee_amount, PROTOCOL_FEE_PERCENT.into(), 100, Rounding::Down)?;
+    pub fn split_fees(
+        &self,
+        fee_amount: u64,
+        has_referral: bool,
+        protocol_fee_percent: u8,
+        referral_fee_percent: u8,
+        fee_discount_bps: u16,
+    ) -> Result<(u64, u64, u64)> {
+        // the volume tier discount only reduces the partner and creator share, so the protocol fee is the protocol share of the undiscounted fee.
+        // fee_discount_bps must be the discount applied in the fee numerator, see TradeFeeNumerator
+        let protocol_fee = safe_mul_div_cast_u64(
+            fee_amount,
+            u64::from(protocol_fee_percent).safe_mul(MAX_BASIS_POINT)?,
+            MAX_BASIS_POINT
+                .safe_sub(fee_discount_bps.into())?
+                .safe_mul(100)?,
+            Rounding::Down,
+        )?;
 
         // update trading fee
         let trading_fee: u64 = fee_amount.safe_sub(protocol_fee)?;
 
         let referral_fee = if has_referral {
-            safe_mul_div_cast_u64(protocol_fee, HOST_FEE_PERCENT.into(), 100, Rounding::Down)?
+            safe_mul_div_cast_u64(
+                protocol_fee,
+                referral_fee_percent.into(),
+                100,
+                Rounding::Down,
+            )?
         } else {
             0
         };
@@ -232,7 +323,8 @@ pub struct BaseFeeConfig {
     pub third_factor: u64,
     pub first_factor: u16,
     pub base_fee_mode: u8,
-    pub padding_0: [u8; 5],
+    pub rate_limiter_mode: u8,
+    pub padding_0: [u8; 4],
 }
 
 const_assert_eq!(BaseFeeConfig::INIT_SPACE, 32);
@@ -247,6 +339,8 @@ impl BaseFeeConfig {
                 reference_amount: self.third_factor,
                 max_limiter_duration: self.second_factor,
                 fee_increment_bps: self.first_factor,
+                rate_limiter_mode: RateLimiterMode::try_from(self.rate_limiter_mode)
+                    .map_err(|_| PoolError::InvalidFeeRateLimiter)?,
             })
         } else {
             Err(PoolError::InvalidFeeRateLimiter.into())
@@ -260,6 +354,7 @@ impl BaseFeeConfig {
             self.second_factor,
             self.third_factor,
             self.base_fee_mode,
+            self.rate_limiter_mode,
         )
     }
 
@@ -293,7 +388,9 @@ pub struct DynamicFeeConfig {
     pub filter_period: u16,
     pub decay_period: u16,
     pub reduction_factor: u16,
-    pub padding2: [u8; 8], // Add padding for u128 alignment
+    /// swaps are halted while the volatility accumulator is over this threshold, 0 to disable
+    pub circuit_breaker_volatility_accumulator: u32,
+    pub padding2: [u8; 4], // Add padding for u128 alignment
     pub bin_step_u128: u128,
 }
 
@@ -304,6 +401,10 @@ impl DynamicFeeConfig {
         self.initialized != 0
     }
 
+    pub fn is_circuit_breaker_enable(&self) -> bool {
+        self.is_dynamic_fee_enable() && self.circuit_breaker_volatility_accumulator != 0
+    }
+
     pub fn get_variable_fee_numerator(
         &self,
         volatility_tracker: &VolatilityTracker,
@@ -433,6 +534,44 @@ pub enum TokenType {
     Token2022,
 }
 
+#[repr(u8)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    IntoPrimitive,
+    TryFromPrimitive,
+    AnchorDeserialize,
+    AnchorSerialize,
+)]
+pub enum CurveType {
+    /// Piecewise constant product, the liquidity of every segment is given by the curve points
+    ConstantProduct,
+    /// Price grows linearly with the base amount sold, from sqrt_start_price to curve[0].sqrt_price while curve[0].liquidity base token is sold
+    LinearPrice,
+    /// Price grows exponentially with the base amount sold, from sqrt_start_price to curve[0].sqrt_price while curve[0].liquidity base token is sold
+    ExponentialPrice,
+}
+
+/// Share of the quote trading fee that is used to buy back and burn base token, in the QuoteTokenBuybackBurn collect fee mode
+#[repr(u8)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    IntoPrimitive,
+    TryFromPrimitive,
+    AnchorDeserialize,
+    AnchorSerialize,
+)]
+pub enum BuybackFeeSource {
+    Partner,
+    Creator,
+    PartnerAndCreator,
+}
+
 #[repr(u8)]
 #[derive(
     Clone,
@@ -498,10 +637,23 @@ pub struct PoolConfig {
     pub partner_liquidity_vesting_info: LiquidityVestingInfo,
     // Creator liquidity vesting info, only available for DAMM v2 migration
     pub creator_liquidity_vesting_info: LiquidityVestingInfo,
-    /// Padding for future use
-    pub padding_0: [u8; 14],
-    /// Previously was protocol and referral fee percent. Beware of tombstone.
-    pub padding_1: u16,
+    /// Duration of the buy cap window from activation point, in slot or timestamp depends on activation type, 0 means no cap. The max buy amount per wallet is stored in the config extension
+    pub buy_cap_duration: u32,
+    /// Number of pools created with the config, only tracked from version 1
+    pub pool_count: u32,
+    /// Whether a swap hook is registered, the hook program is invoked after every swap
+    pub swap_hook_flag: u8,
+    /// Whether sells pay the sell base fee stored in the config extension, instead of the base fee in pool fees
+    pub sell_base_fee_flag: u8,
+    /// Whether the fee claimer retired the config, no pool can be created with a retired config
+    pub retired_flag: u8,
+    /// Share of the quote trading fee that is bought back and burned, only used in the QuoteTokenBuybackBurn collect fee mode
+    pub buyback_fee_source: u8,
+    pub padding_0: [u8; 2],
+    /// Curve type, only used from version 1. Previously was protocol fee percent, beware of tombstone in version 0.
+    pub curve_type: u8,
+    /// Whether pools can only be created by the creators allowed by the partner or with the fee claimer signature, only used from version 1. Previously was referral fee percent, beware of tombstone in version 0.
+    pub creator_allowlist_flag: u8,
     /// Collect fee mode
     pub collect_fee_mode: u8,
     /// migration option
@@ -536,7 +688,14 @@ pub struct PoolConfig {
     pub migration_fee_percentage: u8,
     /// creator migration fee percentage
     pub creator_migration_fee_percentage: u8,
-    pub padding_2: [u8; 7],
+    /// protocol percentage of the trading fee, snapshotted from the protocol config. Only used from version 1
+    pub protocol_fee_percent: u8,
+    /// referral percentage of the protocol fee, snapshotted from the protocol config. Only used from version 1
+    pub referral_fee_percent: u8,
+    /// protocol percentage of the pool creation fee, snapshotted from the protocol config. Only used from version 1
+    pub protocol_pool_creation_fee_percent: u8,
+    /// Duration of the allowlist phase from activation point, in slot or timestamp depends on activation type, 0 means no allowlist phase
+    pub allowlist_duration: u32,
     /// swap base amount
     pub swap_base_amount: u64,
     /// migration quote threshold (in quote token)
@@ -733,8 +892,16 @@ impl PoolConfig {
         migrated_pool_market_cap_fee_scheduler: MigratedPoolMarketCapFeeSchedulerParams,
         curve: &[LiquidityDistributionParameters],
         enable_creator_first_swap_with_min_fee: u8,
+        buy_cap_duration: u32,
+        allowlist_duration: u32,
+        sell_base_fee_flag: u8,
+        curve_type: u8,
+        creator_allowlist_flag: u8,
+        buyback_fee_source: u8,
+        protocol_config: &ProtocolConfig,
     ) -> Result<()> {
-        self.version = 0;
+        // version 1: protocol fee shares, curve type and creator allowlist flag are stored in the config, and pools are counted
+        self.version = 1;
         self.quote_mint = *quote_mint;
         self.fee_claimer = *fee_claimer;
         self.leftover_receiver = *leftover_receiver;
@@ -789,6 +956,17 @@ impl PoolConfig {
 
         self.enable_first_swap_with_min_fee = enable_creator_first_swap_with_min_fee;
 
+        self.buy_cap_duration = buy_cap_duration;
+        self.allowlist_duration = allowlist_duration;
+        self.sell_base_fee_flag = sell_base_fee_flag;
+        self.protocol_fee_percent = protocol_config.protocol_fee_percent;
+        self.referral_fee_percent = protocol_config.referral_fee_percent;
+        self.protocol_pool_creation_fee_percent =
+            protocol_config.protocol_pool_creation_fee_percent;
+        self.curve_type = curve_type;
+        self.creator_allowlist_flag = creator_allowlist_flag;
+        self.buyback_fee_source = buyback_fee_source;
+
         for i in 0..curve.len() {
             self.curve[i] = curve[i].to_liquidity_distribution_config();
         }
@@ -842,13 +1020,33 @@ impl PoolConfig {
         swap_base_amount: u64,
         sqrt_start_price: u128,
         curve: &[LiquidityDistributionParameters],
+    ) -> Result<u64> {
+        let max_base_amount_on_curve =
+            get_base_token_for_swap(sqrt_start_price, MAX_SQRT_PRICE, &curve)?;
+        PoolConfig::cap_swap_amount_with_buffer(swap_base_amount, max_base_amount_on_curve)
+    }
+
+    /// Same as get_swap_amount_with_buffer, but the price curve ends at its end price instead of the max sqrt price
+    pub fn get_swap_amount_with_buffer_on_price_curve(
+        swap_base_amount: u64,
+        price_curve: &PriceCurve,
+    ) -> Result<u64> {
+        let max_base_amount_on_curve = price_curve.get_delta_amount_base(
+            price_curve.sqrt_start_price,
+            price_curve.end_sqrt_price,
+            Rounding::Up,
+        )?;
+        PoolConfig::cap_swap_amount_with_buffer(swap_base_amount, max_base_amount_on_curve)
+    }
+
+    fn cap_swap_amount_with_buffer(
+        swap_base_amount: u64,
+        max_base_amount_on_curve: U256,
     ) -> Result<u64> {
         let swap_amount_buffer = u128::from(swap_base_amount)
             .safe_mul(SWAP_BUFFER_PERCENTAGE.into())?
             .safe_div(100)?
             .safe_add(swap_base_amount.into())?;
-        let max_base_amount_on_curve =
-            get_base_token_for_swap(sqrt_start_price, MAX_SQRT_PRICE, &curve)?;
 
         if U256::from(swap_amount_buffer) < max_base_amount_on_curve {
             Ok(u64::try_from(swap_amount_buffer).map_err(|_| PoolError::MathOverflow)?)
@@ -872,6 +1070,16 @@ impl PoolConfig {
     pub fn get_initial_base_supply(&self) -> Result<u64> {
         if self.is_fixed_token_supply() {
             Ok(self.pre_migration_token_supply)
+        } else if let Some(price_curve) = self.get_price_curve()? {
+            let swap_amount_with_buffer = PoolConfig::get_swap_amount_with_buffer_on_price_curve(
+                self.swap_base_amount,
+                &price_curve,
+            )?;
+            PoolConfig::get_total_token_supply(
+                swap_amount_with_buffer,
+                self.migration_base_threshold,
+                &self.locked_vesting_config.to_locked_vesting_params(),
+            )
         } else {
             let mut curve = vec![];
             for i in 0..MAX_CURVE_POINT_CONFIG {
@@ -913,6 +1121,29 @@ impl PoolConfig {
         self.fixed_token_supply_flag == 1
     }
 
+    pub fn retire(&mut self) {
+        self.retired_flag = 1;
+    }
+
+    pub fn is_retired(&self) -> bool {
+        self.retired_flag == 1
+    }
+
+    pub fn increase_pool_count(&mut self) -> Result<()> {
+        self.pool_count = self.pool_count.safe_add(1)?;
+        Ok(())
+    }
+
+    /// Pools are only counted from version 1, so older configs can't be closed as they may be referenced by pools
+    pub fn is_closable(&self) -> bool {
+        self.version >= 1 && self.pool_count == 0
+    }
+
+    // creator_allowlist_flag is a tombstone in version 0
+    pub fn is_creator_allowlist_enabled(&self) -> bool {
+        self.version != 0 && self.creator_allowlist_flag == 1
+    }
+
     pub fn get_liquidity_distribution(&self, liquidity: u128) -> Result<LiquidityDistribution> {
         let partner_permanent_locked_liquidity = safe_mul_div_cast_u128(
             liquidity,
@@ -997,10 +1228,102 @@ impl PoolConfig {
         })
     }
 
+    pub fn get_curve_type(&self) -> Result<CurveType> {
+        // curve_type is a tombstone in version 0, all of these configs are constant product
+        if self.version == 0 {
+            return Ok(CurveType::ConstantProduct);
+        }
+        let curve_type =
+            CurveType::try_from(self.curve_type).map_err(|_| PoolError::InvalidCurveType)?;
+        Ok(curve_type)
+    }
+
+    /// Price curve of the config, none for the constant product curve
+    pub fn get_price_curve(&self) -> Result<Option<PriceCurve>> {
+        let curve_type = self.get_curve_type()?;
+        if curve_type == CurveType::ConstantProduct {
+            return Ok(None);
+        }
+        let price_curve = PriceCurve::new(
+            curve_type,
+            self.sqrt_start_price,
+            self.curve[0].sqrt_price,
+            self.curve[0].liquidity,
+        )?;
+        Ok(Some(price_curve))
+    }
+
+    pub fn get_protocol_fee_percent(&self) -> u8 {
+        if self.version == 0 {
+            PROTOCOL_FEE_PERCENT
+        } else {
+            self.protocol_fee_percent
+        }
+    }
+
+    pub fn get_referral_fee_percent(&self) -> u8 {
+        if self.version == 0 {
+            HOST_FEE_PERCENT
+        } else {
+            self.referral_fee_percent
+        }
+    }
+
+    pub fn get_protocol_pool_creation_fee_percent(&self) -> u8 {
+        if self.version == 0 {
+            PROTOCOL_POOL_CREATION_FEE_PERCENT
+        } else {
+            self.protocol_pool_creation_fee_percent
+        }
+    }
+
+    pub fn get_fee_on_amount(
+        &self,
+        trade_fee_numerator: u64,
+        amount: u64,
+        has_referral: bool,
+        fee_discount_bps: u16,
+    ) -> Result<FeeOnAmountResult> {
+        self.pool_fees.get_fee_on_amount(
+            trade_fee_numerator,
+            amount,
+            has_referral,
+            self.get_protocol_fee_percent(),
+            self.get_referral_fee_percent(),
+            fee_discount_bps,
+        )
+    }
+
+    pub fn split_fees(
+        &self,
+        fee_amount: u64,
+        has_referral: bool,
+        fee_discount_bps: u16,
+    ) -> Result<(u64, u64, u64)> {
+        self.pool_fees.split_fees(
+            fee_amount,
+            has_referral,
+            self.get_protocol_fee_percent(),
+            self.get_referral_fee_percent(),
+            fee_discount_bps,
+        )
+    }
+
+    /// Discount on the total fee for a volume tier discount on the partner and creator share of the fee, in bps
+    pub fn get_total_fee_discount_bps(&self, tier_fee_discount_bps: u16) -> Result<u16> {
+        let partner_and_creator_fee_percent = 100u8.safe_sub(self.get_protocol_fee_percent())?;
+        safe_mul_div_cast_u64(
+            tier_fee_discount_bps.into(),
+            partner_and_creator_fee_percent.into(),
+            100,
+            Rounding::Down,
+        )
+    }
+
     pub fn split_pool_creation_fee(&self) -> Result<(u64, u64)> {
         let protocol_fee = safe_mul_div_cast_u64(
             self.pool_creation_fee,
-            PROTOCOL_POOL_CREATION_FEE_PERCENT.into(),
+            self.get_protocol_pool_creation_fee_percent().into(),
             100,
             Rounding::Down,
         )?;
@@ -1176,9 +1499,128 @@ impl PoolConfig {
         Ok(pool_fees)
     }
 
+    pub fn is_sell_base_fee_applied(&self, trade_direction: TradeDirection) -> bool {
+        self.sell_base_fee_flag == 1 && trade_direction == TradeDirection::BaseToQuote
+    }
+
+    /// Config with the sell base fee in place of the base fee, so sells go through the same fee path as buys
+    pub fn with_sell_base_fee(&self, config_extension: &PoolConfigExtension) -> Box<PoolConfig> {
+        let mut config = Box::new(*self);
+        config.pool_fees.base_fee = config_extension.sell_base_fee;
+        config
+    }
+
+    pub fn is_fee_step_table_enabled(&self) -> bool {
+        self.pool_fees.base_fee.base_fee_mode == BaseFeeMode::FeeStepTable.into()
+    }
+
+    /// The config extension is only created together with the config when the sell base fee, the fee step table, the wallet buy cap or the buyback is set
+    pub fn has_config_extension(&self) -> bool {
+        self.sell_base_fee_flag == 1
+            || self.is_fee_step_table_enabled()
+            || self.is_wallet_buy_cap_enabled()
+            || self.is_buyback_burn_enabled()
+    }
+
+    /// Config with the fee of the current step of the fee step table as base fee
+    pub fn with_fee_step(
+        &self,
+        config_extension: &PoolConfigExtension,
+        current_point: u64,
+        activation_point: u64,
+    ) -> Result<Box<PoolConfig>> {
+        let mut config = Box::new(*self);
+        config.pool_fees.base_fee.cliff_fee_numerator = config_extension.get_fee_step_numerator(
+            self.pool_fees.base_fee.first_factor,
+            current_point,
+            activation_point,
+        )?;
+        Ok(config)
+    }
+
+    /// Config with the base fee a swap pays, if it differs from the config's: sells pay the sell base fee of the config extension, otherwise the fee of the current step if the base fee is a fee step table
+    pub fn get_fee_config(
+        &self,
+        trade_direction: TradeDirection,
+        config_extension: Option<&PoolConfigExtension>,
+        current_point: u64,
+        activation_point: u64,
+    ) -> Result<Option<Box<PoolConfig>>> {
+        if self.is_sell_base_fee_applied(trade_direction) {
+            let config_extension = config_extension.ok_or(PoolError::MissingConfigExtension)?;
+            Ok(Some(self.with_sell_base_fee(config_extension)))
+        } else if self.is_fee_step_table_enabled() {
+            let config_extension = config_extension.ok_or(PoolError::MissingConfigExtension)?;
+            Ok(Some(self.with_fee_step(
+                config_extension,
+                current_point,
+                activation_point,
+            )?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn is_swap_hook_enabled(&self) -> bool {
+        self.swap_hook_flag == 1
+    }
+
     pub fn is_first_swap_with_min_fee_enabled(&self) -> bool {
         self.enable_first_swap_with_min_fee == 1
     }
+
+    pub fn update_fee_claimer(&mut self, fee_claimer: Pubkey, leftover_receiver: Pubkey) {
+        self.fee_claimer = fee_claimer;
+        self.leftover_receiver = leftover_receiver;
+    }
+
+    pub fn is_buyback_burn_enabled(&self) -> bool {
+        self.collect_fee_mode == CollectFeeMode::QuoteTokenBuybackBurn.into()
+    }
+
+    /// Whether the partner quote fee is bought back and burned, instead of being claimed by the partner
+    pub fn is_partner_quote_fee_bought_back(&self) -> bool {
+        self.is_buyback_burn_enabled()
+            && self.buyback_fee_source != BuybackFeeSource::Creator.into()
+    }
+
+    /// Whether the creator quote fee is bought back and burned, instead of being claimed by the creator
+    pub fn is_creator_quote_fee_bought_back(&self) -> bool {
+        self.is_buyback_burn_enabled()
+            && self.buyback_fee_source != BuybackFeeSource::Partner.into()
+    }
+
+    pub fn is_wallet_buy_cap_enabled(&self) -> bool {
+        self.buy_cap_duration > 0
+    }
+
+    /// Buy cap is only applied for buying (QuoteToBase) in the first buy_cap_duration points after activation point
+    pub fn is_wallet_buy_cap_applied(
+        &self,
+        current_point: u64,
+        activation_point: u64,
+        trade_direction: TradeDirection,
+    ) -> Result<bool> {
+        if !self.is_wallet_buy_cap_enabled() || trade_direction == TradeDirection::BaseToQuote {
+            return Ok(false);
+        }
+        let last_effective_point = activation_point.safe_add(self.buy_cap_duration.into())?;
+        Ok(current_point < last_effective_point)
+    }
+
+    /// Only allowlisted wallets can buy (QuoteToBase) in the first allowlist_duration points after activation point
+    pub fn is_allowlist_phase_applied(
+        &self,
+        current_point: u64,
+        activation_point: u64,
+        trade_direction: TradeDirection,
+    ) -> Result<bool> {
+        if self.allowlist_duration == 0 || trade_direction == TradeDirection::BaseToQuote {
+            return Ok(false);
+        }
+        let last_effective_point = activation_point.safe_add(self.allowlist_duration.into())?;
+        Ok(current_point < last_effective_point)
+    }
 }
 
 pub struct PartnerAndCreatorSplitFee {
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use crate::{constants::MAX_FEE_STEPS, safe_math::SafeMath};
+
+use super::BaseFeeConfig;
+
+#[zero_copy]
+#[derive(InitSpace, Debug, Default)]
+pub struct FeeStep {
+    /// points elapsed since activation from which the fee numerator is charged, in slot or timestamp depends on activation type
+    pub elapsed_point: u64,
+    /// base fee numerator of the step
+    pub fee_numerator: u64,
+}
+
+const_assert_eq!(FeeStep::INIT_SPACE, 16);
+
+/// Extra config of a pool config, for the fields that don't fit in PoolConfig. It can only be created together with the config
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct PoolConfigExtension {
+    /// config
+    pub config: Pubkey,
+    /// base fee of BaseToQuote (sell) trades, only used when sell_base_fee_flag of the config is set
+    pub sell_base_fee: BaseFeeConfig,
+    /// fee steps of the FeeStepTable base fee mode, only the first number_of_step (first_factor of the base fee) steps are used
+    pub fee_steps: [FeeStep; MAX_FEE_STEPS],
+    /// max quote amount (included fee) a wallet can spend to buy base token in the buy cap window, only used when buy_cap_duration of the config is set
+    pub max_buy_amount_per_wallet: u64,
+    /// max quote fee the buyback can spend in a buyback period, only used in the QuoteTokenBuybackBurn collect fee mode
+    pub buyback_max_quote_amount_per_period: u64,
+    /// duration of a buyback period, in slot or timestamp depends on activation type
+    pub buyback_period: u64,
+    /// padding for future use
+    pub padding: [u64; 13],
+}
+
+const_assert_eq!(PoolConfigExtension::INIT_SPACE, 320);
+
+impl PoolConfigExtension {
+    pub fn initialize(
+        &mut self,
+        config: Pubkey,
+        sell_base_fee: BaseFeeConfig,
+        fee_steps: [FeeStep; MAX_FEE_STEPS],
+        max_buy_amount_per_wallet: u64,
+        buyback_max_quote_amount_per_period: u64,
+        buyback_period: u64,
+    ) {
+        self.config = config;
+        self.sell_base_fee = sell_base_fee;
+        self.fee_steps = fee_steps;
+        self.max_buy_amount_per_wallet = max_buy_amount_per_wallet;
+        self.buyback_max_quote_amount_per_period = buyback_max_quote_amount_per_period;
+        self.buyback_period = buyback_period;
+    }
+
+    /// Fee numerator of the last step reached at current_point, steps are sorted by elapsed_point and the first one starts at activation
+    pub fn get_fee_step_numerator(
+        &self,
+        number_of_step: u16,
+        current_point: u64,
+        activation_point: u64,
+    ) -> Result<u64> {
+        let elapsed_point = current_point.safe_sub(activation_point)?;
+        let fee_steps = &self.fee_steps[..usize::from(number_of_step).min(MAX_FEE_STEPS)];
+
+        let fee_numerator = fee_steps
+            .iter()
+            .take_while(|fee_step| fee_step.elapsed_point <= elapsed_point)
+            .last()
+            .map(|fee_step| fee_step.fee_numerator)
+            .unwrap_or_default();
+
+        Ok(fee_numerator)
+    }
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use crate::{
+    constants::{fee::MAX_BASIS_POINT, MAX_CREATOR_FEE_SHARE_RECIPIENTS},
+    safe_math::SafeMath,
+    u128x128_math::Rounding,
+    utils_math::safe_mul_div_cast_u64,
+};
+
+#[zero_copy]
+#[derive(InitSpace, Debug, Default)]
+pub struct CreatorFeeShareRecipient {
+    /// recipient wallet
+    pub recipient: Pubkey,
+    /// share of the creator trading fee, in bps
+    pub weight_bps: u16,
+    pub padding: [u8; 6],
+}
+
+const_assert_eq!(CreatorFeeShareRecipient::INIT_SPACE, 40);
+
+/// Split the creator trading fee of a pool between multiple recipients
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct CreatorFeeShare {
+    /// pool
+    pub pool: Pubkey,
+    /// recipients, unused recipients are zero
+    pub recipients: [CreatorFeeShareRecipient; MAX_CREATOR_FEE_SHARE_RECIPIENTS],
+    /// padding for future use
+    pub padding: [u64; 4],
+}
+
+const_assert_eq!(CreatorFeeShare::INIT_SPACE, 264);
+
+impl CreatorFeeShare {
+    pub fn initialize(
+        &mut self,
+        pool: Pubkey,
+        recipients: [CreatorFeeShareRecipient; MAX_CREATOR_FEE_SHARE_RECIPIENTS],
+    ) {
+        self.pool = pool;
+        self.recipients = recipients;
+    }
+
+    pub fn get_recipients(&self) -> &[CreatorFeeShareRecipient] {
+        let count = self
+            .recipients
+            .iter()
+            .take_while(|recipient| recipient.weight_bps != 0)
+            .count();
+        &self.recipients[..count]
+    }
+
+    /// Split the amount by the weight of each recipient, the rounding dust goes to the last recipient
+    pub fn split_fee(&self, amount: u64) -> Result<[u64; MAX_CREATOR_FEE_SHARE_RECIPIENTS]> {
+        let recipients = self.get_recipients();
+        let mut amounts = [0u64; MAX_CREATOR_FEE_SHARE_RECIPIENTS];
+        let mut remaining_amount = amount;
+        for (i, recipient) in recipients.iter().enumerate() {
+            let recipient_amount = if i == recipients.len() - 1 {
+                remaining_amount
+            } else {
+                safe_mul_div_cast_u64(
+                    amount,
+                    recipient.weight_bps.into(),
+                    MAX_BASIS_POINT,
+                    Rounding::Down,
+                )?
+            };
+            amounts[i] = recipient_amount;
+            remaining_amount = remaining_amount.safe_sub(recipient_amount)?;
+        }
+        Ok(amounts)
+    }
+}
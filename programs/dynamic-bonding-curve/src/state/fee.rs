@@ -12,6 +12,14 @@ use crate::{
     PoolError,
 };
 
+/// Trade fee numerator with the volume tier discount that is actually applied in it
+#[derive(Debug, PartialEq)]
+pub struct TradeFeeNumerator {
+    pub fee_numerator: u64,
+    /// discount on the total fee in bps, lower than the tier discount when the fee is floored at the min base fee
+    pub fee_discount_bps: u16,
+}
+
 /// Encodes all results of swapping
 #[derive(Debug, PartialEq)]
 pub struct FeeOnAmountResult {
@@ -115,6 +123,8 @@ pub struct FeeMode {
     pub fees_on_input: bool,
     pub fees_on_base_token: bool,
     pub has_referral: bool,
+    /// discount on the total fee for the wallet volume tier, in bps. Only the partner and creator share of the fee is discounted
+    pub fee_discount_bps: u16,
 }
 
 impl FeeMode {
@@ -132,14 +142,21 @@ impl FeeMode {
             (CollectFeeMode::OutputToken, TradeDirection::QuoteToBase) => (false, true),
 
             // When collecting fees on quote token
-            (CollectFeeMode::QuoteToken, TradeDirection::BaseToQuote) => (false, false),
-            (CollectFeeMode::QuoteToken, TradeDirection::QuoteToBase) => (true, false),
+            (
+                CollectFeeMode::QuoteToken | CollectFeeMode::QuoteTokenBuybackBurn,
+                TradeDirection::BaseToQuote,
+            ) => (false, false),
+            (
+                CollectFeeMode::QuoteToken | CollectFeeMode::QuoteTokenBuybackBurn,
+                TradeDirection::QuoteToBase,
+            ) => (true, false),
         };
 
         Ok(FeeMode {
             fees_on_input,
             fees_on_base_token,
             has_referral,
+            fee_discount_bps: 0,
         })
     }
 }
@@ -204,10 +221,31 @@ mod tests {
         assert_eq!(fee_mode.has_referral, true);
     }
 
+    #[test]
+    fn test_fee_mode_quote_token_buyback_burn() {
+        let fee_mode = FeeMode::get_fee_mode(
+            CollectFeeMode::QuoteTokenBuybackBurn as u8,
+            TradeDirection::QuoteToBase,
+            false,
+        )
+        .unwrap();
+        assert_eq!(fee_mode.fees_on_input, true);
+        assert_eq!(fee_mode.fees_on_base_token, false);
+
+        let fee_mode = FeeMode::get_fee_mode(
+            CollectFeeMode::QuoteTokenBuybackBurn as u8,
+            TradeDirection::BaseToQuote,
+            false,
+        )
+        .unwrap();
+        assert_eq!(fee_mode.fees_on_input, false);
+        assert_eq!(fee_mode.fees_on_base_token, false);
+    }
+
     #[test]
     fn test_invalid_collect_fee_mode() {
         let result = FeeMode::get_fee_mode(
-            2, // Invalid mode
+            3, // Invalid mode
             TradeDirection::QuoteToBase,
             false,
         );
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+/// Pending transfer of the fee claimer and leftover receiver roles of a config, proposed by the current fee claimer and accepted by the new one
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct FeeClaimerTransfer {
+    /// config
+    pub config: Pubkey,
+    /// proposed fee claimer
+    pub new_fee_claimer: Pubkey,
+    /// proposed leftover receiver
+    pub new_leftover_receiver: Pubkey,
+    /// payer of the account rent, refunded when the transfer is accepted or cancelled
+    pub payer: Pubkey,
+    /// padding for future use
+    pub padding: [u64; 4],
+}
+
+const_assert_eq!(FeeClaimerTransfer::INIT_SPACE, 160);
+
+impl FeeClaimerTransfer {
+    pub fn initialize(
+        &mut self,
+        config: Pubkey,
+        new_fee_claimer: Pubkey,
+        new_leftover_receiver: Pubkey,
+        payer: Pubkey,
+    ) {
+        self.config = config;
+        self.new_fee_claimer = new_fee_claimer;
+        self.new_leftover_receiver = new_leftover_receiver;
+        self.payer = payer;
+    }
+}
@@ -11,3 +11,27 @@ pub mod virtual_pool_metadata;
 pub use virtual_pool_metadata::*;
 pub mod operator;
 pub use operator::*;
+pub mod wallet_buy_tracker;
+pub use wallet_buy_tracker::*;
+pub mod presale_allowlist;
+pub use presale_allowlist::*;
+pub mod swap_hook;
+pub use swap_hook::*;
+pub mod config_extension;
+pub use config_extension::*;
+pub mod protocol_config;
+pub use protocol_config::*;
+pub mod referrer;
+pub use referrer::*;
+pub mod volume_fee_tiers;
+pub use volume_fee_tiers::*;
+pub mod wallet_volume_tracker;
+pub use wallet_volume_tracker::*;
+pub mod creator_fee_share;
+pub use creator_fee_share::*;
+pub mod fee_claimer_transfer;
+pub use fee_claimer_transfer::*;
+pub mod allowed_creator;
+pub use allowed_creator::*;
+pub mod pool_observations;
+pub use pool_observations::*;
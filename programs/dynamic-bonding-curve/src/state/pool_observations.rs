@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use crate::{
+    constants::{MAX_POOL_OBSERVATIONS, MIN_POOL_OBSERVATION_INTERVAL},
+    safe_math::SafeMath,
+    PoolError,
+};
+
+#[zero_copy]
+#[derive(InitSpace, Debug, Default)]
+pub struct Observation {
+    /// sqrt price cumulative at the timestamp
+    pub sqrt_price_cumulative: u128,
+    pub timestamp: u64,
+    pub padding: u64,
+}
+
+const_assert_eq!(Observation::INIT_SPACE, 32);
+
+impl Observation {
+    /// Sqrt price cumulative at a timestamp between this observation and a newer one, assuming the price is constant between them
+    fn interpolate(&self, newer: &Observation, timestamp: u64) -> Result<u128> {
+        let elapsed = newer.timestamp.safe_sub(self.timestamp)?;
+        // cumulative wraps on overflow, the difference is still correct as long as it fits in u128
+        let average_sqrt_price = newer
+            .sqrt_price_cumulative
+            .wrapping_sub(self.sqrt_price_cumulative)
+            .safe_div(elapsed.into())?;
+        let delta = timestamp.safe_sub(self.timestamp)?;
+        Ok(self
+            .sqrt_price_cumulative
+            .wrapping_add(average_sqrt_price.wrapping_mul(delta.into())))
+    }
+}
+
+/// Sqrt price accumulator of a pool and a ring buffer of its past values, to compute the time-weighted sqrt price over a window.
+/// Swaps update it when it's passed in remaining accounts, the observations restart when the price moved without an update
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct PoolObservations {
+    /// pool
+    pub pool: Pubkey,
+    /// sum of sqrt_price * elapsed seconds since the account is created, wrapping on overflow
+    pub sqrt_price_cumulative: u128,
+    /// sqrt price of the pool after the last update
+    pub last_sqrt_price: u128,
+    /// last timestamp the sqrt price cumulative was updated
+    pub last_update_timestamp: u64,
+    /// index of the latest observation
+    pub latest_index: u8,
+    /// number of observations written, up to MAX_POOL_OBSERVATIONS
+    pub observation_count: u8,
+    pub padding_0: [u8; 6],
+    /// observations written at least MIN_POOL_OBSERVATION_INTERVAL seconds apart, the oldest one is overwritten when the buffer is full
+    pub observations: [Observation; MAX_POOL_OBSERVATIONS],
+}
+
+const_assert_eq!(PoolObservations::INIT_SPACE, 1104);
+
+impl PoolObservations {
+    pub fn initialize(&mut self, pool: Pubkey, sqrt_price: u128, current_timestamp: u64) {
+        self.pool = pool;
+        self.last_sqrt_price = sqrt_price;
+        self.restart(current_timestamp);
+    }
+
+    /// Drop the observations and start again from the current sqrt price cumulative
+    fn restart(&mut self, current_timestamp: u64) {
+        self.last_update_timestamp = current_timestamp;
+        self.observations[0] = Observation {
+            sqrt_price_cumulative: self.sqrt_price_cumulative,
+            timestamp: current_timestamp,
+            padding: 0,
+        };
+        self.latest_index = 0;
+        self.observation_count = 1;
+    }
+
+    fn get_sqrt_price_cumulative(&self, sqrt_price: u128, current_timestamp: u64) -> Result<u128> {
+        let elapsed = current_timestamp.safe_sub(self.last_update_timestamp)?;
+        Ok(self
+            .sqrt_price_cumulative
+            .wrapping_add(sqrt_price.wrapping_mul(elapsed.into())))
+    }
+
+    fn get_observation(&self, offset: usize) -> &Observation {
+        let index = (usize::from(self.latest_index) + MAX_POOL_OBSERVATIONS - offset)
+            % MAX_POOL_OBSERVATIONS;
+        &self.observations[index]
+    }
+
+    /// Accumulate the sqrt price held before a swap since the last update, and write an observation if the latest one is old enough.
+    /// If the price before the swap isn't the price after the last update, swaps without the account moved the price in between and
+    /// the price path is unknown, so the observations restart from the current timestamp
+    pub fn update(
+        &mut self,
+        old_sqrt_price: u128,
+        new_sqrt_price: u128,
+        current_timestamp: u64,
+    ) -> Result<()> {
+        if old_sqrt_price != self.last_sqrt_price {
+            self.last_sqrt_price = new_sqrt_price;
+            self.restart(current_timestamp);
+            return Ok(());
+        }
+        self.sqrt_price_cumulative =
+            self.get_sqrt_price_cumulative(old_sqrt_price, current_timestamp)?;
+        self.last_sqrt_price = new_sqrt_price;
+        self.last_update_timestamp = current_timestamp;
+
+        let latest_timestamp = self.get_observation(0).timestamp;
+        if current_timestamp >= latest_timestamp.safe_add(MIN_POOL_OBSERVATION_INTERVAL)? {
+            let index = (usize::from(self.latest_index) + 1) % MAX_POOL_OBSERVATIONS;
+            self.observations[index] = Observation {
+                sqrt_price_cumulative: self.sqrt_price_cumulative,
+                timestamp: current_timestamp,
+                padding: 0,
+            };
+            self.latest_index = index as u8;
+            if usize::from(self.observation_count) < MAX_POOL_OBSERVATIONS {
+                self.observation_count = self.observation_count.safe_add(1)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Time-weighted sqrt price over the last `window` seconds, where `sqrt_price` is the current sqrt price of the pool.
+    /// The window must be covered by the observations, and the price must not have moved since the last update
+    pub fn twap(&self, sqrt_price: u128, current_timestamp: u64, window: u64) -> Result<u128> {
+        require!(
+            window > 0 && sqrt_price == self.last_sqrt_price,
+            PoolError::InvalidTwapWindow
+        );
+        let start_timestamp = current_timestamp.safe_sub(window)?;
+
+        let current = Observation {
+            sqrt_price_cumulative: self.get_sqrt_price_cumulative(sqrt_price, current_timestamp)?,
+            timestamp: current_timestamp,
+            padding: 0,
+        };
+        let mut newer = current;
+        for offset in 0..usize::from(self.observation_count) {
+            let observation = self.get_observation(offset);
+            if observation.timestamp <= start_timestamp {
+                let start_cumulative = observation.interpolate(&newer, start_timestamp)?;
+                let delta = current.sqrt_price_cumulative.wrapping_sub(start_cumulative);
+                return Ok(delta.safe_div(window.into())?);
+            }
+            newer = *observation;
+        }
+        Err(PoolError::InvalidTwapWindow.into())
+    }
+}
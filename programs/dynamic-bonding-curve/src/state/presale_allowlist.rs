@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use static_assertions::const_assert_eq;
+
+use crate::{constants::MAX_ALLOWLIST_PROOF_LENGTH, PoolError};
+
+const LEAF_PREFIX: &[u8] = &[0];
+const INTERMEDIATE_PREFIX: &[u8] = &[1];
+
+/// Allowlist of wallets that can buy in the allowlist phase of pools created from a config
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct PresaleAllowlist {
+    /// config
+    pub config: Pubkey,
+    /// merkle root of the allowlisted wallets and their allocations, leaf = sha256(0 || wallet || allocation_le), node = sha256(1 || min(a, b) || max(a, b)).
+    /// The allocation is the max quote amount (included fee) the wallet can spend to buy base token in the allowlist phase
+    pub merkle_root: [u8; 32],
+    /// padding for future use
+    pub padding: [u64; 5],
+}
+
+const_assert_eq!(PresaleAllowlist::INIT_SPACE, 104);
+
+impl PresaleAllowlist {
+    pub fn initialize(&mut self, config: Pubkey, merkle_root: [u8; 32]) {
+        self.config = config;
+        self.merkle_root = merkle_root;
+    }
+
+    pub fn verify_proof(&self, wallet: &Pubkey, allocation: u64, proof: &[[u8; 32]]) -> Result<()> {
+        require!(
+            proof.len() <= MAX_ALLOWLIST_PROOF_LENGTH,
+            PoolError::InvalidAllowlistProof
+        );
+        let mut computed_hash =
+            hashv(&[LEAF_PREFIX, wallet.as_ref(), &allocation.to_le_bytes()]).to_bytes();
+        for node in proof.iter() {
+            computed_hash = if computed_hash <= *node {
+                hashv(&[INTERMEDIATE_PREFIX, &computed_hash, node]).to_bytes()
+            } else {
+                hashv(&[INTERMEDIATE_PREFIX, node, &computed_hash]).to_bytes()
+            };
+        }
+        require!(
+            computed_hash == self.merkle_root,
+            PoolError::InvalidAllowlistProof
+        );
+        Ok(())
+    }
+}
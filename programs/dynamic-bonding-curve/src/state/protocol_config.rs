@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use crate::{
+    constants::fee::{HOST_FEE_PERCENT, PROTOCOL_FEE_PERCENT, PROTOCOL_POOL_CREATION_FEE_PERCENT},
+    PoolError,
+};
+
+/// Protocol fee shares managed by admin. The shares are snapshotted into the pool config when the config is created
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+pub struct ProtocolConfig {
+    /// partner (fee claimer) that the shares are negotiated with, default pubkey means the shares apply to all partners
+    pub partner: Pubkey,
+    /// protocol percentage of the trading fee
+    pub protocol_fee_percent: u8,
+    /// referral percentage of the protocol fee
+    pub referral_fee_percent: u8,
+    /// protocol percentage of the pool creation fee, the remainder goes to the partner
+    pub protocol_pool_creation_fee_percent: u8,
+    pub padding_0: [u8; 5],
+    /// padding for future use
+    pub padding: [u64; 8],
+}
+
+const_assert_eq!(ProtocolConfig::INIT_SPACE, 104);
+
+impl Default for ProtocolConfig {
+    fn default() -> Self {
+        Self {
+            partner: Pubkey::default(),
+            protocol_fee_percent: PROTOCOL_FEE_PERCENT,
+            referral_fee_percent: HOST_FEE_PERCENT,
+            protocol_pool_creation_fee_percent: PROTOCOL_POOL_CREATION_FEE_PERCENT,
+            padding_0: [0; 5],
+            padding: [0; 8],
+        }
+    }
+}
+
+impl ProtocolConfig {
+    pub fn initialize(
+        &mut self,
+        partner: Pubkey,
+        protocol_fee_percent: u8,
+        referral_fee_percent: u8,
+        protocol_pool_creation_fee_percent: u8,
+    ) {
+        self.partner = partner;
+        self.update(
+            protocol_fee_percent,
+            referral_fee_percent,
+            protocol_pool_creation_fee_percent,
+        );
+    }
+
+    pub fn update(
+        &mut self,
+        protocol_fee_percent: u8,
+        referral_fee_percent: u8,
+        protocol_pool_creation_fee_percent: u8,
+    ) {
+        self.protocol_fee_percent = protocol_fee_percent;
+        self.referral_fee_percent = referral_fee_percent;
+        self.protocol_pool_creation_fee_percent = protocol_pool_creation_fee_percent;
+    }
+
+    /// Copy of the protocol config stored in the account, none if admin didn't create it
+    pub fn try_load(account_info: &AccountInfo) -> Result<Option<Self>> {
+        if account_info.data_is_empty() {
+            return Ok(None);
+        }
+        require!(
+            account_info.owner.eq(&crate::ID),
+            PoolError::InvalidProtocolConfig
+        );
+        let data = account_info.try_borrow_data()?;
+        let end = 8 + ProtocolConfig::INIT_SPACE;
+        require!(
+            data.len() >= end && data[..8].eq(ProtocolConfig::DISCRIMINATOR),
+            PoolError::InvalidProtocolConfig
+        );
+        Ok(Some(bytemuck::pod_read_unaligned(&data[8..end])))
+    }
+}
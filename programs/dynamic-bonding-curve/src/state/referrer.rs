@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use crate::safe_math::SafeMath;
+
+/// Referrer of swaps in any pool, the referral fee of a swap is sent to the referrer's token account of the fee mint, so it accrues per mint until the owner claims it
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct Referrer {
+    /// owner of the referrer, who can claim the referral fee
+    pub owner: Pubkey,
+    /// number of swaps referred
+    pub total_referred_swap: u64,
+    /// padding for future use
+    pub padding: [u64; 4],
+}
+
+const_assert_eq!(Referrer::INIT_SPACE, 72);
+
+impl Referrer {
+    pub fn initialize(&mut self, owner: Pubkey) {
+        self.owner = owner;
+    }
+
+    pub fn record_referred_swap(&mut self) -> Result<()> {
+        self.total_referred_swap = self.total_referred_swap.safe_add(1)?;
+        Ok(())
+    }
+}
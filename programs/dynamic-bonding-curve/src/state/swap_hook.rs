@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+/// Program invoked after every swap on pools created from a config
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct SwapHook {
+    /// config
+    pub config: Pubkey,
+    /// hook program, invoked after every swap with SWAP_HOOK_DISCRIMINATOR followed by borsh serialized SwapHookData
+    pub hook_program: Pubkey,
+    /// padding for future use
+    pub padding: [u64; 4],
+}
+
+const_assert_eq!(SwapHook::INIT_SPACE, 96);
+
+impl SwapHook {
+    pub fn initialize(&mut self, config: Pubkey, hook_program: Pubkey) {
+        self.config = config;
+        self.hook_program = hook_program;
+    }
+}
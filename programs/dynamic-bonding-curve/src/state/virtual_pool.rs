@@ -16,10 +16,11 @@ use crate::{
         get_next_sqrt_price_from_input, get_next_sqrt_price_from_output,
     },
     params::swap::TradeDirection,
+    price_curve::PriceCurve,
     safe_math::SafeMath,
     state::{
-        fee::{FeeMode, FeeOnAmountResult, VolatilityTracker},
-        PoolConfig,
+        fee::{FeeMode, FeeOnAmountResult, TradeFeeNumerator, VolatilityTracker},
+        PoolConfig, PoolConfigExtension,
     },
     u128x128_math::Rounding,
     utils_math::safe_mul_div_cast_u64,
@@ -45,6 +46,17 @@ pub enum CollectFeeMode {
     QuoteToken,
     /// Output token is being used for fee collection
     OutputToken,
+    /// Only quote token is being used for fee collection, partner trading fee is used to buy back base token from the curve and burn it
+    QuoteTokenBuybackBurn,
+}
+
+impl CollectFeeMode {
+    pub fn is_fee_on_quote_token_only(&self) -> bool {
+        matches!(
+            self,
+            CollectFeeMode::QuoteToken | CollectFeeMode::QuoteTokenBuybackBurn
+        )
+    }
 }
 
 #[repr(u8)]
@@ -150,14 +162,22 @@ pub struct VirtualPool {
     pub creation_fee_bits: u8,
     /// Cached flag
     pub has_swap: u8,
+    /// Creator trading fee is distributed to the recipients of the pool's creator fee share, instead of being claimed by the creator
+    pub has_creator_fee_share: u8,
+    /// Cached flag, the creator quote fee is bought back and burned instead of being claimed until the curve is complete
+    pub creator_quote_fee_buyback_flag: u8,
     /// Padding for further use
-    pub _padding_0: [u8; 5],
+    pub _padding_0: [u8; 3],
     pub protocol_liquidity_migration_fee_bps: u16,
     pub _padding_1: [u8; 6],
     pub protocol_migration_base_fee_amount: u64,
     pub protocol_migration_quote_fee_amount: u64,
-    /// Padding for further use
-    pub _padding_2: [u64; 3],
+    /// Total base token that is bought back and burned. It's kept next to the other pool totals instead of in [PoolMetrics], as PoolMetrics has no padding left to grow
+    pub total_burned_base_amount: u64,
+    /// Start of the current buyback period, in slot or timestamp depends on activation type
+    pub buyback_period_start_point: u64,
+    /// Quote fee spent by the buyback in the current buyback period
+    pub buyback_quote_amount_in_period: u64,
 }
 
 const_assert_eq!(VirtualPool::INIT_SPACE, 416);
@@ -215,6 +235,7 @@ impl VirtualPool {
         activation_point: u64,
         base_reserve: u64,
         protocol_liquidity_migration_fee_bps: u16,
+        creator_quote_fee_buyback_flag: u8,
     ) {
         self.volatility_tracker = volatility_tracker;
         self.config = config;
@@ -227,6 +248,7 @@ impl VirtualPool {
         self.activation_point = activation_point;
         self.base_reserve = base_reserve;
         self.protocol_liquidity_migration_fee_bps = protocol_liquidity_migration_fee_bps;
+        self.creator_quote_fee_buyback_flag = creator_quote_fee_buyback_flag;
     }
 
     pub fn get_swap_result_from_exact_output(
@@ -245,27 +267,24 @@ impl VirtualPool {
         let included_fee_out_amount = if fee_mode.fees_on_input {
             amount_out
         } else {
-            let trade_fee_numerator = if eligible_for_first_swap_with_min_fee {
-                config.pool_fees.get_min_base_fee_numerator()?
-            } else {
-                config
-                    .pool_fees
-                    .get_total_fee_numerator_from_excluded_fee_amount(
-                        &self.volatility_tracker,
-                        current_point,
-                        self.activation_point,
-                        amount_out,
-                        trade_direction,
-                    )?
-            };
+            let TradeFeeNumerator {
+                fee_numerator: trade_fee_numerator,
+                fee_discount_bps,
+            } = self.get_trade_fee_numerator_from_excluded_fee_amount(
+                config,
+                current_point,
+                amount_out,
+                trade_direction,
+                fee_mode,
+                eligible_for_first_swap_with_min_fee,
+            )?;
 
             let (included_fee_out_amount, fee_amount) =
                 PoolFeesConfig::get_included_fee_amount(trade_fee_numerator, amount_out)?;
 
             // that ensure included_fee_out_amount = amount_out + trading_fee + protocol_fee + referral_fee
-            let (trading_fee, protocol_fee, referral_fee) = config
-                .pool_fees
-                .split_fees(fee_amount, fee_mode.has_referral)?;
+            let (trading_fee, protocol_fee, referral_fee) =
+                config.split_fees(fee_amount, fee_mode.has_referral, fee_discount_bps)?;
 
             actual_trading_fee = trading_fee;
             actual_protocol_fee = protocol_fee;
@@ -291,27 +310,24 @@ impl VirtualPool {
         );
 
         let (excluded_fee_input_amount, included_fee_input_amount) = if fee_mode.fees_on_input {
-            let trade_fee_numerator = if eligible_for_first_swap_with_min_fee {
-                config.pool_fees.get_min_base_fee_numerator()?
-            } else {
-                config
-                    .pool_fees
-                    .get_total_fee_numerator_from_excluded_fee_amount(
-                        &self.volatility_tracker,
-                        current_point,
-                        self.activation_point,
-                        amount_in,
-                        trade_direction,
-                    )?
-            };
+            let TradeFeeNumerator {
+                fee_numerator: trade_fee_numerator,
+                fee_discount_bps,
+            } = self.get_trade_fee_numerator_from_excluded_fee_amount(
+                config,
+                current_point,
+                amount_in,
+                trade_direction,
+                fee_mode,
+                eligible_for_first_swap_with_min_fee,
+            )?;
 
             let (included_fee_in_amount, fee_amount) =
                 PoolFeesConfig::get_included_fee_amount(trade_fee_numerator, amount_in)?;
 
             // that ensure included_fee_in_amount = excluded_fee_input_amount + trading_fee + protocol_fee + referral_fee
-            let (trading_fee, protocol_fee, referral_fee) = config
-                .pool_fees
-                .split_fees(fee_amount, fee_mode.has_referral)?;
+            let (trading_fee, protocol_fee, referral_fee) =
+                config.split_fees(fee_amount, fee_mode.has_referral, fee_discount_bps)?;
 
             actual_trading_fee = trading_fee;
             actual_protocol_fee = protocol_fee;
@@ -338,6 +354,10 @@ impl VirtualPool {
         config: &PoolConfig,
         amount_out: u64,
     ) -> Result<SwapAmountFromOutput> {
+        if let Some(price_curve) = config.get_price_curve()? {
+            return self
+                .calculate_base_to_quote_from_amount_out_on_price_curve(&price_curve, amount_out);
+        }
         let mut current_sqrt_price = self.sqrt_price;
         let mut amount_left = amount_out;
         let mut total_amount_in = 0;
@@ -428,6 +448,10 @@ impl VirtualPool {
         config: &PoolConfig,
         amount_out: u64,
     ) -> Result<SwapAmountFromOutput> {
+        if let Some(price_curve) = config.get_price_curve()? {
+            return self
+                .calculate_quote_to_base_from_amount_out_on_price_curve(&price_curve, amount_out);
+        }
         let mut total_input_amount = 0u64;
         let mut amount_left = amount_out;
         let mut current_sqrt_price = self.sqrt_price;
@@ -489,21 +513,20 @@ impl VirtualPool {
         })
     }
 
-    pub fn get_swap_result_from_exact_input(
+    fn get_trade_fee_numerator_from_included_fee_amount(
         &self,
         config: &PoolConfig,
-        amount_in: u64,
-        fee_mode: &FeeMode,
-        trade_direction: TradeDirection,
         current_point: u64,
+        included_fee_amount: u64,
+        trade_direction: TradeDirection,
+        fee_mode: &FeeMode,
         eligible_for_first_swap_with_min_fee: bool,
-    ) -> Result<SwapResult2> {
-        let mut actual_protocol_fee = 0;
-        let mut actual_trading_fee = 0;
-        let mut actual_referral_fee = 0;
-
-        let trade_fee_numerator = if eligible_for_first_swap_with_min_fee {
-            config.pool_fees.get_min_base_fee_numerator()?
+    ) -> Result<TradeFeeNumerator> {
+        if eligible_for_first_swap_with_min_fee {
+            Ok(TradeFeeNumerator {
+                fee_numerator: config.pool_fees.get_min_base_fee_numerator()?,
+                fee_discount_bps: 0,
+            })
         } else {
             config
                 .pool_fees
@@ -511,21 +534,80 @@ impl VirtualPool {
                     &self.volatility_tracker,
                     current_point,
                     self.activation_point,
-                    amount_in,
+                    included_fee_amount,
                     trade_direction,
-                )?
-        };
+                    config.sqrt_start_price,
+                    self.sqrt_price,
+                    fee_mode.fee_discount_bps,
+                )
+        }
+    }
+
+    fn get_trade_fee_numerator_from_excluded_fee_amount(
+        &self,
+        config: &PoolConfig,
+        current_point: u64,
+        excluded_fee_amount: u64,
+        trade_direction: TradeDirection,
+        fee_mode: &FeeMode,
+        eligible_for_first_swap_with_min_fee: bool,
+    ) -> Result<TradeFeeNumerator> {
+        if eligible_for_first_swap_with_min_fee {
+            Ok(TradeFeeNumerator {
+                fee_numerator: config.pool_fees.get_min_base_fee_numerator()?,
+                fee_discount_bps: 0,
+            })
+        } else {
+            config
+                .pool_fees
+                .get_total_fee_numerator_from_excluded_fee_amount(
+                    &self.volatility_tracker,
+                    current_point,
+                    self.activation_point,
+                    excluded_fee_amount,
+                    trade_direction,
+                    config.sqrt_start_price,
+                    self.sqrt_price,
+                    fee_mode.fee_discount_bps,
+                )
+        }
+    }
+
+    pub fn get_swap_result_from_exact_input(
+        &self,
+        config: &PoolConfig,
+        amount_in: u64,
+        fee_mode: &FeeMode,
+        trade_direction: TradeDirection,
+        current_point: u64,
+        eligible_for_first_swap_with_min_fee: bool,
+    ) -> Result<SwapResult2> {
+        let mut actual_protocol_fee = 0;
+        let mut actual_trading_fee = 0;
+        let mut actual_referral_fee = 0;
 
         let actual_amount_in = if fee_mode.fees_on_input {
+            let TradeFeeNumerator {
+                fee_numerator: trade_fee_numerator,
+                fee_discount_bps,
+            } = self.get_trade_fee_numerator_from_included_fee_amount(
+                config,
+                current_point,
+                amount_in,
+                trade_direction,
+                fee_mode,
+                eligible_for_first_swap_with_min_fee,
+            )?;
             let FeeOnAmountResult {
                 amount,
                 protocol_fee,
                 trading_fee,
                 referral_fee,
-            } = config.pool_fees.get_fee_on_amount(
+            } = config.get_fee_on_amount(
                 trade_fee_numerator,
                 amount_in,
                 fee_mode.has_referral,
+                fee_discount_bps,
             )?;
 
             actual_protocol_fee = protocol_fee;
@@ -542,9 +624,11 @@ impl VirtualPool {
             next_sqrt_price,
             amount_left,
         } = match trade_direction {
-            TradeDirection::BaseToQuote => {
-                self.calculate_base_to_quote_from_amount_in(config, actual_amount_in)?
-            }
+            TradeDirection::BaseToQuote => self.calculate_base_to_quote_from_amount_in(
+                config,
+                actual_amount_in,
+                config.sqrt_start_price,
+            )?,
             TradeDirection::QuoteToBase => self.calculate_quote_to_base_from_amount_in(
                 config,
                 actual_amount_in,
@@ -557,15 +641,28 @@ impl VirtualPool {
         let actual_amount_out = if fee_mode.fees_on_input {
             output_amount
         } else {
+            // fee on output is charged on the output amount, so a sell is rate limited on its quote output
+            let TradeFeeNumerator {
+                fee_numerator: trade_fee_numerator,
+                fee_discount_bps,
+            } = self.get_trade_fee_numerator_from_included_fee_amount(
+                config,
+                current_point,
+                output_amount,
+                trade_direction,
+                fee_mode,
+                eligible_for_first_swap_with_min_fee,
+            )?;
             let FeeOnAmountResult {
                 amount,
                 protocol_fee,
                 trading_fee,
                 referral_fee,
-            } = config.pool_fees.get_fee_on_amount(
+            } = config.get_fee_on_amount(
                 trade_fee_numerator,
                 output_amount,
                 fee_mode.has_referral,
+                fee_discount_bps,
             )?;
 
             actual_trading_fee = trading_fee;
@@ -595,35 +692,37 @@ impl VirtualPool {
         trade_direction: TradeDirection,
         current_point: u64,
         eligible_for_first_swap_with_min_fee: bool,
+        sqrt_price_limit: Option<u128>,
     ) -> Result<SwapResult2> {
+        let stop_sqrt_price =
+            self.get_stop_sqrt_price(config, trade_direction, sqrt_price_limit)?;
+
         let mut actual_protocol_fee = 0;
         let mut actual_trading_fee = 0;
         let mut actual_referral_fee = 0;
 
-        let trade_fee_numerator = if eligible_for_first_swap_with_min_fee {
-            config.pool_fees.get_min_base_fee_numerator()?
-        } else {
-            config
-                .pool_fees
-                .get_total_fee_numerator_from_included_fee_amount(
-                    &self.volatility_tracker,
-                    current_point,
-                    self.activation_point,
-                    amount_in,
-                    trade_direction,
-                )?
-        };
-
         let mut actual_amount_in = if fee_mode.fees_on_input {
+            let TradeFeeNumerator {
+                fee_numerator: trade_fee_numerator,
+                fee_discount_bps,
+            } = self.get_trade_fee_numerator_from_included_fee_amount(
+                config,
+                current_point,
+                amount_in,
+                trade_direction,
+                fee_mode,
+                eligible_for_first_swap_with_min_fee,
+            )?;
             let FeeOnAmountResult {
                 amount,
                 protocol_fee,
                 trading_fee,
                 referral_fee,
-            } = config.pool_fees.get_fee_on_amount(
+            } = config.get_fee_on_amount(
                 trade_fee_numerator,
                 amount_in,
                 fee_mode.has_referral,
+                fee_discount_bps,
             )?;
 
             actual_protocol_fee = protocol_fee;
@@ -640,13 +739,15 @@ impl VirtualPool {
             next_sqrt_price,
             amount_left,
         } = match trade_direction {
-            TradeDirection::BaseToQuote => {
-                self.calculate_base_to_quote_from_amount_in(config, actual_amount_in)?
-            }
+            TradeDirection::BaseToQuote => self.calculate_base_to_quote_from_amount_in(
+                config,
+                actual_amount_in,
+                stop_sqrt_price,
+            )?,
             TradeDirection::QuoteToBase => self.calculate_quote_to_base_from_amount_in(
                 config,
                 actual_amount_in,
-                config.migration_sqrt_price,
+                stop_sqrt_price,
             )?,
         };
 
@@ -654,27 +755,24 @@ impl VirtualPool {
             actual_amount_in = actual_amount_in.safe_sub(amount_left)?;
             // recalculate included_fee_input_amount actual_trading_fee, actual_protocol_fee, actual_referral_fee
             if fee_mode.fees_on_input {
-                let trade_fee_numerator = if eligible_for_first_swap_with_min_fee {
-                    config.pool_fees.get_min_base_fee_numerator()?
-                } else {
-                    config
-                        .pool_fees
-                        .get_total_fee_numerator_from_excluded_fee_amount(
-                            &self.volatility_tracker,
-                            current_point,
-                            self.activation_point,
-                            actual_amount_in,
-                            trade_direction,
-                        )?
-                };
+                let TradeFeeNumerator {
+                    fee_numerator: trade_fee_numerator,
+                    fee_discount_bps,
+                } = self.get_trade_fee_numerator_from_excluded_fee_amount(
+                    config,
+                    current_point,
+                    actual_amount_in,
+                    trade_direction,
+                    fee_mode,
+                    eligible_for_first_swap_with_min_fee,
+                )?;
 
                 let (included_fee_input_amount, fee_amount) =
                     PoolFeesConfig::get_included_fee_amount(trade_fee_numerator, actual_amount_in)?;
 
                 // that ensure included_fee_input_amount = actual_amount_in + trading_fee + protocol_fee + referral_fee
-                let (trading_fee, protocol_fee, referral_fee) = config
-                    .pool_fees
-                    .split_fees(fee_amount, fee_mode.has_referral)?;
+                let (trading_fee, protocol_fee, referral_fee) =
+                    config.split_fees(fee_amount, fee_mode.has_referral, fee_discount_bps)?;
 
                 actual_trading_fee = trading_fee;
                 actual_protocol_fee = protocol_fee;
@@ -691,15 +789,28 @@ impl VirtualPool {
         let actual_amount_out = if fee_mode.fees_on_input {
             output_amount
         } else {
+            // fee on output is charged on the output amount, so a sell is rate limited on its quote output
+            let TradeFeeNumerator {
+                fee_numerator: trade_fee_numerator,
+                fee_discount_bps,
+            } = self.get_trade_fee_numerator_from_included_fee_amount(
+                config,
+                current_point,
+                output_amount,
+                trade_direction,
+                fee_mode,
+                eligible_for_first_swap_with_min_fee,
+            )?;
             let FeeOnAmountResult {
                 amount,
                 protocol_fee,
                 trading_fee,
                 referral_fee,
-            } = config.pool_fees.get_fee_on_amount(
+            } = config.get_fee_on_amount(
                 trade_fee_numerator,
                 output_amount,
                 fee_mode.has_referral,
+                fee_discount_bps,
             )?;
 
             actual_protocol_fee = protocol_fee;
@@ -721,11 +832,51 @@ impl VirtualPool {
         })
     }
 
+    /// Returns the price at which a partial swap must stop. Without a limit, it is the end of the curve in the trade direction
+    pub fn get_stop_sqrt_price(
+        &self,
+        config: &PoolConfig,
+        trade_direction: TradeDirection,
+        sqrt_price_limit: Option<u128>,
+    ) -> Result<u128> {
+        let stop_sqrt_price = match trade_direction {
+            TradeDirection::BaseToQuote => {
+                let Some(sqrt_price_limit) = sqrt_price_limit else {
+                    return Ok(config.sqrt_start_price);
+                };
+                require!(
+                    sqrt_price_limit < self.sqrt_price,
+                    PoolError::InvalidSqrtPriceLimit
+                );
+                sqrt_price_limit.max(config.sqrt_start_price)
+            }
+            TradeDirection::QuoteToBase => {
+                let Some(sqrt_price_limit) = sqrt_price_limit else {
+                    return Ok(config.migration_sqrt_price);
+                };
+                require!(
+                    sqrt_price_limit > self.sqrt_price,
+                    PoolError::InvalidSqrtPriceLimit
+                );
+                sqrt_price_limit.min(config.migration_sqrt_price)
+            }
+        };
+        Ok(stop_sqrt_price)
+    }
+
     fn calculate_base_to_quote_from_amount_in(
         &self,
         config: &PoolConfig,
         amount_in: u64,
+        stop_sqrt_price: u128, // will be sqrt_start_price if there is no price limit
     ) -> Result<SwapAmountFromInput> {
+        if let Some(price_curve) = config.get_price_curve()? {
+            return self.calculate_base_to_quote_from_amount_in_on_price_curve(
+                &price_curve,
+                amount_in,
+                stop_sqrt_price,
+            );
+        }
         // finding new target price
         let mut total_output_amount = 0u64;
         let mut current_sqrt_price = self.sqrt_price;
@@ -735,9 +886,10 @@ impl VirtualPool {
             if config.curve[i].sqrt_price == 0 || config.curve[i].liquidity == 0 {
                 continue;
             }
-            if config.curve[i].sqrt_price < current_sqrt_price {
+            let reference_sqrt_price = stop_sqrt_price.max(config.curve[i].sqrt_price);
+            if reference_sqrt_price < current_sqrt_price {
                 let max_amount_in = get_delta_amount_base_unsigned_256(
-                    config.curve[i].sqrt_price,
+                    reference_sqrt_price,
                     current_sqrt_price,
                     config.curve[i + 1].liquidity,
                     Rounding::Up,
@@ -761,7 +913,7 @@ impl VirtualPool {
                     amount_left = 0;
                     break;
                 } else {
-                    let next_sqrt_price = config.curve[i].sqrt_price;
+                    let next_sqrt_price = reference_sqrt_price;
                     let output_amount = get_delta_amount_quote_unsigned(
                         next_sqrt_price,
                         current_sqrt_price,
@@ -775,10 +927,13 @@ impl VirtualPool {
                             .try_into()
                             .map_err(|_| PoolError::TypeCastFailed)?,
                     )?;
+                    if next_sqrt_price == stop_sqrt_price {
+                        break;
+                    }
                 }
             }
         }
-        if amount_left != 0 {
+        if amount_left != 0 && current_sqrt_price > stop_sqrt_price {
             let mut next_sqrt_price = get_next_sqrt_price_from_input(
                 current_sqrt_price,
                 config.curve[0].liquidity,
@@ -786,8 +941,8 @@ impl VirtualPool {
                 true,
             )?;
 
-            if next_sqrt_price < config.sqrt_start_price {
-                next_sqrt_price = config.sqrt_start_price;
+            if next_sqrt_price < stop_sqrt_price {
+                next_sqrt_price = stop_sqrt_price;
                 let amount_in = get_delta_amount_base_unsigned(
                     next_sqrt_price,
                     current_sqrt_price,
@@ -820,8 +975,15 @@ impl VirtualPool {
         &self,
         config: &PoolConfig,
         amount_in: u64,
-        stop_sqrt_price: u128, // will be migration_sqrt_price in partial fill
+        stop_sqrt_price: u128, // will be migration_sqrt_price if there is no price limit
     ) -> Result<SwapAmountFromInput> {
+        if let Some(price_curve) = config.get_price_curve()? {
+            return self.calculate_quote_to_base_from_amount_in_on_price_curve(
+                &price_curve,
+                amount_in,
+                stop_sqrt_price,
+            );
+        }
         // finding new target price
         let mut total_output_amount = 0u64;
         let mut current_sqrt_price = self.sqrt_price;
@@ -874,7 +1036,7 @@ impl VirtualPool {
                     )?;
                     if next_sqrt_price == stop_sqrt_price {
                         #[cfg(feature = "local")]
-                        {
+                        if stop_sqrt_price == config.migration_sqrt_price {
                             let amount_consumed = amount_in.safe_sub(amount_left)?;
                             require!(
                                 self.quote_reserve.safe_add(amount_consumed)?
@@ -896,6 +1058,140 @@ impl VirtualPool {
         })
     }
 
+    // The price curves have a single segment from sqrt_start_price to end_sqrt_price
+    fn calculate_base_to_quote_from_amount_out_on_price_curve(
+        &self,
+        price_curve: &PriceCurve,
+        amount_out: u64,
+    ) -> Result<SwapAmountFromOutput> {
+        let max_amount_out = price_curve.get_delta_amount_quote(
+            price_curve.sqrt_start_price,
+            self.sqrt_price,
+            Rounding::Down,
+        )?;
+        require!(
+            U256::from(amount_out) <= max_amount_out,
+            PoolError::InsufficientLiquidity
+        );
+        let next_sqrt_price =
+            price_curve.get_next_sqrt_price_from_output(self.sqrt_price, amount_out, true)?;
+        // redundant check
+        require!(
+            next_sqrt_price >= price_curve.sqrt_start_price,
+            PoolError::InsufficientLiquidity
+        );
+        let amount_in =
+            price_curve.get_delta_amount_base(next_sqrt_price, self.sqrt_price, Rounding::Up)?;
+
+        Ok(SwapAmountFromOutput {
+            amount_in: amount_in
+                .try_into()
+                .map_err(|_| PoolError::TypeCastFailed)?,
+            next_sqrt_price,
+        })
+    }
+
+    fn calculate_quote_to_base_from_amount_out_on_price_curve(
+        &self,
+        price_curve: &PriceCurve,
+        amount_out: u64,
+    ) -> Result<SwapAmountFromOutput> {
+        let max_amount_out = price_curve.get_delta_amount_base(
+            self.sqrt_price,
+            price_curve.end_sqrt_price,
+            Rounding::Down,
+        )?;
+        require!(
+            U256::from(amount_out) <= max_amount_out,
+            PoolError::AmountLeftIsNotZero
+        );
+        let next_sqrt_price =
+            price_curve.get_next_sqrt_price_from_output(self.sqrt_price, amount_out, false)?;
+        let amount_in =
+            price_curve.get_delta_amount_quote(self.sqrt_price, next_sqrt_price, Rounding::Up)?;
+
+        Ok(SwapAmountFromOutput {
+            amount_in: amount_in
+                .try_into()
+                .map_err(|_| PoolError::TypeCastFailed)?,
+            next_sqrt_price,
+        })
+    }
+
+    fn calculate_base_to_quote_from_amount_in_on_price_curve(
+        &self,
+        price_curve: &PriceCurve,
+        amount_in: u64,
+        stop_sqrt_price: u128,
+    ) -> Result<SwapAmountFromInput> {
+        if stop_sqrt_price >= self.sqrt_price {
+            return Ok(SwapAmountFromInput {
+                amount_left: amount_in,
+                output_amount: 0,
+                next_sqrt_price: self.sqrt_price,
+            });
+        }
+        let max_amount_in =
+            price_curve.get_delta_amount_base(stop_sqrt_price, self.sqrt_price, Rounding::Up)?;
+        let (next_sqrt_price, amount_left) = if U256::from(amount_in) < max_amount_in {
+            let next_sqrt_price =
+                price_curve.get_next_sqrt_price_from_input(self.sqrt_price, amount_in, true)?;
+            (next_sqrt_price.max(stop_sqrt_price), 0)
+        } else {
+            let max_amount_in: u64 = max_amount_in
+                .try_into()
+                .map_err(|_| PoolError::TypeCastFailed)?;
+            (stop_sqrt_price, amount_in.safe_sub(max_amount_in)?)
+        };
+        let output_amount =
+            price_curve.get_delta_amount_quote(next_sqrt_price, self.sqrt_price, Rounding::Down)?;
+
+        Ok(SwapAmountFromInput {
+            amount_left,
+            output_amount: output_amount
+                .try_into()
+                .map_err(|_| PoolError::TypeCastFailed)?,
+            next_sqrt_price,
+        })
+    }
+
+    fn calculate_quote_to_base_from_amount_in_on_price_curve(
+        &self,
+        price_curve: &PriceCurve,
+        amount_in: u64,
+        stop_sqrt_price: u128,
+    ) -> Result<SwapAmountFromInput> {
+        if stop_sqrt_price <= self.sqrt_price {
+            return Ok(SwapAmountFromInput {
+                amount_left: amount_in,
+                output_amount: 0,
+                next_sqrt_price: self.sqrt_price,
+            });
+        }
+        let max_amount_in =
+            price_curve.get_delta_amount_quote(self.sqrt_price, stop_sqrt_price, Rounding::Up)?;
+        let (next_sqrt_price, amount_left) = if U256::from(amount_in) < max_amount_in {
+            let next_sqrt_price =
+                price_curve.get_next_sqrt_price_from_input(self.sqrt_price, amount_in, false)?;
+            (next_sqrt_price.min(stop_sqrt_price), 0)
+        } else {
+            let max_amount_in: u64 = max_amount_in
+                .try_into()
+                .map_err(|_| PoolError::TypeCastFailed)?;
+            (stop_sqrt_price, amount_in.safe_sub(max_amount_in)?)
+        };
+        let output_amount =
+            price_curve.get_delta_amount_base(self.sqrt_price, next_sqrt_price, Rounding::Down)?;
+
+        Ok(SwapAmountFromInput {
+            amount_left,
+            output_amount: output_amount
+                .try_into()
+                .map_err(|_| PoolError::TypeCastFailed)?,
+            next_sqrt_price,
+        })
+    }
+
     pub fn apply_swap_result(
         &mut self,
         config: &PoolConfig,
@@ -958,6 +1254,139 @@ impl VirtualPool {
         Ok(())
     }
 
+    /// Unclaimed quote fee that is reserved for the buyback, from the partner and/or creator share depends on the buyback fee source
+    pub fn get_buyback_quote_fee(&self, config: &PoolConfig) -> Result<u64> {
+        let mut buyback_quote_fee: u64 = 0;
+        if config.is_partner_quote_fee_bought_back() {
+            buyback_quote_fee = buyback_quote_fee.safe_add(self.partner_quote_fee)?;
+        }
+        if config.is_creator_quote_fee_bought_back() {
+            buyback_quote_fee = buyback_quote_fee.safe_add(self.creator_quote_fee)?;
+        }
+        Ok(buyback_quote_fee)
+    }
+
+    /// A new buyback period starts once buyback_period has elapsed since the start of the last one
+    fn is_new_buyback_period(
+        &self,
+        config_extension: &PoolConfigExtension,
+        current_point: u64,
+    ) -> Result<bool> {
+        let period_end_point = self
+            .buyback_period_start_point
+            .safe_add(config_extension.buyback_period)?;
+        Ok(current_point >= period_end_point)
+    }
+
+    /// Quote fee the buyback can still spend in the buyback period of current_point
+    pub fn get_buyback_quote_amount_left_in_period(
+        &self,
+        config_extension: &PoolConfigExtension,
+        current_point: u64,
+    ) -> Result<u64> {
+        if self.is_new_buyback_period(config_extension, current_point)? {
+            Ok(config_extension.buyback_max_quote_amount_per_period)
+        } else {
+            Ok(config_extension
+                .buyback_max_quote_amount_per_period
+                .saturating_sub(self.buyback_quote_amount_in_period))
+        }
+    }
+
+    /// Buy base token from the curve with the reserved quote fee up to max_quote_amount and the amount left in the buyback period, without any fee.
+    /// The buyback stops at the price limit or the migration price, the unused quote fee is kept for the next buyback
+    pub fn get_buyback_result(
+        &self,
+        config: &PoolConfig,
+        config_extension: &PoolConfigExtension,
+        max_quote_amount: u64,
+        sqrt_price_limit: Option<u128>,
+        current_point: u64,
+    ) -> Result<BuybackResult> {
+        let quote_amount = self
+            .get_buyback_quote_fee(config)?
+            .min(max_quote_amount)
+            .min(self.get_buyback_quote_amount_left_in_period(config_extension, current_point)?);
+        let stop_sqrt_price =
+            self.get_stop_sqrt_price(config, TradeDirection::QuoteToBase, sqrt_price_limit)?;
+        let SwapAmountFromInput {
+            output_amount,
+            next_sqrt_price,
+            amount_left,
+        } = self.calculate_quote_to_base_from_amount_in(config, quote_amount, stop_sqrt_price)?;
+
+        Ok(BuybackResult {
+            quote_amount: quote_amount.safe_sub(amount_left)?,
+            base_amount: output_amount,
+            next_sqrt_price,
+        })
+    }
+
+    pub fn apply_buyback_result(
+        &mut self,
+        config: &PoolConfig,
+        config_extension: &PoolConfigExtension,
+        buyback_result: &BuybackResult,
+        current_point: u64,
+        current_timestamp: u64,
+    ) -> Result<()> {
+        let &BuybackResult {
+            quote_amount,
+            base_amount,
+            next_sqrt_price,
+        } = buyback_result;
+
+        let old_sqrt_price = self.sqrt_price;
+        self.sqrt_price = next_sqrt_price;
+
+        // the partner quote fee is spent first, then the creator quote fee
+        let partner_quote_amount = if config.is_partner_quote_fee_bought_back() {
+            self.partner_quote_fee.min(quote_amount)
+        } else {
+            0
+        };
+        let creator_quote_amount = quote_amount.safe_sub(partner_quote_amount)?;
+        require!(
+            creator_quote_amount == 0 || config.is_creator_quote_fee_bought_back(),
+            PoolError::UndeterminedError
+        );
+
+        // the quote fee is already in the quote vault, so it only moves from the partner and creator fee to the reserve
+        self.partner_quote_fee = self.partner_quote_fee.safe_sub(partner_quote_amount)?;
+        self.creator_quote_fee = self.creator_quote_fee.safe_sub(creator_quote_amount)?;
+        self.quote_reserve = self.quote_reserve.safe_add(quote_amount)?;
+        self.base_reserve = self.base_reserve.safe_sub(base_amount)?;
+        self.total_burned_base_amount = self.total_burned_base_amount.safe_add(base_amount)?;
+
+        if self.is_new_buyback_period(config_extension, current_point)? {
+            self.buyback_period_start_point = current_point;
+            self.buyback_quote_amount_in_period = 0;
+        }
+        self.buyback_quote_amount_in_period =
+            self.buyback_quote_amount_in_period.safe_add(quote_amount)?;
+
+        self.update_post_swap(config, old_sqrt_price, current_timestamp)
+    }
+
+    /// Whether the volatility accumulator at the current price, after the references decay until current_timestamp, is over the circuit breaker threshold
+    pub fn is_circuit_breaker_triggered(
+        &self,
+        config: &PoolConfig,
+        current_timestamp: u64,
+    ) -> Result<bool> {
+        let dynamic_fee = &config.pool_fees.dynamic_fee;
+        if !dynamic_fee.is_circuit_breaker_enable() {
+            return Ok(false);
+        }
+
+        let mut volatility_tracker = self.volatility_tracker;
+        volatility_tracker.update_references(dynamic_fee, self.sqrt_price, current_timestamp)?;
+        volatility_tracker.update_volatility_accumulator(dynamic_fee, self.sqrt_price)?;
+
+        Ok(volatility_tracker.volatility_accumulator
+            > dynamic_fee.circuit_breaker_volatility_accumulator.into())
+    }
+
     pub fn update_pre_swap(&mut self, config: &PoolConfig, current_timestamp: u64) -> Result<()> {
         if config.pool_fees.dynamic_fee.is_dynamic_fee_enable() {
             self.volatility_tracker.update_references(
@@ -1069,6 +1498,16 @@ impl VirtualPool {
         Ok((token_base_amount, token_quote_amount))
     }
 
+    pub fn is_creator_fee_share_enabled(&self) -> bool {
+        self.has_creator_fee_share == 1
+    }
+
+    /// The creator quote fee is reserved for the buyback until the curve is complete. The config isn't an account of the creator fee claims, so the flag is cached in the pool
+    pub fn is_creator_quote_fee_reserved_for_buyback(&self) -> Result<bool> {
+        Ok(self.creator_quote_fee_buyback_flag == 1
+            && self.get_migration_progress()? == MigrationProgress::PreBondingCurve)
+    }
+
     pub fn get_protocol_and_trading_base_fee(&self) -> Result<u64> {
         Ok(self
             .partner_base_fee
@@ -1264,3 +1703,12 @@ pub struct SwapAmountFromInput {
     output_amount: u64,
     next_sqrt_price: u128,
 }
+
+#[derive(Debug, PartialEq)]
+pub struct BuybackResult {
+    /// partner and creator quote fee spent on the curve
+    pub quote_amount: u64,
+    /// base token bought back, to be burned
+    pub base_amount: u64,
+    pub next_sqrt_price: u128,
+}
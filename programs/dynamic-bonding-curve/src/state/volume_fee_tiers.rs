@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use crate::constants::MAX_VOLUME_FEE_TIERS;
+
+#[zero_copy]
+#[derive(InitSpace, Debug, Default)]
+pub struct VolumeFeeTier {
+    /// min cumulative quote volume of the wallet in the config to reach the tier
+    pub min_volume: u64,
+    /// discount on the total fee, in bps
+    pub fee_discount_bps: u16,
+    pub padding: [u8; 6],
+}
+
+const_assert_eq!(VolumeFeeTier::INIT_SPACE, 16);
+
+/// Fee discounts for wallets with high cumulative volume in the pools of a config
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct VolumeFeeTiers {
+    /// config
+    pub config: Pubkey,
+    /// tiers sorted by min volume, unused tiers are zero
+    pub tiers: [VolumeFeeTier; MAX_VOLUME_FEE_TIERS],
+    /// padding for future use
+    pub padding: [u64; 4],
+}
+
+const_assert_eq!(VolumeFeeTiers::INIT_SPACE, 128);
+
+impl VolumeFeeTiers {
+    pub fn initialize(&mut self, config: Pubkey, tiers: [VolumeFeeTier; MAX_VOLUME_FEE_TIERS]) {
+        self.config = config;
+        self.tiers = tiers;
+    }
+
+    pub fn update(&mut self, tiers: [VolumeFeeTier; MAX_VOLUME_FEE_TIERS]) {
+        self.tiers = tiers;
+    }
+
+    /// Discount of the highest tier that the wallet volume reached
+    pub fn get_fee_discount_bps(&self, wallet_volume: u64) -> u16 {
+        self.tiers
+            .iter()
+            .take_while(|tier| tier.fee_discount_bps != 0)
+            .filter(|tier| wallet_volume >= tier.min_volume)
+            .last()
+            .map(|tier| tier.fee_discount_bps)
+            .unwrap_or(0)
+    }
+}
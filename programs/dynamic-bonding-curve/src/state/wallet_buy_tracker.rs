@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use crate::{safe_math::SafeMath, PoolError};
+
+/// Track the quote amount that a wallet spent to buy base token in a pool during the buy cap window
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct WalletBuyTracker {
+    /// pool
+    pub pool: Pubkey,
+    /// owner of the tracker, the wallet that pays for the swap
+    pub owner: Pubkey,
+    /// total quote amount (included fee) spent in the buy cap window
+    pub total_buy_amount: u64,
+    /// total quote amount (included fee) spent in the allowlist phase
+    pub allowlist_buy_amount: u64,
+    /// padding for future use
+    pub padding: [u64; 2],
+}
+
+const_assert_eq!(WalletBuyTracker::INIT_SPACE, 96);
+
+impl WalletBuyTracker {
+    pub fn initialize(&mut self, pool: Pubkey, owner: Pubkey) {
+        self.pool = pool;
+        self.owner = owner;
+    }
+
+    pub fn accumulate_buy_amount(&mut self, amount: u64, max_buy_amount: u64) -> Result<()> {
+        self.total_buy_amount = self.total_buy_amount.safe_add(amount)?;
+        require!(
+            self.total_buy_amount <= max_buy_amount,
+            PoolError::ExceededWalletBuyCap
+        );
+        Ok(())
+    }
+
+    pub fn accumulate_allowlist_buy_amount(&mut self, amount: u64, allocation: u64) -> Result<()> {
+        self.allowlist_buy_amount = self.allowlist_buy_amount.safe_add(amount)?;
+        require!(
+            self.allowlist_buy_amount <= allocation,
+            PoolError::ExceededAllowlistAllocation
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use crate::safe_math::SafeMath;
+
+/// Track the cumulative quote volume of a wallet in the pools of a config, used to get the wallet fee tier
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct WalletVolumeTracker {
+    /// config
+    pub config: Pubkey,
+    /// owner of the tracker, the wallet that pays for the swap
+    pub owner: Pubkey,
+    /// cumulative quote volume, input amount (included fee) of buys and output amount of sells
+    pub total_volume: u64,
+    /// padding for future use
+    pub padding: [u64; 3],
+}
+
+const_assert_eq!(WalletVolumeTracker::INIT_SPACE, 96);
+
+impl WalletVolumeTracker {
+    pub fn initialize(&mut self, config: Pubkey, owner: Pubkey) {
+        self.config = config;
+        self.owner = owner;
+    }
+
+    pub fn accumulate_volume(&mut self, volume: u64) -> Result<()> {
+        self.total_volume = self.total_volume.safe_add(volume)?;
+        Ok(())
+    }
+}
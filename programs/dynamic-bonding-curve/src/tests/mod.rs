@@ -39,3 +39,51 @@ mod test_operator_permission;
 
 #[cfg(test)]
 mod test_safe_math;
+
+#[cfg(test)]
+mod test_wallet_buy_cap;
+
+#[cfg(test)]
+mod test_presale_allowlist;
+
+#[cfg(test)]
+mod test_swap_hook;
+
+#[cfg(test)]
+mod test_twap;
+
+#[cfg(test)]
+mod test_sell_base_fee;
+
+#[cfg(test)]
+mod test_fee_market_cap_scheduler;
+
+#[cfg(test)]
+mod test_protocol_config;
+
+#[cfg(test)]
+mod test_referrer;
+
+#[cfg(test)]
+mod test_volume_fee_tiers;
+
+#[cfg(test)]
+mod test_buyback_burn;
+
+#[cfg(test)]
+mod test_creator_fee_share;
+
+#[cfg(test)]
+mod test_circuit_breaker;
+
+#[cfg(test)]
+mod test_fee_step_table;
+
+#[cfg(test)]
+mod test_price_curve;
+
+#[cfg(test)]
+mod test_close_config;
+
+#[cfg(test)]
+mod test_creator_allowlist;
@@ -0,0 +1,310 @@
+use crate::{
+    constants::{MAX_CURVE_POINT, MAX_SQRT_PRICE},
+    params::{
+        liquidity_distribution::{get_migration_threshold_price, LiquidityDistributionParameters},
+        swap::TradeDirection,
+    },
+    state::{
+        fee::{FeeMode, VolatilityTracker},
+        BuybackFeeSource, CollectFeeMode, LiquidityDistributionConfig, MigrationProgress,
+        PoolConfig, PoolConfigExtension, VirtualPool,
+    },
+};
+use anchor_lang::prelude::Pubkey;
+
+use super::price_math::get_price_from_id;
+
+const BUYBACK_PERIOD: u64 = 100;
+
+fn initialize_pool_and_config(
+    buyback_fee_source: BuybackFeeSource,
+    buyback_max_quote_amount_per_period: u64,
+) -> (PoolConfig, PoolConfigExtension, VirtualPool) {
+    let migration_quote_threshold = 50_000_000_000; // 50k usdc
+    let sqrt_start_price: u128 = get_price_from_id(-100, 80).unwrap();
+    let curve = vec![LiquidityDistributionParameters {
+        sqrt_price: MAX_SQRT_PRICE,
+        liquidity: 1_000_000_000_000_000_000_000_000u128
+            .checked_shl(64)
+            .unwrap(),
+    }];
+
+    let migration_sqrt_price =
+        get_migration_threshold_price(migration_quote_threshold, sqrt_start_price, &curve).unwrap();
+    let mut config = PoolConfig {
+        migration_quote_threshold,
+        sqrt_start_price,
+        migration_sqrt_price,
+        collect_fee_mode: CollectFeeMode::QuoteTokenBuybackBurn.into(),
+        buyback_fee_source: buyback_fee_source.into(),
+        ..Default::default()
+    };
+    for i in 0..MAX_CURVE_POINT {
+        if i < curve.len() {
+            config.curve[i] = curve[i].to_liquidity_distribution_config();
+        } else {
+            config.curve[i] = LiquidityDistributionConfig {
+                sqrt_price: MAX_SQRT_PRICE,
+                liquidity: 0,
+            }
+        }
+    }
+
+    let mut pool = VirtualPool::default();
+    pool.initialize(
+        VolatilityTracker::default(),
+        Pubkey::default(),
+        Pubkey::default(),
+        Pubkey::default(),
+        Pubkey::default(),
+        Pubkey::default(),
+        config.sqrt_start_price,
+        0,
+        0,
+        1_000_000_000_000,
+        0,
+        config.is_creator_quote_fee_bought_back().into(),
+    );
+
+    let mut config_extension = PoolConfigExtension::default();
+    config_extension.initialize(
+        Pubkey::default(),
+        Default::default(),
+        Default::default(),
+        0,
+        buyback_max_quote_amount_per_period,
+        BUYBACK_PERIOD,
+    );
+    (config, config_extension, pool)
+}
+
+#[test]
+fn test_buyback_matches_swap_without_fee() {
+    let (config, config_extension, mut pool) =
+        initialize_pool_and_config(BuybackFeeSource::Partner, u64::MAX);
+    assert!(config.is_buyback_burn_enabled());
+
+    let partner_quote_fee = 1_000_000_000;
+    pool.partner_quote_fee = partner_quote_fee;
+
+    let swap_result = pool
+        .get_swap_result_from_exact_input(
+            &config,
+            partner_quote_fee,
+            &FeeMode::default(),
+            TradeDirection::QuoteToBase,
+            0,
+            false,
+        )
+        .unwrap();
+
+    let buyback_result = pool
+        .get_buyback_result(&config, &config_extension, u64::MAX, None, 0)
+        .unwrap();
+    assert_eq!(buyback_result.quote_amount, partner_quote_fee);
+    assert_eq!(buyback_result.base_amount, swap_result.output_amount);
+    assert_eq!(buyback_result.next_sqrt_price, swap_result.next_sqrt_price);
+
+    let base_reserve = pool.base_reserve;
+    pool.apply_buyback_result(&config, &config_extension, &buyback_result, 0, 0)
+        .unwrap();
+    assert_eq!(pool.partner_quote_fee, 0);
+    assert_eq!(pool.quote_reserve, partner_quote_fee);
+    assert_eq!(pool.base_reserve, base_reserve - buyback_result.base_amount);
+    assert_eq!(pool.sqrt_price, buyback_result.next_sqrt_price);
+}
+
+#[test]
+fn test_buyback_stops_at_migration_price() {
+    let (config, config_extension, mut pool) =
+        initialize_pool_and_config(BuybackFeeSource::Partner, u64::MAX);
+
+    let partner_quote_fee = config.migration_quote_threshold * 2;
+    pool.partner_quote_fee = partner_quote_fee;
+
+    let buyback_result = pool
+        .get_buyback_result(&config, &config_extension, u64::MAX, None, 0)
+        .unwrap();
+    assert!(buyback_result.quote_amount < partner_quote_fee);
+    assert_eq!(buyback_result.next_sqrt_price, config.migration_sqrt_price);
+
+    pool.apply_buyback_result(&config, &config_extension, &buyback_result, 0, 0)
+        .unwrap();
+    assert!(pool.is_curve_complete(config.migration_quote_threshold));
+    // the unused quote fee is kept, and can be claimed by the partner after the curve is complete
+    assert_eq!(
+        pool.partner_quote_fee,
+        partner_quote_fee - buyback_result.quote_amount
+    );
+}
+
+#[test]
+fn test_buyback_without_partner_fee() {
+    let (config, config_extension, pool) =
+        initialize_pool_and_config(BuybackFeeSource::Partner, u64::MAX);
+
+    let buyback_result = pool
+        .get_buyback_result(&config, &config_extension, u64::MAX, None, 0)
+        .unwrap();
+    assert_eq!(buyback_result.quote_amount, 0);
+    assert_eq!(buyback_result.base_amount, 0);
+}
+
+#[test]
+fn test_buyback_with_quote_cap_and_price_limit() {
+    let (config, config_extension, mut pool) =
+        initialize_pool_and_config(BuybackFeeSource::Partner, u64::MAX);
+
+    let partner_quote_fee = 1_000_000_000;
+    pool.partner_quote_fee = partner_quote_fee;
+
+    // only the capped quote fee is spent
+    let buyback_result = pool
+        .get_buyback_result(&config, &config_extension, partner_quote_fee / 4, None, 0)
+        .unwrap();
+    assert_eq!(buyback_result.quote_amount, partner_quote_fee / 4);
+    pool.apply_buyback_result(&config, &config_extension, &buyback_result, 0, 0)
+        .unwrap();
+    assert_eq!(pool.total_burned_base_amount, buyback_result.base_amount);
+    let total_burned_base_amount = pool.total_burned_base_amount;
+
+    // the buyback stops at the price limit
+    let sqrt_price_limit = pool.sqrt_price + 1;
+    let buyback_result = pool
+        .get_buyback_result(
+            &config,
+            &config_extension,
+            u64::MAX,
+            Some(sqrt_price_limit),
+            0,
+        )
+        .unwrap();
+    assert!(buyback_result.quote_amount < pool.partner_quote_fee);
+    assert_eq!(buyback_result.next_sqrt_price, sqrt_price_limit);
+    pool.apply_buyback_result(&config, &config_extension, &buyback_result, 0, 0)
+        .unwrap();
+    assert_eq!(
+        pool.total_burned_base_amount,
+        total_burned_base_amount + buyback_result.base_amount
+    );
+
+    assert!(pool
+        .get_buyback_result(
+            &config,
+            &config_extension,
+            u64::MAX,
+            Some(pool.sqrt_price),
+            0,
+        )
+        .is_err());
+}
+
+#[test]
+fn test_buyback_spend_is_capped_per_period() {
+    let quote_amount_per_period = 100_000_000;
+    let (config, config_extension, mut pool) =
+        initialize_pool_and_config(BuybackFeeSource::Partner, quote_amount_per_period);
+
+    let partner_quote_fee = 1_000_000_000;
+    pool.partner_quote_fee = partner_quote_fee;
+
+    let current_point = 1_000;
+    let buyback_result = pool
+        .get_buyback_result(&config, &config_extension, u64::MAX, None, current_point)
+        .unwrap();
+    assert_eq!(buyback_result.quote_amount, quote_amount_per_period);
+    pool.apply_buyback_result(
+        &config,
+        &config_extension,
+        &buyback_result,
+        current_point,
+        0,
+    )
+    .unwrap();
+    assert_eq!(pool.buyback_period_start_point, current_point);
+    assert_eq!(pool.buyback_quote_amount_in_period, quote_amount_per_period);
+
+    // nothing is left in the period
+    let current_point = current_point + BUYBACK_PERIOD - 1;
+    assert_eq!(
+        pool.get_buyback_quote_amount_left_in_period(&config_extension, current_point)
+            .unwrap(),
+        0
+    );
+    let buyback_result = pool
+        .get_buyback_result(&config, &config_extension, u64::MAX, None, current_point)
+        .unwrap();
+    assert_eq!(buyback_result.quote_amount, 0);
+
+    // the next period starts with the full amount
+    let current_point = current_point + 1;
+    let buyback_result = pool
+        .get_buyback_result(&config, &config_extension, u64::MAX, None, current_point)
+        .unwrap();
+    assert_eq!(buyback_result.quote_amount, quote_amount_per_period);
+    pool.apply_buyback_result(
+        &config,
+        &config_extension,
+        &buyback_result,
+        current_point,
+        0,
+    )
+    .unwrap();
+    assert_eq!(pool.buyback_period_start_point, current_point);
+    assert_eq!(pool.buyback_quote_amount_in_period, quote_amount_per_period);
+    assert_eq!(
+        pool.partner_quote_fee,
+        partner_quote_fee - 2 * quote_amount_per_period
+    );
+}
+
+#[test]
+fn test_buyback_fee_source() {
+    let partner_quote_fee = 1_000_000_000;
+    let creator_quote_fee = 500_000_000;
+
+    let (config, _, mut pool) = initialize_pool_and_config(BuybackFeeSource::Partner, u64::MAX);
+    pool.partner_quote_fee = partner_quote_fee;
+    pool.creator_quote_fee = creator_quote_fee;
+    assert!(config.is_partner_quote_fee_bought_back());
+    assert!(!config.is_creator_quote_fee_bought_back());
+    assert!(!pool.is_creator_quote_fee_reserved_for_buyback().unwrap());
+    assert_eq!(
+        pool.get_buyback_quote_fee(&config).unwrap(),
+        partner_quote_fee
+    );
+
+    let (config, config_extension, mut pool) =
+        initialize_pool_and_config(BuybackFeeSource::Creator, u64::MAX);
+    pool.partner_quote_fee = partner_quote_fee;
+    pool.creator_quote_fee = creator_quote_fee;
+    assert!(!config.is_partner_quote_fee_bought_back());
+    assert!(pool.is_creator_quote_fee_reserved_for_buyback().unwrap());
+    let buyback_result = pool
+        .get_buyback_result(&config, &config_extension, u64::MAX, None, 0)
+        .unwrap();
+    assert_eq!(buyback_result.quote_amount, creator_quote_fee);
+    pool.apply_buyback_result(&config, &config_extension, &buyback_result, 0, 0)
+        .unwrap();
+    assert_eq!(pool.partner_quote_fee, partner_quote_fee);
+    assert_eq!(pool.creator_quote_fee, 0);
+
+    // the partner quote fee is spent first
+    let (config, config_extension, mut pool) =
+        initialize_pool_and_config(BuybackFeeSource::PartnerAndCreator, u64::MAX);
+    pool.partner_quote_fee = partner_quote_fee;
+    pool.creator_quote_fee = creator_quote_fee;
+    let quote_amount = partner_quote_fee + creator_quote_fee / 2;
+    let buyback_result = pool
+        .get_buyback_result(&config, &config_extension, quote_amount, None, 0)
+        .unwrap();
+    assert_eq!(buyback_result.quote_amount, quote_amount);
+    pool.apply_buyback_result(&config, &config_extension, &buyback_result, 0, 0)
+        .unwrap();
+    assert_eq!(pool.partner_quote_fee, 0);
+    assert_eq!(pool.creator_quote_fee, creator_quote_fee / 2);
+
+    // the creator quote fee is claimable once the curve is complete
+    pool.set_migration_progress(MigrationProgress::LockedVesting.into());
+    assert!(!pool.is_creator_quote_fee_reserved_for_buyback().unwrap());
+}
@@ -0,0 +1,100 @@
+use crate::{
+    constants::dynamic_fee::{
+        BIN_STEP_BPS_DEFAULT, BIN_STEP_BPS_U128_DEFAULT, DECAY_PERIOD_DEFAULT,
+        FILTER_PERIOD_DEFAULT, MAX_VOLATILITY_ACCUMULATOR, REDUCTION_FACTOR_DEFAULT,
+    },
+    params::fee_parameters::{DynamicFeeParameters, PoolFeeParameters},
+    state::{PoolConfig, VirtualPool},
+};
+
+const LAST_UPDATE_TIMESTAMP: u64 = 1_000_000;
+
+fn get_dynamic_fee_parameters(circuit_breaker_volatility_accumulator: u32) -> DynamicFeeParameters {
+    DynamicFeeParameters {
+        bin_step: BIN_STEP_BPS_DEFAULT,
+        bin_step_u128: BIN_STEP_BPS_U128_DEFAULT,
+        filter_period: FILTER_PERIOD_DEFAULT,
+        decay_period: DECAY_PERIOD_DEFAULT,
+        reduction_factor: REDUCTION_FACTOR_DEFAULT,
+        max_volatility_accumulator: MAX_VOLATILITY_ACCUMULATOR,
+        variable_fee_control: 100_000,
+        circuit_breaker_volatility_accumulator,
+    }
+}
+
+fn get_accounts(circuit_breaker_volatility_accumulator: u32) -> (PoolConfig, VirtualPool) {
+    let config = PoolConfig {
+        pool_fees: PoolFeeParameters {
+            dynamic_fee: Some(get_dynamic_fee_parameters(
+                circuit_breaker_volatility_accumulator,
+            )),
+            ..Default::default()
+        }
+        .to_pool_fees_config(),
+        ..Default::default()
+    };
+
+    let mut pool = VirtualPool {
+        sqrt_price: 1 << 64,
+        ..Default::default()
+    };
+    pool.volatility_tracker.last_update_timestamp = LAST_UPDATE_TIMESTAMP;
+    pool.volatility_tracker.sqrt_price_reference = pool.sqrt_price;
+    pool.volatility_tracker.volatility_accumulator = 1_000_000;
+    pool.volatility_tracker.volatility_reference = 1_000_000;
+    (config, pool)
+}
+
+#[test]
+fn test_circuit_breaker_decay() {
+    let (config, pool) = get_accounts(800_000);
+
+    // high frequency window, the volatility doesn't decay
+    assert!(pool
+        .is_circuit_breaker_triggered(&config, LAST_UPDATE_TIMESTAMP + 5)
+        .unwrap());
+    // half of the volatility decays under the threshold
+    assert!(!pool
+        .is_circuit_breaker_triggered(&config, LAST_UPDATE_TIMESTAMP + 60)
+        .unwrap());
+    // all volatility decays
+    assert!(!pool
+        .is_circuit_breaker_triggered(&config, LAST_UPDATE_TIMESTAMP + 120)
+        .unwrap());
+}
+
+#[test]
+fn test_circuit_breaker_price_move() {
+    let (config, mut pool) = get_accounts(1_200_000);
+
+    assert!(!pool
+        .is_circuit_breaker_triggered(&config, LAST_UPDATE_TIMESTAMP + 5)
+        .unwrap());
+
+    // the price moved 2% away from the reference since the last swap
+    pool.sqrt_price = pool.sqrt_price * 101 / 100;
+    assert!(pool
+        .is_circuit_breaker_triggered(&config, LAST_UPDATE_TIMESTAMP + 5)
+        .unwrap());
+}
+
+#[test]
+fn test_circuit_breaker_disabled() {
+    let (config, pool) = get_accounts(0);
+
+    assert!(!pool
+        .is_circuit_breaker_triggered(&config, LAST_UPDATE_TIMESTAMP + 5)
+        .unwrap());
+}
+
+#[test]
+fn test_validate_circuit_breaker_volatility_accumulator() {
+    assert!(get_dynamic_fee_parameters(0).validate().is_ok());
+    assert!(get_dynamic_fee_parameters(MAX_VOLATILITY_ACCUMULATOR - 1)
+        .validate()
+        .is_ok());
+    // never reached as the volatility accumulator is capped
+    assert!(get_dynamic_fee_parameters(MAX_VOLATILITY_ACCUMULATOR)
+        .validate()
+        .is_err());
+}
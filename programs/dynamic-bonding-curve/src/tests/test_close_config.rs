@@ -0,0 +1,52 @@
+use crate::state::{BaseFeeMode, CollectFeeMode, PoolConfig};
+
+#[test]
+fn test_retire_config() {
+    let mut config = PoolConfig::default();
+    assert!(!config.is_retired());
+
+    config.retire();
+    assert_eq!(config.retired_flag, 1);
+    assert!(config.is_retired());
+}
+
+#[test]
+fn test_config_is_closable_until_a_pool_is_created() {
+    let mut config = PoolConfig {
+        version: 1,
+        ..Default::default()
+    };
+    assert!(config.is_closable());
+
+    config.increase_pool_count().unwrap();
+    assert_eq!(config.pool_count, 1);
+    assert!(!config.is_closable());
+}
+
+#[test]
+fn test_legacy_config_is_not_closable() {
+    // pools aren't counted before version 1, so the config may be referenced by pools
+    let config = PoolConfig::default();
+    assert!(!config.is_closable());
+}
+
+#[test]
+fn test_config_has_config_extension() {
+    let mut config = PoolConfig::default();
+    assert!(!config.has_config_extension());
+
+    config.sell_base_fee_flag = 1;
+    assert!(config.has_config_extension());
+
+    config.sell_base_fee_flag = 0;
+    config.pool_fees.base_fee.base_fee_mode = BaseFeeMode::FeeStepTable.into();
+    assert!(config.has_config_extension());
+
+    config.pool_fees.base_fee.base_fee_mode = 0;
+    config.buy_cap_duration = 100;
+    assert!(config.has_config_extension());
+
+    config.buy_cap_duration = 0;
+    config.collect_fee_mode = CollectFeeMode::QuoteTokenBuybackBurn.into();
+    assert!(config.has_config_extension());
+}
@@ -0,0 +1,18 @@
+use crate::state::PoolConfig;
+
+#[test]
+fn test_creator_allowlist_is_tombstone_in_version_0() {
+    // version 0 configs stored the referral fee percent in the creator allowlist flag
+    let mut config = PoolConfig {
+        creator_allowlist_flag: 20,
+        ..Default::default()
+    };
+    assert!(!config.is_creator_allowlist_enabled());
+
+    config.version = 1;
+    config.creator_allowlist_flag = 0;
+    assert!(!config.is_creator_allowlist_enabled());
+
+    config.creator_allowlist_flag = 1;
+    assert!(config.is_creator_allowlist_enabled());
+}
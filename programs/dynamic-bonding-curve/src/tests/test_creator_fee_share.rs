@@ -0,0 +1,69 @@
+use anchor_lang::prelude::Pubkey;
+
+use crate::{
+    state::CreatorFeeShare, to_creator_fee_share_recipients, CreatorFeeShareRecipientParameters,
+    PoolError,
+};
+
+fn recipient(recipient: Pubkey, weight_bps: u16) -> CreatorFeeShareRecipientParameters {
+    CreatorFeeShareRecipientParameters {
+        recipient,
+        weight_bps,
+    }
+}
+
+#[test]
+fn test_validate_creator_fee_share_recipients() {
+    let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    assert!(to_creator_fee_share_recipients(&[recipient(a, 10_000)]).is_ok());
+    assert!(to_creator_fee_share_recipients(&[recipient(a, 3_000), recipient(b, 7_000)]).is_ok());
+
+    let invalid_recipients = [
+        // empty
+        vec![],
+        // too many recipients
+        (0..6)
+            .map(|_| recipient(Pubkey::new_unique(), 1_000))
+            .collect(),
+        // weights don't add up to 100%
+        vec![recipient(a, 3_000), recipient(b, 6_000)],
+        vec![recipient(a, 5_000), recipient(b, 6_000)],
+        // zero weight
+        vec![recipient(a, 10_000), recipient(b, 0)],
+        // duplicated recipient
+        vec![recipient(a, 5_000), recipient(a, 5_000)],
+        // default pubkey
+        vec![recipient(Pubkey::default(), 10_000)],
+    ];
+    for recipients in invalid_recipients {
+        assert_eq!(
+            to_creator_fee_share_recipients(&recipients).unwrap_err(),
+            PoolError::InvalidCreatorFeeShare.into()
+        );
+    }
+}
+
+#[test]
+fn test_split_creator_fee() {
+    let mut creator_fee_share = CreatorFeeShare::default();
+    creator_fee_share.initialize(
+        Pubkey::new_unique(),
+        to_creator_fee_share_recipients(&[
+            recipient(Pubkey::new_unique(), 3_333),
+            recipient(Pubkey::new_unique(), 3_333),
+            recipient(Pubkey::new_unique(), 3_334),
+        ])
+        .unwrap(),
+    );
+    assert_eq!(creator_fee_share.get_recipients().len(), 3);
+
+    // the rounding dust goes to the last recipient
+    let amounts = creator_fee_share.split_fee(100).unwrap();
+    assert_eq!(amounts, [33, 33, 34, 0, 0]);
+
+    let amounts = creator_fee_share.split_fee(1_000_000).unwrap();
+    assert_eq!(amounts, [333_300, 333_300, 333_400, 0, 0]);
+    assert_eq!(amounts.iter().sum::<u64>(), 1_000_000);
+
+    assert_eq!(creator_fee_share.split_fee(0).unwrap(), [0; 5]);
+}
@@ -0,0 +1,232 @@
+use crate::{
+    activation_handler::ActivationType,
+    base_fee::{BaseFeeHandler, FeeMarketCapScheduler, FeeSchedulerMode},
+    constants::fee::MIN_FEE_NUMERATOR,
+    params::{
+        fee_parameters::{BaseFeeParameters, PoolFeeParameters},
+        swap::TradeDirection,
+    },
+    state::{fee::VolatilityTracker, BaseFeeMode},
+};
+
+const INIT_SQRT_PRICE: u128 = 100_000_000_000_000_000_000;
+
+fn get_sqrt_price_after_steps(steps: u128, sqrt_price_step_bps: u128) -> u128 {
+    INIT_SQRT_PRICE + INIT_SQRT_PRICE * steps * sqrt_price_step_bps / 10_000
+}
+
+#[test]
+fn test_validate_fee_market_cap_scheduler() {
+    let fee_market_cap_scheduler = FeeMarketCapScheduler {
+        cliff_fee_numerator: 500_000_000, // 50%
+        number_of_period: 60,
+        sqrt_price_step_bps: 100, // 1%
+        reduction_factor: 8_000_000,
+        fee_scheduler_mode: FeeSchedulerMode::Linear.into(),
+    };
+    assert!(fee_market_cap_scheduler
+        .validate(0, ActivationType::Slot)
+        .is_ok());
+    // sells pay fee in quote token in both collect fee modes, so it is not tied to collect fee mode
+    assert!(fee_market_cap_scheduler
+        .validate(1, ActivationType::Timestamp)
+        .is_ok());
+
+    // zero scheduler is not allowed
+    for fee_market_cap_scheduler in [
+        FeeMarketCapScheduler {
+            number_of_period: 0,
+            ..fee_market_cap_scheduler
+        },
+        FeeMarketCapScheduler {
+            sqrt_price_step_bps: 0,
+            ..fee_market_cap_scheduler
+        },
+        FeeMarketCapScheduler {
+            reduction_factor: 0,
+            ..fee_market_cap_scheduler
+        },
+    ] {
+        assert!(fee_market_cap_scheduler
+            .validate(0, ActivationType::Slot)
+            .is_err());
+    }
+
+    // min base fee is below min fee
+    let fee_market_cap_scheduler = FeeMarketCapScheduler {
+        reduction_factor: 8_300_000,
+        ..fee_market_cap_scheduler
+    };
+    assert!(fee_market_cap_scheduler
+        .validate(0, ActivationType::Slot)
+        .is_err());
+    assert!(fee_market_cap_scheduler.validate_min_base_fee().is_err());
+}
+
+#[test]
+fn test_fee_market_cap_scheduler_linear() {
+    let cliff_fee_numerator = 500_000_000; // 50%
+    let reduction_factor = 8_000_000;
+    let sqrt_price_step_bps = 100;
+    let number_of_period = 60;
+    let fee_market_cap_scheduler = FeeMarketCapScheduler {
+        cliff_fee_numerator,
+        number_of_period,
+        sqrt_price_step_bps,
+        reduction_factor,
+        fee_scheduler_mode: FeeSchedulerMode::Linear.into(),
+    };
+
+    let get_fee_numerator = |current_sqrt_price: u128| {
+        fee_market_cap_scheduler
+            .get_base_fee_numerator_from_included_fee_amount(
+                0,
+                0,
+                TradeDirection::QuoteToBase,
+                1_000_000_000,
+                INIT_SQRT_PRICE,
+                current_sqrt_price,
+            )
+            .unwrap()
+    };
+
+    // price is not above the start price
+    assert_eq!(get_fee_numerator(INIT_SQRT_PRICE), cliff_fee_numerator);
+    assert_eq!(get_fee_numerator(INIT_SQRT_PRICE - 1), cliff_fee_numerator);
+
+    // fee only steps down once the price passes the next step
+    let one_step_sqrt_price = get_sqrt_price_after_steps(1, sqrt_price_step_bps.into());
+    assert_eq!(
+        get_fee_numerator(one_step_sqrt_price - 1),
+        cliff_fee_numerator
+    );
+    assert_eq!(
+        get_fee_numerator(one_step_sqrt_price),
+        cliff_fee_numerator - reduction_factor
+    );
+
+    let ten_step_sqrt_price = get_sqrt_price_after_steps(10, sqrt_price_step_bps.into());
+    assert_eq!(
+        get_fee_numerator(ten_step_sqrt_price),
+        cliff_fee_numerator - 10 * reduction_factor
+    );
+
+    // fee is floored at the last period
+    let min_fee_numerator = cliff_fee_numerator - u64::from(number_of_period) * reduction_factor;
+    assert_eq!(
+        fee_market_cap_scheduler
+            .get_min_base_fee_numerator()
+            .unwrap(),
+        min_fee_numerator
+    );
+    assert_eq!(get_fee_numerator(INIT_SQRT_PRICE * 100), min_fee_numerator);
+
+    // fee goes back up if price drops
+    assert_eq!(
+        get_fee_numerator(one_step_sqrt_price),
+        cliff_fee_numerator - reduction_factor
+    );
+
+    // exact out uses the same fee numerator
+    assert_eq!(
+        fee_market_cap_scheduler
+            .get_base_fee_numerator_from_excluded_fee_amount(
+                0,
+                0,
+                TradeDirection::BaseToQuote,
+                1_000_000_000,
+                INIT_SQRT_PRICE,
+                ten_step_sqrt_price,
+            )
+            .unwrap(),
+        cliff_fee_numerator - 10 * reduction_factor
+    );
+}
+
+#[test]
+fn test_fee_market_cap_scheduler_exponential() {
+    let cliff_fee_numerator = 500_000_000; // 50%
+    let fee_market_cap_scheduler = FeeMarketCapScheduler {
+        cliff_fee_numerator,
+        number_of_period: 100,
+        sqrt_price_step_bps: 50,
+        reduction_factor: 500, // 5% per period
+        fee_scheduler_mode: FeeSchedulerMode::Exponential.into(),
+    };
+    assert!(fee_market_cap_scheduler
+        .validate(0, ActivationType::Slot)
+        .is_ok());
+
+    let mut previous_fee_numerator = cliff_fee_numerator;
+    for steps in 1..=10 {
+        let fee_numerator = fee_market_cap_scheduler
+            .get_base_fee_numerator_from_included_fee_amount(
+                0,
+                0,
+                TradeDirection::QuoteToBase,
+                1_000_000_000,
+                INIT_SQRT_PRICE,
+                get_sqrt_price_after_steps(steps, 50),
+            )
+            .unwrap();
+        assert!(fee_numerator < previous_fee_numerator);
+        previous_fee_numerator = fee_numerator;
+    }
+    assert!(
+        previous_fee_numerator
+            >= fee_market_cap_scheduler
+                .get_min_base_fee_numerator()
+                .unwrap()
+    );
+    assert!(
+        fee_market_cap_scheduler
+            .get_min_base_fee_numerator()
+            .unwrap()
+            >= MIN_FEE_NUMERATOR
+    );
+}
+
+#[test]
+fn test_pool_fees_with_fee_market_cap_scheduler() {
+    let cliff_fee_numerator = 100_000_000; // 10%
+    let reduction_factor = 1_000_000;
+    let sqrt_price_step_bps = 200;
+    let pool_fees = PoolFeeParameters {
+        base_fee: BaseFeeParameters {
+            cliff_fee_numerator,
+            first_factor: 50,
+            second_factor: sqrt_price_step_bps,
+            third_factor: reduction_factor,
+            base_fee_mode: BaseFeeMode::FeeMarketCapSchedulerLinear.into(),
+        },
+        ..Default::default()
+    };
+    pool_fees.validate(0, ActivationType::Slot).unwrap();
+
+    let pool_fees = pool_fees.to_pool_fees_config();
+    let volatility_tracker = VolatilityTracker::default();
+    let current_sqrt_price = get_sqrt_price_after_steps(3, sqrt_price_step_bps.into());
+
+    // fee does not depend on time
+    for current_point in [0, 1_000, 1_000_000] {
+        let fee_numerator = pool_fees
+            .get_total_fee_numerator_from_included_fee_amount(
+                &volatility_tracker,
+                current_point,
+                0,
+                1_000_000_000,
+                TradeDirection::QuoteToBase,
+                INIT_SQRT_PRICE,
+                current_sqrt_price,
+                0,
+            )
+            .unwrap()
+            .fee_numerator;
+        assert_eq!(fee_numerator, cliff_fee_numerator - 3 * reduction_factor);
+    }
+
+    assert_eq!(
+        pool_fees.get_min_base_fee_numerator().unwrap(),
+        cliff_fee_numerator - 50 * reduction_factor
+    );
+}
@@ -0,0 +1,145 @@
+use crate::{
+    activation_handler::ActivationType,
+    base_fee::{BaseFeeHandler, FeeStepTable},
+    constants::{fee::MIN_FEE_NUMERATOR, MAX_FEE_STEPS},
+    params::swap::TradeDirection,
+    state::{fee::VolatilityTracker, BaseFeeMode, FeeStep, PoolConfig, PoolConfigExtension},
+    PoolError,
+};
+
+const ACTIVATION_POINT: u64 = 1_000;
+
+// 99% for 2 slots, 10% for 30 slots, 2% for 600 slots, 1% forever
+fn get_fee_steps() -> [FeeStep; MAX_FEE_STEPS] {
+    let mut fee_steps = [FeeStep::default(); MAX_FEE_STEPS];
+    fee_steps[0] = FeeStep {
+        elapsed_point: 0,
+        fee_numerator: 990_000_000,
+    };
+    fee_steps[1] = FeeStep {
+        elapsed_point: 2,
+        fee_numerator: 100_000_000,
+    };
+    fee_steps[2] = FeeStep {
+        elapsed_point: 32,
+        fee_numerator: 20_000_000,
+    };
+    fee_steps[3] = FeeStep {
+        elapsed_point: 632,
+        fee_numerator: 10_000_000,
+    };
+    fee_steps
+}
+
+#[test]
+fn test_fee_step_numerator() {
+    let mut config_extension = PoolConfigExtension::default();
+    config_extension.initialize(
+        Default::default(),
+        Default::default(),
+        get_fee_steps(),
+        0,
+        0,
+        0,
+    );
+
+    let get_fee_step_numerator = |elapsed_point: u64| {
+        config_extension
+            .get_fee_step_numerator(4, ACTIVATION_POINT + elapsed_point, ACTIVATION_POINT)
+            .unwrap()
+    };
+
+    assert_eq!(get_fee_step_numerator(0), 990_000_000);
+    assert_eq!(get_fee_step_numerator(1), 990_000_000);
+    assert_eq!(get_fee_step_numerator(2), 100_000_000);
+    assert_eq!(get_fee_step_numerator(31), 100_000_000);
+    assert_eq!(get_fee_step_numerator(32), 20_000_000);
+    assert_eq!(get_fee_step_numerator(631), 20_000_000);
+    assert_eq!(get_fee_step_numerator(632), 10_000_000);
+    assert_eq!(get_fee_step_numerator(1_000_000), 10_000_000);
+}
+
+#[test]
+fn test_fee_step_table_base_fee() {
+    let mut config = PoolConfig::default();
+    config.pool_fees.base_fee.base_fee_mode = BaseFeeMode::FeeStepTable.into();
+    config.pool_fees.base_fee.cliff_fee_numerator = 990_000_000;
+    config.pool_fees.base_fee.first_factor = 4;
+    config.pool_fees.base_fee.third_factor = 10_000_000;
+    assert!(config.is_fee_step_table_enabled());
+
+    let mut config_extension = PoolConfigExtension::default();
+    config_extension.initialize(
+        Default::default(),
+        Default::default(),
+        get_fee_steps(),
+        0,
+        0,
+        0,
+    );
+
+    let volatility_tracker = VolatilityTracker::default();
+    let get_total_fee_numerator = |config: &PoolConfig| {
+        config
+            .pool_fees
+            .get_total_fee_numerator_from_included_fee_amount(
+                &volatility_tracker,
+                ACTIVATION_POINT + 10,
+                ACTIVATION_POINT,
+                1_000_000,
+                TradeDirection::QuoteToBase,
+                0,
+                0,
+                0,
+            )
+            .unwrap()
+            .fee_numerator
+    };
+
+    // the config without the config extension charges the highest step fee
+    assert_eq!(get_total_fee_numerator(&config), 990_000_000);
+
+    let fee_step_config = config
+        .with_fee_step(&config_extension, ACTIVATION_POINT + 10, ACTIVATION_POINT)
+        .unwrap();
+    assert_eq!(get_total_fee_numerator(&fee_step_config), 100_000_000);
+    assert_eq!(
+        fee_step_config
+            .pool_fees
+            .get_min_base_fee_numerator()
+            .unwrap(),
+        10_000_000
+    );
+}
+
+#[test]
+fn test_validate_fee_step_table() {
+    let fee_step_table = FeeStepTable {
+        fee_numerator: 990_000_000,
+        number_of_step: 4,
+        min_fee_numerator: 10_000_000,
+    };
+    fee_step_table.validate(0, ActivationType::Slot).unwrap();
+    fee_step_table.validate_min_base_fee().unwrap();
+
+    let too_many_steps = FeeStepTable {
+        number_of_step: MAX_FEE_STEPS as u16 + 1,
+        ..fee_step_table
+    };
+    assert_eq!(
+        too_many_steps
+            .validate(0, ActivationType::Slot)
+            .unwrap_err(),
+        PoolError::InvalidFeeStepTable.into()
+    );
+
+    let below_min_fee = FeeStepTable {
+        min_fee_numerator: MIN_FEE_NUMERATOR - 1,
+        ..fee_step_table
+    };
+    assert!(below_min_fee.validate(0, ActivationType::Slot).is_err());
+    assert_eq!(
+        below_min_fee.validate_min_base_fee().unwrap_err(),
+        PoolError::InvalidMinBaseFee.into()
+    );
+}
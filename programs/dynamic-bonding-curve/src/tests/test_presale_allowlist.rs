@@ -0,0 +1,125 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::{
+    params::swap::TradeDirection,
+    state::{PoolConfig, PresaleAllowlist, WalletBuyTracker},
+    PoolError,
+};
+
+fn hash_leaf(wallet: &Pubkey, allocation: u64) -> [u8; 32] {
+    hashv(&[&[0], wallet.as_ref(), &allocation.to_le_bytes()]).to_bytes()
+}
+
+fn hash_node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        hashv(&[&[1], a, b]).to_bytes()
+    } else {
+        hashv(&[&[1], b, a]).to_bytes()
+    }
+}
+
+#[test]
+fn test_allowlist_phase() {
+    let activation_point = 1_000;
+    let config = PoolConfig {
+        allowlist_duration: 100,
+        ..Default::default()
+    };
+
+    assert!(config
+        .is_allowlist_phase_applied(
+            activation_point,
+            activation_point,
+            TradeDirection::QuoteToBase
+        )
+        .unwrap());
+    assert!(!config
+        .is_allowlist_phase_applied(
+            activation_point + 100,
+            activation_point,
+            TradeDirection::QuoteToBase
+        )
+        .unwrap());
+
+    // selling is not restricted
+    assert!(!config
+        .is_allowlist_phase_applied(
+            activation_point,
+            activation_point,
+            TradeDirection::BaseToQuote
+        )
+        .unwrap());
+
+    // no allowlist phase
+    let config = PoolConfig::default();
+    assert!(!config
+        .is_allowlist_phase_applied(
+            activation_point,
+            activation_point,
+            TradeDirection::QuoteToBase
+        )
+        .unwrap());
+}
+
+#[test]
+fn test_presale_allowlist_verify_proof() {
+    let wallets = [
+        (Pubkey::new_unique(), 1_000_000),
+        (Pubkey::new_unique(), 2_000_000),
+        (Pubkey::new_unique(), 3_000_000),
+    ];
+    let leaves: Vec<[u8; 32]> = wallets
+        .iter()
+        .map(|(wallet, allocation)| hash_leaf(wallet, *allocation))
+        .collect();
+
+    // root = node(node(leaf_0, leaf_1), leaf_2)
+    let node_01 = hash_node(&leaves[0], &leaves[1]);
+    let merkle_root = hash_node(&node_01, &leaves[2]);
+
+    let mut presale_allowlist = PresaleAllowlist::default();
+    presale_allowlist.initialize(Pubkey::new_unique(), merkle_root);
+
+    presale_allowlist
+        .verify_proof(&wallets[0].0, wallets[0].1, &[leaves[1], leaves[2]])
+        .unwrap();
+    presale_allowlist
+        .verify_proof(&wallets[1].0, wallets[1].1, &[leaves[0], leaves[2]])
+        .unwrap();
+    presale_allowlist
+        .verify_proof(&wallets[2].0, wallets[2].1, &[node_01])
+        .unwrap();
+
+    let err = presale_allowlist
+        .verify_proof(&Pubkey::new_unique(), wallets[0].1, &[leaves[1], leaves[2]])
+        .unwrap_err();
+    assert_eq!(err, PoolError::InvalidAllowlistProof.into());
+
+    // the allocation is part of the leaf
+    let err = presale_allowlist
+        .verify_proof(&wallets[0].0, wallets[2].1, &[leaves[1], leaves[2]])
+        .unwrap_err();
+    assert_eq!(err, PoolError::InvalidAllowlistProof.into());
+
+    let err = presale_allowlist
+        .verify_proof(&wallets[0].0, wallets[0].1, &[leaves[2]])
+        .unwrap_err();
+    assert_eq!(err, PoolError::InvalidAllowlistProof.into());
+}
+
+#[test]
+fn test_wallet_buy_tracker_accumulate_allowlist_buy_amount() {
+    let allocation = 1_000_000;
+    let mut wallet_buy_tracker = WalletBuyTracker::default();
+
+    wallet_buy_tracker
+        .accumulate_allowlist_buy_amount(allocation, allocation)
+        .unwrap();
+    assert_eq!(wallet_buy_tracker.allowlist_buy_amount, allocation);
+
+    let err = wallet_buy_tracker
+        .accumulate_allowlist_buy_amount(1, allocation)
+        .unwrap_err();
+    assert_eq!(err, PoolError::ExceededAllowlistAllocation.into());
+}
@@ -0,0 +1,349 @@
+use crate::{
+    params::swap::TradeDirection,
+    price_curve::{log2_q64, mul_pow2_q64, PriceCurve},
+    state::{
+        fee::{FeeMode, VolatilityTracker},
+        CollectFeeMode, CurveType, LiquidityDistributionConfig, PoolConfig, VirtualPool,
+    },
+    u128x128_math::Rounding,
+    PoolError,
+};
+use anchor_lang::prelude::Pubkey;
+use ruint::aliases::U256;
+
+// 1B tokens with 6 decimals
+const BASE_AMOUNT: u64 = 1_000_000_000_000_000;
+// price 1
+const SQRT_START_PRICE: u128 = 1 << 64;
+// price 9
+const END_SQRT_PRICE: u128 = 3 << 64;
+const MIGRATION_QUOTE_THRESHOLD: u64 = 2_000_000_000_000_000;
+
+fn get_price(sqrt_price: u128) -> f64 {
+    (sqrt_price as f64 / (1u128 << 64) as f64).powi(2)
+}
+
+fn initialize_pool_and_config(curve_type: CurveType) -> (PoolConfig, VirtualPool) {
+    let price_curve = PriceCurve::new(
+        curve_type,
+        SQRT_START_PRICE,
+        END_SQRT_PRICE,
+        BASE_AMOUNT.into(),
+    )
+    .unwrap();
+    let migration_sqrt_price = price_curve
+        .get_migration_sqrt_price(MIGRATION_QUOTE_THRESHOLD)
+        .unwrap();
+
+    let mut config = PoolConfig {
+        version: 1,
+        curve_type: curve_type.into(),
+        migration_quote_threshold: MIGRATION_QUOTE_THRESHOLD,
+        sqrt_start_price: SQRT_START_PRICE,
+        migration_sqrt_price,
+        collect_fee_mode: CollectFeeMode::QuoteToken.into(),
+        ..Default::default()
+    };
+    config.curve[0] = LiquidityDistributionConfig {
+        sqrt_price: END_SQRT_PRICE,
+        liquidity: BASE_AMOUNT.into(),
+    };
+
+    let mut pool = VirtualPool::default();
+    pool.initialize(
+        VolatilityTracker::default(),
+        Pubkey::default(),
+        Pubkey::default(),
+        Pubkey::default(),
+        Pubkey::default(),
+        Pubkey::default(),
+        config.sqrt_start_price,
+        0,
+        0,
+        BASE_AMOUNT,
+        0,
+        0,
+    );
+    (config, pool)
+}
+
+#[test]
+fn test_log2_q64() {
+    assert_eq!(log2_q64(1, Rounding::Down).unwrap(), 0);
+    assert_eq!(log2_q64(1 << 100, Rounding::Down).unwrap(), 100 << 64);
+
+    // log2(3) * 2^64 = 29237397617229858719.62...
+    let log2_3_down = log2_q64(3, Rounding::Down).unwrap();
+    let log2_3_up = log2_q64(3, Rounding::Up).unwrap();
+    assert!(log2_3_down <= 29237397617229858719 && 29237397617229858720 <= log2_3_up);
+
+    assert_eq!(
+        log2_q64(0, Rounding::Down).unwrap_err(),
+        PoolError::MathOverflow.into()
+    );
+}
+
+#[test]
+fn test_mul_pow2_q64() {
+    assert_eq!(
+        mul_pow2_q64(1_000, 3 << 64, true, Rounding::Down).unwrap(),
+        8_000
+    );
+    assert_eq!(
+        mul_pow2_q64(1_000, 3 << 64, false, Rounding::Up).unwrap(),
+        125
+    );
+
+    // 2^0.5 * 10^18 = 1414213562373095048.8
+    let exponent = 1 << 63;
+    let value = 1_000_000_000_000_000_000;
+    assert_eq!(
+        mul_pow2_q64(value, exponent, true, Rounding::Down).unwrap(),
+        1_414_213_562_373_095_048
+    );
+    assert_eq!(
+        mul_pow2_q64(value, exponent, true, Rounding::Up).unwrap(),
+        1_414_213_562_373_095_049
+    );
+    // 2^-0.5 * 10^18 = 707106781186547524.4
+    assert_eq!(
+        mul_pow2_q64(value, exponent, false, Rounding::Down).unwrap(),
+        707_106_781_186_547_524
+    );
+    assert_eq!(
+        mul_pow2_q64(value, exponent, false, Rounding::Up).unwrap(),
+        707_106_781_186_547_525
+    );
+}
+
+#[test]
+fn test_linear_price_curve() {
+    let price_curve = PriceCurve::new(
+        CurveType::LinearPrice,
+        SQRT_START_PRICE,
+        END_SQRT_PRICE,
+        BASE_AMOUNT.into(),
+    )
+    .unwrap();
+
+    let base_amount = price_curve
+        .get_delta_amount_base(SQRT_START_PRICE, END_SQRT_PRICE, Rounding::Down)
+        .unwrap();
+    assert_eq!(base_amount, U256::from(BASE_AMOUNT));
+
+    // average price is 5
+    let quote_amount = price_curve
+        .get_delta_amount_quote(SQRT_START_PRICE, END_SQRT_PRICE, Rounding::Down)
+        .unwrap();
+    assert_eq!(quote_amount, U256::from(BASE_AMOUNT * 5));
+
+    // price grows by 8 / BASE_AMOUNT for every base token sold
+    for (base_amount_sold, expected_price) in [(BASE_AMOUNT / 4, 3.0), (BASE_AMOUNT / 2, 5.0)] {
+        let sqrt_price = price_curve
+            .get_next_sqrt_price_from_output(SQRT_START_PRICE, base_amount_sold, false)
+            .unwrap();
+        assert!((get_price(sqrt_price) - expected_price).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_exponential_price_curve() {
+    let price_curve = PriceCurve::new(
+        CurveType::ExponentialPrice,
+        SQRT_START_PRICE,
+        END_SQRT_PRICE,
+        BASE_AMOUNT.into(),
+    )
+    .unwrap();
+
+    let base_amount = price_curve
+        .get_delta_amount_base(SQRT_START_PRICE, END_SQRT_PRICE, Rounding::Down)
+        .unwrap();
+    assert_eq!(base_amount, U256::from(BASE_AMOUNT));
+
+    // quote = BASE_AMOUNT * (9 - 1) / ln(9)
+    let quote_amount: u64 = price_curve
+        .get_delta_amount_quote(SQRT_START_PRICE, END_SQRT_PRICE, Rounding::Down)
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let expected_quote_amount = BASE_AMOUNT as f64 * 8.0 / 9f64.ln();
+    assert!((quote_amount as f64 - expected_quote_amount).abs() / expected_quote_amount < 1e-12);
+
+    // price is multiplied by 3 every half of the base amount
+    for (base_amount_sold, expected_price) in
+        [(BASE_AMOUNT / 4, 3f64.sqrt()), (BASE_AMOUNT / 2, 3.0)]
+    {
+        let sqrt_price = price_curve
+            .get_next_sqrt_price_from_output(SQRT_START_PRICE, base_amount_sold, false)
+            .unwrap();
+        assert!((get_price(sqrt_price) - expected_price).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_invalid_price_curve() {
+    assert_eq!(
+        PriceCurve::new(
+            CurveType::ConstantProduct,
+            SQRT_START_PRICE,
+            END_SQRT_PRICE,
+            BASE_AMOUNT.into(),
+        )
+        .unwrap_err(),
+        PoolError::InvalidCurveType.into()
+    );
+    // the base amount must fit in u64
+    assert_eq!(
+        PriceCurve::new(
+            CurveType::LinearPrice,
+            SQRT_START_PRICE,
+            END_SQRT_PRICE,
+            u128::from(u64::MAX) + 1,
+        )
+        .unwrap_err(),
+        PoolError::InvalidCurve.into()
+    );
+    // the price range is narrower than the precision of the logarithm
+    assert_eq!(
+        PriceCurve::new(
+            CurveType::ExponentialPrice,
+            SQRT_START_PRICE,
+            SQRT_START_PRICE + 1,
+            BASE_AMOUNT.into(),
+        )
+        .unwrap_err(),
+        PoolError::InvalidCurve.into()
+    );
+}
+
+#[test]
+fn test_curve_type_is_tombstone_in_version_0() {
+    // version 0 configs stored the protocol fee percent in the curve type
+    let (mut config, _pool) = initialize_pool_and_config(CurveType::LinearPrice);
+    assert!(config.get_price_curve().unwrap().is_some());
+
+    config.version = 0;
+    config.curve_type = 20;
+    assert_eq!(config.get_curve_type().unwrap(), CurveType::ConstantProduct);
+    assert!(config.get_price_curve().unwrap().is_none());
+
+    config.version = 1;
+    config.curve_type = 3;
+    assert_eq!(
+        config.get_curve_type().unwrap_err(),
+        PoolError::InvalidCurveType.into()
+    );
+}
+
+#[test]
+fn test_swap_on_price_curve() {
+    for curve_type in [CurveType::LinearPrice, CurveType::ExponentialPrice] {
+        let (config, mut pool) = initialize_pool_and_config(curve_type);
+
+        let amount_in = MIGRATION_QUOTE_THRESHOLD / 10;
+        let fee_mode =
+            FeeMode::get_fee_mode(config.collect_fee_mode, TradeDirection::QuoteToBase, false)
+                .unwrap();
+        let buy_result = pool
+            .get_swap_result_from_exact_input(
+                &config,
+                amount_in,
+                &fee_mode,
+                TradeDirection::QuoteToBase,
+                0,
+                false,
+            )
+            .unwrap();
+
+        // buying the same base amount with exact output doesn't cost more
+        let buy_exact_out_result = pool
+            .get_swap_result_from_exact_output(
+                &config,
+                buy_result.output_amount,
+                &fee_mode,
+                TradeDirection::QuoteToBase,
+                0,
+                false,
+            )
+            .unwrap();
+        assert!(buy_exact_out_result.included_fee_input_amount <= amount_in);
+        assert!(buy_exact_out_result.next_sqrt_price <= buy_result.next_sqrt_price);
+
+        // selling back the base amount doesn't return more than what was paid
+        pool.sqrt_price = buy_result.next_sqrt_price;
+        let fee_mode =
+            FeeMode::get_fee_mode(config.collect_fee_mode, TradeDirection::BaseToQuote, false)
+                .unwrap();
+        let sell_result = pool
+            .get_swap_result_from_exact_input(
+                &config,
+                buy_result.output_amount,
+                &fee_mode,
+                TradeDirection::BaseToQuote,
+                0,
+                false,
+            )
+            .unwrap();
+        assert!(sell_result.output_amount <= amount_in);
+        assert!(sell_result.next_sqrt_price >= config.sqrt_start_price);
+
+        // selling the whole base amount stops at the start price
+        let sell_result = pool
+            .get_swap_result_from_partial_input(
+                &config,
+                buy_result.output_amount * 2,
+                &fee_mode,
+                TradeDirection::BaseToQuote,
+                0,
+                false,
+                None,
+            )
+            .unwrap();
+        assert_eq!(sell_result.next_sqrt_price, config.sqrt_start_price);
+        // the base amount to the start price is rounded up
+        assert!(sell_result.amount_left + 1 >= buy_result.output_amount);
+        assert!(sell_result.output_amount <= amount_in);
+    }
+}
+
+#[test]
+fn test_partial_fill_to_migration_on_price_curve() {
+    for curve_type in [CurveType::LinearPrice, CurveType::ExponentialPrice] {
+        let (config, pool) = initialize_pool_and_config(curve_type);
+        let fee_mode =
+            FeeMode::get_fee_mode(config.collect_fee_mode, TradeDirection::QuoteToBase, false)
+                .unwrap();
+
+        // can't buy over the migration price with exact input
+        let amount_in = MIGRATION_QUOTE_THRESHOLD * 2;
+        assert_eq!(
+            pool.get_swap_result_from_exact_input(
+                &config,
+                amount_in,
+                &fee_mode,
+                TradeDirection::QuoteToBase,
+                0,
+                false,
+            )
+            .unwrap_err(),
+            PoolError::InsufficientLiquidity.into()
+        );
+
+        let result = pool
+            .get_swap_result_from_partial_input(
+                &config,
+                amount_in,
+                &fee_mode,
+                TradeDirection::QuoteToBase,
+                0,
+                false,
+                None,
+            )
+            .unwrap();
+        assert_eq!(result.next_sqrt_price, config.migration_sqrt_price);
+        let consumed_amount = amount_in - result.amount_left;
+        assert!(consumed_amount >= MIGRATION_QUOTE_THRESHOLD);
+        assert!(consumed_amount - MIGRATION_QUOTE_THRESHOLD <= 1);
+    }
+}
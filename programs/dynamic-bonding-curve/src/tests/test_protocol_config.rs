@@ -0,0 +1,126 @@
+use anchor_lang::prelude::Pubkey;
+
+use crate::{
+    constants::fee::{HOST_FEE_PERCENT, PROTOCOL_FEE_PERCENT, PROTOCOL_POOL_CREATION_FEE_PERCENT},
+    state::{fee::FeeOnAmountResult, PoolConfig, ProtocolConfig},
+    ProtocolConfigParameters,
+};
+
+#[test]
+fn test_legacy_config_uses_default_protocol_fee_shares() {
+    // configs created before protocol config are version 0, and their snapshot fields are zero
+    let config = PoolConfig {
+        pool_creation_fee: 1_000_000,
+        ..Default::default()
+    };
+    assert_eq!(config.get_protocol_fee_percent(), PROTOCOL_FEE_PERCENT);
+    assert_eq!(config.get_referral_fee_percent(), HOST_FEE_PERCENT);
+    assert_eq!(
+        config.get_protocol_pool_creation_fee_percent(),
+        PROTOCOL_POOL_CREATION_FEE_PERCENT
+    );
+
+    let (trading_fee, protocol_fee, referral_fee) = config.split_fees(1_000, true, 0).unwrap();
+    assert_eq!(trading_fee, 800);
+    assert_eq!(protocol_fee, 160);
+    assert_eq!(referral_fee, 40);
+
+    let (protocol_fee, partner_fee) = config.split_pool_creation_fee().unwrap();
+    assert_eq!(protocol_fee, 100_000);
+    assert_eq!(partner_fee, 900_000);
+}
+
+#[test]
+fn test_config_uses_snapshotted_protocol_fee_shares() {
+    let config = PoolConfig {
+        version: 1,
+        protocol_fee_percent: 10,
+        referral_fee_percent: 50,
+        protocol_pool_creation_fee_percent: 0,
+        pool_creation_fee: 1_000_000,
+        ..Default::default()
+    };
+
+    let (trading_fee, protocol_fee, referral_fee) = config.split_fees(1_000, true, 0).unwrap();
+    assert_eq!(trading_fee, 900);
+    assert_eq!(protocol_fee, 50);
+    assert_eq!(referral_fee, 50);
+
+    let (trading_fee, protocol_fee, referral_fee) = config.split_fees(1_000, false, 0).unwrap();
+    assert_eq!(trading_fee, 900);
+    assert_eq!(protocol_fee, 100);
+    assert_eq!(referral_fee, 0);
+
+    // 1% fee
+    let FeeOnAmountResult {
+        amount,
+        trading_fee,
+        protocol_fee,
+        referral_fee,
+    } = config
+        .get_fee_on_amount(10_000_000, 100_000, true, 0)
+        .unwrap();
+    assert_eq!(amount, 99_000);
+    assert_eq!(trading_fee, 900);
+    assert_eq!(protocol_fee, 50);
+    assert_eq!(referral_fee, 50);
+
+    // partner takes the whole pool creation fee
+    let (protocol_fee, partner_fee) = config.split_pool_creation_fee().unwrap();
+    assert_eq!(protocol_fee, 0);
+    assert_eq!(partner_fee, 1_000_000);
+}
+
+#[test]
+fn test_protocol_config() {
+    let default_protocol_config = ProtocolConfig::default();
+    assert_eq!(
+        default_protocol_config.protocol_fee_percent,
+        PROTOCOL_FEE_PERCENT
+    );
+    assert_eq!(
+        default_protocol_config.referral_fee_percent,
+        HOST_FEE_PERCENT
+    );
+    assert_eq!(
+        default_protocol_config.protocol_pool_creation_fee_percent,
+        PROTOCOL_POOL_CREATION_FEE_PERCENT
+    );
+
+    let partner = Pubkey::new_unique();
+    let mut protocol_config = ProtocolConfig::default();
+    protocol_config.initialize(partner, 5, 20, 0);
+
+    protocol_config.update(10, 30, 5);
+    assert_eq!(protocol_config.partner, partner);
+    assert_eq!(protocol_config.protocol_fee_percent, 10);
+    assert_eq!(protocol_config.referral_fee_percent, 30);
+    assert_eq!(protocol_config.protocol_pool_creation_fee_percent, 5);
+}
+
+#[test]
+fn test_validate_protocol_config_parameters() {
+    let params = ProtocolConfigParameters {
+        protocol_fee_percent: 100,
+        referral_fee_percent: 0,
+        protocol_pool_creation_fee_percent: 100,
+    };
+    assert!(params.validate().is_ok());
+
+    for params in [
+        ProtocolConfigParameters {
+            protocol_fee_percent: 101,
+            ..params
+        },
+        ProtocolConfigParameters {
+            referral_fee_percent: 101,
+            ..params
+        },
+        ProtocolConfigParameters {
+            protocol_pool_creation_fee_percent: 101,
+            ..params
+        },
+    ] {
+        assert!(params.validate().is_err());
+    }
+}
@@ -5,13 +5,13 @@ use proptest::proptest;
 
 use crate::constants::fee::MAX_FEE_BPS;
 use crate::constants::fee::MIN_FEE_BPS;
-use crate::state::PoolFeesConfig;
+use crate::state::{BaseFeeMode, PoolFeesConfig, RateLimiterMode};
 use crate::{
     activation_handler::ActivationType,
     base_fee::{BaseFeeHandler, FeeRateLimiter},
     constants::fee::{FEE_DENOMINATOR, MAX_FEE_NUMERATOR, MIN_FEE_NUMERATOR},
     params::{
-        fee_parameters::{to_bps, to_numerator},
+        fee_parameters::{to_bps, to_numerator, BaseFeeParameters, PoolFeeParameters},
         swap::TradeDirection,
     },
     u128x128_math::Rounding,
@@ -27,6 +27,7 @@ fn test_validate_rate_limiter() {
             reference_amount: 1_000_000_000, // 1SOL
             max_limiter_duration: 60,        // 60 seconds
             fee_increment_bps: 10,           // 10 bps
+            ..Default::default()
         };
         assert!(rate_limiter.validate(1, ActivationType::Slot).is_err());
         assert!(rate_limiter.validate(0, ActivationType::Slot).is_ok());
@@ -39,6 +40,7 @@ fn test_validate_rate_limiter() {
             reference_amount: 1,     // 1SOL
             max_limiter_duration: 0, // 60 seconds
             fee_increment_bps: 0,    // 10 bps
+            ..Default::default()
         };
         assert!(rate_limiter.validate(0, ActivationType::Slot).is_err());
         let rate_limiter = FeeRateLimiter {
@@ -46,6 +48,7 @@ fn test_validate_rate_limiter() {
             reference_amount: 0,     // 1SOL
             max_limiter_duration: 1, // 60 seconds
             fee_increment_bps: 0,    // 10 bps
+            ..Default::default()
         };
         assert!(rate_limiter.validate(0, ActivationType::Slot).is_err());
         let rate_limiter = FeeRateLimiter {
@@ -53,6 +56,7 @@ fn test_validate_rate_limiter() {
             reference_amount: 0,     // 1SOL
             max_limiter_duration: 0, // 60 seconds
             fee_increment_bps: 1,    // 10 bps
+            ..Default::default()
         };
         assert!(rate_limiter.validate(0, ActivationType::Slot).is_err());
     }
@@ -64,6 +68,7 @@ fn test_validate_rate_limiter() {
             reference_amount: 1_000_000_000, // 1SOL
             max_limiter_duration: 60,        // 60 seconds
             fee_increment_bps: 10,           // 10 bps
+            ..Default::default()
         };
         assert!(rate_limiter.validate(0, ActivationType::Slot).is_err());
         let rate_limiter = FeeRateLimiter {
@@ -71,6 +76,7 @@ fn test_validate_rate_limiter() {
             reference_amount: 1_000_000_000, // 1SOL
             max_limiter_duration: 60,        // 60 seconds
             fee_increment_bps: 10,           // 10 bps
+            ..Default::default()
         };
         assert!(rate_limiter.validate(0, ActivationType::Slot).is_err());
     }
@@ -89,6 +95,7 @@ fn test_rate_limiter_behavior() {
         reference_amount,         // 1SOL
         max_limiter_duration: 60, // 60 seconds
         fee_increment_bps,        // 10 bps
+        ..Default::default()
     };
     assert!(rate_limiter.validate(0, ActivationType::Slot).is_ok());
 
@@ -146,6 +153,8 @@ fn calculate_output_amount(rate_limiter: &FeeRateLimiter, input_amount: u64) ->
             0,
             TradeDirection::QuoteToBase,
             input_amount,
+            0,
+            0,
         )
         .unwrap();
     let trading_fee: u64 = safe_mul_div_cast_u64(
@@ -170,6 +179,7 @@ fn test_rate_limiter_routing_friendly() {
         reference_amount,         // 1SOL
         max_limiter_duration: 60, // 60 seconds
         fee_increment_bps,        // 10 bps
+        ..Default::default()
     };
 
     let mut input_amount = reference_amount - 10;
@@ -195,6 +205,7 @@ fn test_rate_limiter_base_fee_numerator() {
         reference_amount,         // 1SOL
         max_limiter_duration: 60, // 60 seconds
         fee_increment_bps,        // 10 bps
+        ..Default::default()
     };
 
     {
@@ -205,6 +216,8 @@ fn test_rate_limiter_base_fee_numerator() {
                 0,
                 TradeDirection::BaseToQuote,
                 2_000_000_000,
+                0,
+                0,
             )
             .unwrap();
 
@@ -219,6 +232,8 @@ fn test_rate_limiter_base_fee_numerator() {
                 0,
                 TradeDirection::QuoteToBase,
                 2_000_000_000,
+                0,
+                0,
             )
             .unwrap();
 
@@ -233,6 +248,8 @@ fn test_rate_limiter_base_fee_numerator() {
                 0,
                 TradeDirection::QuoteToBase,
                 2_000_000_000,
+                0,
+                0,
             )
             .unwrap();
 
@@ -240,6 +257,159 @@ fn test_rate_limiter_base_fee_numerator() {
     }
 }
 
+#[test]
+fn test_validate_rate_limiter_mode() {
+    // sells pay fee on quote output in both collect fee modes, buys only in quote token collect fee mode
+    for (rate_limiter_mode, is_output_token_mode_valid) in [
+        (RateLimiterMode::QuoteToBase, false),
+        (RateLimiterMode::BaseToQuote, true),
+        (RateLimiterMode::Both, false),
+    ] {
+        let rate_limiter = FeeRateLimiter {
+            cliff_fee_numerator: 2_500_000,
+            reference_amount: 1_000_000_000,
+            max_limiter_duration: 60,
+            fee_increment_bps: 10,
+            rate_limiter_mode,
+        };
+        assert!(rate_limiter.validate(0, ActivationType::Slot).is_ok());
+        assert_eq!(
+            rate_limiter.validate(1, ActivationType::Slot).is_ok(),
+            is_output_token_mode_valid
+        );
+    }
+
+    // rate limiter mode is only allowed with rate limiter base fee mode
+    let pool_fees = PoolFeeParameters {
+        base_fee: BaseFeeParameters {
+            cliff_fee_numerator: 2_500_000,
+            base_fee_mode: BaseFeeMode::FeeSchedulerLinear.into(),
+            ..Default::default()
+        },
+        rate_limiter_mode: RateLimiterMode::BaseToQuote.into(),
+        ..Default::default()
+    };
+    assert!(pool_fees.validate(0, ActivationType::Slot).is_err());
+
+    let pool_fees = PoolFeeParameters {
+        base_fee: BaseFeeParameters {
+            cliff_fee_numerator: 2_500_000,
+            first_factor: 10,
+            second_factor: 60,
+            third_factor: 1_000_000_000,
+            base_fee_mode: BaseFeeMode::RateLimiter.into(),
+        },
+        rate_limiter_mode: RateLimiterMode::BaseToQuote.into(),
+        ..Default::default()
+    };
+    assert!(pool_fees.validate(1, ActivationType::Slot).is_ok());
+    let rate_limiter = pool_fees
+        .to_pool_fees_config()
+        .base_fee
+        .get_fee_rate_limiter()
+        .unwrap();
+    assert_eq!(rate_limiter.rate_limiter_mode, RateLimiterMode::BaseToQuote);
+}
+
+#[test]
+fn test_rate_limiter_sell_base_fee_numerator() {
+    let base_fee_bps = 100u64; // 1%
+    let reference_amount = 1_000_000_000; // 1 sol
+    let fee_increment_bps = 100; // 1%
+    let cliff_fee_numerator = to_numerator(base_fee_bps.into(), FEE_DENOMINATOR.into()).unwrap();
+
+    let sell_rate_limiter = FeeRateLimiter {
+        cliff_fee_numerator,
+        reference_amount,         // 1SOL of quote output
+        max_limiter_duration: 60, // 60 seconds
+        fee_increment_bps,        // 1%
+        rate_limiter_mode: RateLimiterMode::BaseToQuote,
+    };
+
+    {
+        // buys pay the cliff fee
+        let fee_numerator = sell_rate_limiter
+            .get_base_fee_numerator_from_included_fee_amount(
+                0,
+                0,
+                TradeDirection::QuoteToBase,
+                2_000_000_000,
+                0,
+                0,
+            )
+            .unwrap();
+        assert_eq!(fee_numerator, cliff_fee_numerator);
+    }
+
+    {
+        // sells are escalated on the quote output, same as buys on the quote input
+        let fee_numerator = sell_rate_limiter
+            .get_base_fee_numerator_from_included_fee_amount(
+                0,
+                0,
+                TradeDirection::BaseToQuote,
+                reference_amount * 2,
+                0,
+                0,
+            )
+            .unwrap();
+        let fee_bps = to_bps(fee_numerator.into(), FEE_DENOMINATOR.into()).unwrap();
+        assert_eq!(fee_bps, 150); // 1.5%, (1+1+1) / 2
+
+        // exact out sells get the fee numerator from the excluded fee quote output
+        let excluded_fee_amount = sell_rate_limiter
+            .get_excluded_fee_amount(reference_amount * 2)
+            .unwrap();
+        let fee_numerator = sell_rate_limiter
+            .get_base_fee_numerator_from_excluded_fee_amount(
+                0,
+                0,
+                TradeDirection::BaseToQuote,
+                excluded_fee_amount,
+                0,
+                0,
+            )
+            .unwrap();
+        assert!(fee_numerator > cliff_fee_numerator);
+    }
+
+    {
+        // sells pass last effective point
+        let fee_numerator = sell_rate_limiter
+            .get_base_fee_numerator_from_included_fee_amount(
+                sell_rate_limiter.max_limiter_duration + 1,
+                0,
+                TradeDirection::BaseToQuote,
+                2_000_000_000,
+                0,
+                0,
+            )
+            .unwrap();
+        assert_eq!(fee_numerator, cliff_fee_numerator);
+    }
+
+    {
+        // both directions are escalated
+        let rate_limiter = FeeRateLimiter {
+            rate_limiter_mode: RateLimiterMode::Both,
+            ..sell_rate_limiter
+        };
+        for trade_direction in [TradeDirection::QuoteToBase, TradeDirection::BaseToQuote] {
+            let fee_numerator = rate_limiter
+                .get_base_fee_numerator_from_included_fee_amount(
+                    0,
+                    0,
+                    trade_direction,
+                    2_000_000_000,
+                    0,
+                    0,
+                )
+                .unwrap();
+            assert!(fee_numerator > cliff_fee_numerator);
+        }
+    }
+}
+
 fn assert_rate_limiter_inverse(rate_limiter: &FeeRateLimiter, included_fee_amount: u64) {
     let excluded_fee_amount = rate_limiter
         .get_excluded_fee_amount(included_fee_amount)
@@ -277,6 +447,7 @@ fn test_get_included_fee_amount_rate_limiter() {
         reference_amount,         // 1SOL
         max_limiter_duration: 60, // 60 seconds
         fee_increment_bps,        // 10 bps
+        ..Default::default()
     };
     {
         println!("1");
@@ -361,6 +532,7 @@ proptest! {
             reference_amount,         // 1SOL
             max_limiter_duration: 60, // 60 seconds
             fee_increment_bps,        // 10 bps
+            ..Default::default()
         };
         rate_limiter
         .get_fee_numerator_from_excluded_fee_amount(excluded_fee_amount)
@@ -381,6 +553,7 @@ proptest! {
             reference_amount,         // 1SOL
             max_limiter_duration: 60, // 60 seconds
             fee_increment_bps,        // 10 bps
+            ..Default::default()
         };
         rate_limiter
         .get_fee_numerator_from_excluded_fee_amount(excluded_fee_amount)
@@ -401,7 +574,8 @@ proptest! {
             cliff_fee_numerator,
             reference_amount,
             max_limiter_duration: 60, // 60 seconds
-            fee_increment_bps
+            fee_increment_bps,
+            ..Default::default()
         };
         rate_limiter
         .get_fee_numerator_from_excluded_fee_amount(excluded_fee_amount)
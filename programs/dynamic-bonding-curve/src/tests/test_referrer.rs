@@ -0,0 +1,39 @@
+use anchor_lang::prelude::Pubkey;
+
+use crate::state::{PoolConfig, Referrer};
+
+#[test]
+fn test_referrer_record_referred_swap() {
+    let owner = Pubkey::new_unique();
+    let mut referrer = Referrer::default();
+    referrer.initialize(owner);
+    assert_eq!(referrer.owner, owner);
+
+    referrer.record_referred_swap().unwrap();
+    referrer.record_referred_swap().unwrap();
+    assert_eq!(referrer.total_referred_swap, 2);
+}
+
+#[test]
+fn test_referrer_record_referred_swap_overflow() {
+    let mut referrer = Referrer {
+        total_referred_swap: u64::MAX,
+        ..Default::default()
+    };
+    assert!(referrer.record_referred_swap().is_err());
+}
+
+#[test]
+fn test_referral_fee_follows_config_share() {
+    // referral share is snapshotted from the protocol config
+    let config = PoolConfig {
+        version: 1,
+        protocol_fee_percent: 50,
+        referral_fee_percent: 50,
+        ..Default::default()
+    };
+    let (trading_fee, protocol_fee, referral_fee) = config.split_fees(1_000, true, 0).unwrap();
+    assert_eq!(trading_fee, 500);
+    assert_eq!(protocol_fee, 250);
+    assert_eq!(referral_fee, 250);
+}
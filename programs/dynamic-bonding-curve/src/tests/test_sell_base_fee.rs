@@ -0,0 +1,120 @@
+use crate::{
+    activation_handler::ActivationType,
+    constants::fee::MIN_FEE_NUMERATOR,
+    params::{
+        fee_parameters::{BaseFeeParameters, PoolFeeParameters},
+        swap::TradeDirection,
+    },
+    state::{fee::VolatilityTracker, BaseFeeMode, PoolConfig, PoolConfigExtension},
+    PoolError,
+};
+
+fn flat_base_fee(cliff_fee_numerator: u64) -> BaseFeeParameters {
+    BaseFeeParameters {
+        cliff_fee_numerator,
+        base_fee_mode: BaseFeeMode::FeeSchedulerLinear.into(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_sell_base_fee() {
+    let buy_fee_numerator = 10_000_000; // 1%
+    let sell_fee_numerator = 100_000_000; // 10%
+
+    let pool_fees = PoolFeeParameters {
+        base_fee: flat_base_fee(buy_fee_numerator),
+        dynamic_fee: None,
+        sell_base_fee: Some(flat_base_fee(sell_fee_numerator)),
+        rate_limiter_mode: 0,
+    };
+    pool_fees.validate(0, ActivationType::Slot).unwrap();
+
+    let mut config = PoolConfig {
+        pool_fees: pool_fees.to_pool_fees_config(),
+        sell_base_fee_flag: 1,
+        ..Default::default()
+    };
+    let mut config_extension = PoolConfigExtension::default();
+    config_extension.initialize(
+        Default::default(),
+        pool_fees.sell_base_fee.unwrap().to_base_fee_config(),
+        Default::default(),
+        0,
+        0,
+        0,
+    );
+
+    assert!(!config.is_sell_base_fee_applied(TradeDirection::QuoteToBase));
+    assert!(config.is_sell_base_fee_applied(TradeDirection::BaseToQuote));
+
+    let volatility_tracker = VolatilityTracker::default();
+    let buy_fee = config
+        .pool_fees
+        .get_total_fee_numerator_from_included_fee_amount(
+            &volatility_tracker,
+            0,
+            0,
+            1_000_000,
+            TradeDirection::QuoteToBase,
+            0,
+            0,
+            0,
+        )
+        .unwrap()
+        .fee_numerator;
+    assert_eq!(buy_fee, buy_fee_numerator);
+
+    let sell_config = config.with_sell_base_fee(&config_extension);
+    let sell_fee = sell_config
+        .pool_fees
+        .get_total_fee_numerator_from_included_fee_amount(
+            &volatility_tracker,
+            0,
+            0,
+            1_000_000,
+            TradeDirection::BaseToQuote,
+            0,
+            0,
+            0,
+        )
+        .unwrap()
+        .fee_numerator;
+    assert_eq!(sell_fee, sell_fee_numerator);
+
+    // without the flag, sells pay the base fee
+    config.sell_base_fee_flag = 0;
+    assert!(!config.is_sell_base_fee_applied(TradeDirection::BaseToQuote));
+}
+
+#[test]
+fn test_validate_sell_base_fee() {
+    let base_fee = flat_base_fee(10_000_000);
+
+    // rate limiter is not supported for sell base fee
+    let pool_fees = PoolFeeParameters {
+        base_fee,
+        dynamic_fee: None,
+        sell_base_fee: Some(BaseFeeParameters {
+            cliff_fee_numerator: MIN_FEE_NUMERATOR,
+            first_factor: 10,
+            second_factor: 60,
+            third_factor: 1_000_000_000,
+            base_fee_mode: BaseFeeMode::RateLimiter.into(),
+        }),
+        rate_limiter_mode: 0,
+    };
+    assert_eq!(
+        pool_fees.validate(0, ActivationType::Slot).unwrap_err(),
+        PoolError::InvalidSellBaseFee.into()
+    );
+
+    // sell base fee can't go below min fee
+    let pool_fees = PoolFeeParameters {
+        base_fee,
+        dynamic_fee: None,
+        sell_base_fee: Some(flat_base_fee(MIN_FEE_NUMERATOR - 1)),
+        rate_limiter_mode: 0,
+    };
+    assert!(pool_fees.validate(0, ActivationType::Slot).is_err());
+}
@@ -145,6 +145,7 @@ fn simulate_swap_partiall_fill(
             trade_direction,
             current_timestamp,
             false,
+            None,
         )
         .unwrap();
 
@@ -320,3 +321,76 @@ fn test_swap_exact_out_overflow() {
         PoolError::InsufficientLiquidity.into()
     );
 }
+
+#[test]
+fn test_swap_partial_fill_stop_at_sqrt_price_limit() {
+    let (config, mut pool, _user) = initialize_pool_and_config();
+    let amount_in = config.migration_quote_threshold;
+
+    // buy until the limit, the rest of input is refunded
+    let trade_direction = TradeDirection::QuoteToBase;
+    let fee_mode = &FeeMode::get_fee_mode(config.collect_fee_mode, trade_direction, false).unwrap();
+    let sqrt_price_limit = (config.sqrt_start_price + config.migration_sqrt_price) / 2;
+    let result = pool
+        .get_swap_result_from_partial_input(
+            &config,
+            amount_in,
+            &fee_mode,
+            trade_direction,
+            0,
+            false,
+            Some(sqrt_price_limit),
+        )
+        .unwrap();
+    assert_eq!(result.next_sqrt_price, sqrt_price_limit);
+    assert!(result.amount_left > 0);
+    assert!(result.included_fee_input_amount < amount_in);
+
+    pool.apply_swap_result(
+        &config,
+        &result.get_swap_result(),
+        &fee_mode,
+        trade_direction,
+        0,
+    )
+    .unwrap();
+
+    // sell until the limit, the rest of input is refunded
+    let trade_direction = TradeDirection::BaseToQuote;
+    let fee_mode = &FeeMode::get_fee_mode(config.collect_fee_mode, trade_direction, false).unwrap();
+    let sqrt_price_limit = (config.sqrt_start_price + pool.sqrt_price) / 2;
+    let result = pool
+        .get_swap_result_from_partial_input(
+            &config,
+            result.output_amount,
+            &fee_mode,
+            trade_direction,
+            0,
+            false,
+            Some(sqrt_price_limit),
+        )
+        .unwrap();
+    assert_eq!(result.next_sqrt_price, sqrt_price_limit);
+    assert!(result.amount_left > 0);
+}
+
+#[test]
+fn test_swap_partial_fill_invalid_sqrt_price_limit() {
+    let (config, pool, _user) = initialize_pool_and_config();
+
+    let trade_direction = TradeDirection::QuoteToBase;
+    let fee_mode = &FeeMode::get_fee_mode(config.collect_fee_mode, trade_direction, false).unwrap();
+    let result = pool.get_swap_result_from_partial_input(
+        &config,
+        1_000_000_000,
+        &fee_mode,
+        trade_direction,
+        0,
+        false,
+        Some(pool.sqrt_price),
+    );
+    assert_eq!(
+        result.err().unwrap(),
+        PoolError::InvalidSqrtPriceLimit.into()
+    );
+}
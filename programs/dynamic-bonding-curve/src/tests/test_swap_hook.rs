@@ -0,0 +1,54 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::AnchorSerialize;
+
+use crate::{
+    constants::SWAP_HOOK_DISCRIMINATOR, params::swap::TradeDirection, state::PoolConfig,
+    state::SwapResult2, SwapHookData,
+};
+
+#[test]
+fn test_swap_hook_discriminator() {
+    let hash = hashv(&[b"global:on_swap"]).to_bytes();
+    assert_eq!(SWAP_HOOK_DISCRIMINATOR, hash[..8]);
+}
+
+#[test]
+fn test_swap_hook_data_layout() {
+    let pool = Pubkey::new_unique();
+    let trader = Pubkey::new_unique();
+    let swap_result = SwapResult2 {
+        included_fee_input_amount: 1_000,
+        excluded_fee_input_amount: 990,
+        amount_left: 0,
+        output_amount: 500,
+        next_sqrt_price: 1 << 64,
+        trading_fee: 8,
+        protocol_fee: 2,
+        referral_fee: 0,
+    };
+    let data = SwapHookData {
+        pool,
+        trader,
+        trade_direction: TradeDirection::QuoteToBase.into(),
+        swap_result,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    // pool, trader, trade direction, then swap result
+    assert_eq!(data.len(), 32 + 32 + 1 + 8 * 4 + 16 + 8 * 3);
+    assert_eq!(&data[..32], pool.as_ref());
+    assert_eq!(&data[32..64], trader.as_ref());
+    assert_eq!(data[64], 1);
+    assert_eq!(&data[65..73], &1_000u64.to_le_bytes());
+}
+
+#[test]
+fn test_swap_hook_flag() {
+    let mut config = PoolConfig::default();
+    assert!(!config.is_swap_hook_enabled());
+
+    config.swap_hook_flag = 1;
+    assert!(config.is_swap_hook_enabled());
+}
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::Pubkey;
+
+use crate::{
+    constants::{MAX_POOL_OBSERVATIONS, MAX_SQRT_PRICE, MIN_POOL_OBSERVATION_INTERVAL},
+    state::PoolObservations,
+    PoolError,
+};
+
+fn new_pool_observations(sqrt_price: u128, current_timestamp: u64) -> PoolObservations {
+    let mut pool_observations = PoolObservations::default();
+    pool_observations.initialize(Pubkey::new_unique(), sqrt_price, current_timestamp);
+    pool_observations
+}
+
+#[test]
+fn test_twap_sqrt_price() {
+    let mut pool_observations = new_pool_observations(100, 1_000);
+
+    // price 100 for 100 seconds, then 400 for 300 seconds
+    pool_observations.update(100, 400, 1_100).unwrap();
+    // a swap in the same second doesn't change the accumulator
+    pool_observations.update(400, 400, 1_100).unwrap();
+    assert_eq!(pool_observations.observation_count, 2);
+
+    assert_eq!(pool_observations.twap(400, 1_400, 400).unwrap(), 325);
+    assert_eq!(pool_observations.twap(400, 1_400, 300).unwrap(), 400);
+    // the start of the window is interpolated between the observations
+    assert_eq!(pool_observations.twap(400, 1_100, 50).unwrap(), 100);
+}
+
+#[test]
+fn test_twap_window_not_covered() {
+    let pool_observations = new_pool_observations(100, 1_000);
+
+    assert_eq!(
+        pool_observations.twap(100, 1_100, 101).unwrap_err(),
+        PoolError::InvalidTwapWindow.into()
+    );
+    assert_eq!(
+        pool_observations.twap(100, 1_100, 0).unwrap_err(),
+        PoolError::InvalidTwapWindow.into()
+    );
+    assert_eq!(pool_observations.twap(100, 1_100, 100).unwrap(), 100);
+}
+
+#[test]
+fn test_pool_observations_ring_buffer() {
+    let mut pool_observations = new_pool_observations(100, 0);
+
+    // swaps closer than the min interval only update the accumulator
+    pool_observations
+        .update(100, 100, MIN_POOL_OBSERVATION_INTERVAL - 1)
+        .unwrap();
+    assert_eq!(pool_observations.observation_count, 1);
+
+    for i in 1..=MAX_POOL_OBSERVATIONS as u64 {
+        pool_observations
+            .update(100, 100, i * MIN_POOL_OBSERVATION_INTERVAL)
+            .unwrap();
+    }
+    assert_eq!(
+        usize::from(pool_observations.observation_count),
+        MAX_POOL_OBSERVATIONS
+    );
+    // the first observation is overwritten
+    assert_eq!(pool_observations.latest_index, 0);
+
+    let current_timestamp = MAX_POOL_OBSERVATIONS as u64 * MIN_POOL_OBSERVATION_INTERVAL;
+    let max_window = (MAX_POOL_OBSERVATIONS as u64 - 1) * MIN_POOL_OBSERVATION_INTERVAL;
+    assert_eq!(
+        pool_observations
+            .twap(100, current_timestamp, max_window)
+            .unwrap(),
+        100
+    );
+    assert_eq!(
+        pool_observations
+            .twap(100, current_timestamp, max_window + 1)
+            .unwrap_err(),
+        PoolError::InvalidTwapWindow.into()
+    );
+}
+
+#[test]
+fn test_twap_sqrt_price_wrapping() {
+    let mut pool_observations = new_pool_observations(MAX_SQRT_PRICE, 1);
+    pool_observations.sqrt_price_cumulative = u128::MAX - 10;
+    pool_observations.observations[0].sqrt_price_cumulative = u128::MAX - 10;
+
+    pool_observations
+        .update(MAX_SQRT_PRICE, MAX_SQRT_PRICE, 1_000)
+        .unwrap();
+
+    assert!(
+        pool_observations.sqrt_price_cumulative
+            < pool_observations.observations[0].sqrt_price_cumulative
+    );
+    assert_eq!(
+        pool_observations
+            .twap(MAX_SQRT_PRICE, 2_001, 2_000)
+            .unwrap(),
+        MAX_SQRT_PRICE
+    );
+}
+
+#[test]
+fn test_pool_observations_restart_after_gap() {
+    let mut pool_observations = new_pool_observations(100, 0);
+    pool_observations
+        .update(100, 100, MIN_POOL_OBSERVATION_INTERVAL)
+        .unwrap();
+    assert_eq!(pool_observations.observation_count, 2);
+    let sqrt_price_cumulative = pool_observations.sqrt_price_cumulative;
+
+    // a swap without the account moved the price, the twap can't be read until the next update
+    assert_eq!(
+        pool_observations
+            .twap(200, 2 * MIN_POOL_OBSERVATION_INTERVAL, 60)
+            .unwrap_err(),
+        PoolError::InvalidTwapWindow.into()
+    );
+
+    // the unknown price path isn't accumulated and the observations restart
+    let restart_timestamp = 2 * MIN_POOL_OBSERVATION_INTERVAL;
+    pool_observations
+        .update(200, 300, restart_timestamp)
+        .unwrap();
+    assert_eq!(pool_observations.observation_count, 1);
+    assert_eq!(pool_observations.latest_index, 0);
+    assert_eq!(
+        pool_observations.sqrt_price_cumulative,
+        sqrt_price_cumulative
+    );
+    assert_eq!(
+        pool_observations
+            .twap(300, restart_timestamp + 10, 11)
+            .unwrap_err(),
+        PoolError::InvalidTwapWindow.into()
+    );
+    assert_eq!(
+        pool_observations
+            .twap(300, restart_timestamp + 10, 10)
+            .unwrap(),
+        300
+    );
+}
@@ -0,0 +1,170 @@
+use crate::{
+    activation_handler::ActivationType,
+    params::{
+        fee_parameters::{BaseFeeParameters, PoolFeeParameters},
+        swap::TradeDirection,
+    },
+    state::{
+        fee::{TradeFeeNumerator, VolatilityTracker},
+        BaseFeeMode, PoolConfig, VolumeFeeTiers,
+    },
+    to_volume_fee_tiers, PoolError, VolumeFeeTierParameters,
+};
+
+fn tier(min_volume: u64, fee_discount_bps: u16) -> VolumeFeeTierParameters {
+    VolumeFeeTierParameters {
+        min_volume,
+        fee_discount_bps,
+    }
+}
+
+#[test]
+fn test_validate_volume_fee_tiers() {
+    assert!(to_volume_fee_tiers(&[tier(0, 100)]).is_ok());
+    assert!(
+        to_volume_fee_tiers(&[tier(1_000, 100), tier(10_000, 500), tier(100_000, 1_000)]).is_ok()
+    );
+
+    let invalid_tiers = [
+        // empty
+        vec![],
+        // too many tiers
+        vec![tier(1, 1), tier(2, 2), tier(3, 3), tier(4, 4), tier(5, 5)],
+        // zero discount
+        vec![tier(1_000, 0)],
+        // 100% discount
+        vec![tier(1_000, 10_000)],
+        // min volume not increasing
+        vec![tier(1_000, 100), tier(1_000, 200)],
+        // discount not increasing
+        vec![tier(1_000, 200), tier(2_000, 200)],
+    ];
+    for tiers in invalid_tiers {
+        assert_eq!(
+            to_volume_fee_tiers(&tiers).unwrap_err(),
+            PoolError::InvalidVolumeFeeTiers.into()
+        );
+    }
+}
+
+#[test]
+fn test_get_fee_discount_bps() {
+    let mut volume_fee_tiers = VolumeFeeTiers::default();
+    volume_fee_tiers.initialize(
+        Default::default(),
+        to_volume_fee_tiers(&[tier(1_000, 100), tier(10_000, 500), tier(100_000, 1_000)]).unwrap(),
+    );
+
+    assert_eq!(volume_fee_tiers.get_fee_discount_bps(0), 0);
+    assert_eq!(volume_fee_tiers.get_fee_discount_bps(999), 0);
+    assert_eq!(volume_fee_tiers.get_fee_discount_bps(1_000), 100);
+    assert_eq!(volume_fee_tiers.get_fee_discount_bps(99_999), 500);
+    assert_eq!(volume_fee_tiers.get_fee_discount_bps(u64::MAX), 1_000);
+}
+
+#[test]
+fn test_fee_discount_floored_at_min_base_fee() {
+    // 10% cliff fee, reduced to 1% after 10 periods
+    let pool_fees = PoolFeeParameters {
+        base_fee: BaseFeeParameters {
+            cliff_fee_numerator: 100_000_000,
+            first_factor: 10,
+            second_factor: 10,
+            third_factor: 9_000_000,
+            base_fee_mode: BaseFeeMode::FeeSchedulerLinear.into(),
+        },
+        dynamic_fee: None,
+        sell_base_fee: None,
+        rate_limiter_mode: 0,
+    };
+    pool_fees.validate(0, ActivationType::Slot).unwrap();
+    // 5% protocol fee
+    let config = PoolConfig {
+        version: 1,
+        protocol_fee_percent: 5,
+        pool_fees: pool_fees.to_pool_fees_config(),
+        ..Default::default()
+    };
+    let min_base_fee_numerator = config.pool_fees.get_min_base_fee_numerator().unwrap();
+    assert_eq!(min_base_fee_numerator, 10_000_000);
+
+    let volatility_tracker = VolatilityTracker::default();
+    let get_fee_numerator = |fee_discount_bps: u16| {
+        config
+            .pool_fees
+            .get_total_fee_numerator_from_included_fee_amount(
+                &volatility_tracker,
+                0,
+                0,
+                1_000_000,
+                TradeDirection::QuoteToBase,
+                0,
+                0,
+                fee_discount_bps,
+            )
+            .unwrap()
+    };
+
+    assert_eq!(
+        get_fee_numerator(0),
+        TradeFeeNumerator {
+            fee_numerator: 100_000_000,
+            fee_discount_bps: 0
+        }
+    );
+    assert_eq!(
+        get_fee_numerator(5_000),
+        TradeFeeNumerator {
+            fee_numerator: 50_000_000,
+            fee_discount_bps: 5_000
+        }
+    );
+
+    // 98% discount on the partner and creator share is floored at the min base fee, only 90% of the total fee is discounted
+    let tier_fee_discount_bps = config.get_total_fee_discount_bps(9_800).unwrap();
+    assert_eq!(tier_fee_discount_bps, 9_310);
+    let trade_fee_numerator = get_fee_numerator(tier_fee_discount_bps);
+    assert_eq!(
+        trade_fee_numerator,
+        TradeFeeNumerator {
+            fee_numerator: min_base_fee_numerator,
+            fee_discount_bps: 9_000
+        }
+    );
+
+    // the protocol fee is still 5% of the undiscounted 10% fee
+    let fee_on_amount = config
+        .get_fee_on_amount(
+            trade_fee_numerator.fee_numerator,
+            1_000_000,
+            false,
+            trade_fee_numerator.fee_discount_bps,
+        )
+        .unwrap();
+    assert_eq!(fee_on_amount.protocol_fee, 5_000);
+    assert_eq!(fee_on_amount.trading_fee, 5_000);
+    assert_eq!(fee_on_amount.amount, 990_000);
+}
+
+#[test]
+fn test_fee_discount_keeps_protocol_fee() {
+    // 20% protocol fee
+    let config = PoolConfig::default();
+
+    // 50% discount on the partner and creator share is 40% discount on the total fee
+    let fee_discount_bps = config.get_total_fee_discount_bps(5_000).unwrap();
+    assert_eq!(fee_discount_bps, 4_000);
+    assert_eq!(config.get_total_fee_discount_bps(0).unwrap(), 0);
+
+    let (trading_fee, protocol_fee, referral_fee) = config.split_fees(1_000, false, 0).unwrap();
+    assert_eq!((trading_fee, protocol_fee, referral_fee), (800, 200, 0));
+
+    let (trading_fee, protocol_fee, referral_fee) =
+        config.split_fees(600, false, fee_discount_bps).unwrap();
+    assert_eq!((trading_fee, protocol_fee, referral_fee), (400, 200, 0));
+
+    // the referral fee is still the share of the protocol fee
+    let (trading_fee, protocol_fee, referral_fee) =
+        config.split_fees(600, true, fee_discount_bps).unwrap();
+    assert_eq!((trading_fee, protocol_fee, referral_fee), (400, 160, 40));
+}
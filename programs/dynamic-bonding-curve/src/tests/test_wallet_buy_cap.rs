@@ -0,0 +1,74 @@
+use crate::{
+    params::swap::TradeDirection,
+    state::{PoolConfig, WalletBuyTracker},
+    PoolError,
+};
+
+#[test]
+fn test_wallet_buy_cap_window() {
+    let activation_point = 1_000;
+    let config = PoolConfig {
+        buy_cap_duration: 100,
+        ..Default::default()
+    };
+
+    assert!(config
+        .is_wallet_buy_cap_applied(
+            activation_point,
+            activation_point,
+            TradeDirection::QuoteToBase
+        )
+        .unwrap());
+    assert!(config
+        .is_wallet_buy_cap_applied(
+            activation_point + 99,
+            activation_point,
+            TradeDirection::QuoteToBase
+        )
+        .unwrap());
+    assert!(!config
+        .is_wallet_buy_cap_applied(
+            activation_point + 100,
+            activation_point,
+            TradeDirection::QuoteToBase
+        )
+        .unwrap());
+
+    // selling is never capped
+    assert!(!config
+        .is_wallet_buy_cap_applied(
+            activation_point,
+            activation_point,
+            TradeDirection::BaseToQuote
+        )
+        .unwrap());
+
+    // no cap
+    let config = PoolConfig::default();
+    assert!(!config
+        .is_wallet_buy_cap_applied(
+            activation_point,
+            activation_point,
+            TradeDirection::QuoteToBase
+        )
+        .unwrap());
+}
+
+#[test]
+fn test_wallet_buy_tracker_accumulate_buy_amount() {
+    let max_buy_amount = 1_000_000;
+    let mut wallet_buy_tracker = WalletBuyTracker::default();
+
+    wallet_buy_tracker
+        .accumulate_buy_amount(600_000, max_buy_amount)
+        .unwrap();
+    wallet_buy_tracker
+        .accumulate_buy_amount(400_000, max_buy_amount)
+        .unwrap();
+    assert_eq!(wallet_buy_tracker.total_buy_amount, max_buy_amount);
+
+    let err = wallet_buy_tracker
+        .accumulate_buy_amount(1, max_buy_amount)
+        .unwrap_err();
+    assert_eq!(err, PoolError::ExceededWalletBuyCap.into());
+}
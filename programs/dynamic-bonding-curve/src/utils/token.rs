@@ -6,7 +6,7 @@ use anchor_lang::{
     solana_program::system_instruction::transfer,
 };
 use anchor_spl::associated_token::get_associated_token_address_with_program_id;
-use anchor_spl::token::accessor;
+use anchor_spl::token::{accessor, spl_token, CloseAccount, SyncNative};
 use anchor_spl::{
     token::Token,
     token_2022::spl_token_2022::{
@@ -54,7 +54,7 @@ pub fn get_token_program_from_flag(token_program_flag: u8) -> Result<Pubkey> {
 pub fn transfer_token_from_user<'a, 'c: 'info, 'info>(
     authority: &'a Signer<'info>,
     token_mint: &'a InterfaceAccount<'info, Mint>,
-    token_owner_account: &'a InterfaceAccount<'info, TokenAccount>,
+    token_owner_account: AccountInfo<'info>,
     destination_token_account: &'a InterfaceAccount<'info, TokenAccount>,
     token_program: &'a Interface<'info, TokenInterface>,
     amount: u64,
@@ -63,7 +63,7 @@ pub fn transfer_token_from_user<'a, 'c: 'info, 'info>(
 
     let instruction = spl_token_2022::instruction::transfer_checked(
         token_program.key,
-        &token_owner_account.key(),
+        token_owner_account.key,
         &token_mint.key(),
         destination_account.key,
         authority.key,
@@ -73,7 +73,7 @@ pub fn transfer_token_from_user<'a, 'c: 'info, 'info>(
     )?;
 
     let account_infos = vec![
-        token_owner_account.to_account_info(),
+        token_owner_account,
         token_mint.to_account_info(),
         destination_account.to_account_info(),
         authority.to_account_info(),
@@ -170,6 +170,66 @@ pub fn transfer_lamports_from_user<'info>(
     Ok(())
 }
 
+pub fn is_native_mint(mint: &Pubkey) -> bool {
+    spl_token::native_mint::check_id(mint)
+}
+
+/// Create the user's native SOL associated token account, if it doesn't exist
+pub fn create_native_sol_token_account<'info>(
+    user: AccountInfo<'info>,
+    token_account: AccountInfo<'info>,
+    native_mint: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    associated_token_program: AccountInfo<'info>,
+) -> Result<()> {
+    anchor_spl::associated_token::create_idempotent(CpiContext::new(
+        associated_token_program,
+        anchor_spl::associated_token::Create {
+            payer: user.clone(),
+            associated_token: token_account,
+            authority: user,
+            mint: native_mint,
+            system_program,
+            token_program,
+        },
+    ))
+}
+
+/// Transfer lamports from user to the user's native SOL token account and sync its token amount
+pub fn wrap_native_sol_from_user<'info>(
+    user: AccountInfo<'info>,
+    token_account: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    lamports: u64,
+) -> Result<()> {
+    transfer_lamports_from_user(user, token_account.clone(), system_program, lamports)?;
+
+    anchor_spl::token::sync_native(CpiContext::new(
+        token_program,
+        SyncNative {
+            account: token_account,
+        },
+    ))
+}
+
+/// Close the user's native SOL token account, so all the lamports in it are sent back to the user
+pub fn unwrap_native_sol_to_user<'info>(
+    user: AccountInfo<'info>,
+    token_account: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    anchor_spl::token::close_account(CpiContext::new(
+        token_program,
+        CloseAccount {
+            account: token_account,
+            destination: user.clone(),
+            authority: user,
+        },
+    ))
+}
+
 pub fn transfer_lamports_from_pool_account<'info>(
     pool: AccountInfo<'info>,
     to: AccountInfo<'info>,
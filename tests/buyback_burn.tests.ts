@@ -0,0 +1,240 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  buybackAndBurn,
+  claimCreatorTradingFee,
+  claimTradingFee,
+  ConfigParameters,
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+  swap2,
+  SwapMode,
+} from "./instructions";
+import {
+  createVirtualCurveProgram,
+  designCurve,
+  expectThrowsAsync,
+  generateAndFund,
+  getDbcProgramErrorCodeHexString,
+  getMint,
+  startSvm,
+  warpTimestampBy,
+} from "./utils";
+import { getVirtualPool } from "./utils/fetcher";
+import { VirtualCurveProgram } from "./utils/types";
+
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { createToken, mintSplTokenTo } from "./utils/token";
+
+describe("Buyback and burn", () => {
+  let svm: LiteSVM;
+  let admin: Keypair;
+  let operator: Keypair;
+  let partner: Keypair;
+  let user: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let quoteMint: PublicKey;
+  let config: PublicKey;
+  let virtualPool: PublicKey;
+  let instructionParams: ConfigParameters;
+
+  before(async () => {
+    svm = startSvm();
+    admin = generateAndFund(svm);
+    operator = generateAndFund(svm);
+    partner = generateAndFund(svm);
+    user = generateAndFund(svm);
+    poolCreator = generateAndFund(svm);
+    program = createVirtualCurveProgram();
+
+    let lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    quoteMint = createToken(svm, admin, admin.publicKey, 9);
+    instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      0,
+      6,
+      9,
+      50,
+      2, // QuoteTokenBuybackBurn
+      lockedVesting,
+      {
+        feePercentage: 0,
+        creatorFeePercentage: 0,
+      }
+    );
+    // 10% flat base fee
+    instructionParams.poolFees.baseFee = {
+      cliffFeeNumerator: new BN(100_000_000),
+      firstFactor: 0,
+      secondFactor: new BN(0),
+      thirdFactor: new BN(0),
+      baseFeeMode: 0,
+    };
+    instructionParams.activationType = 1; // timestamp
+    // partner and creator quote fee, up to 5% of the buy per hour
+    instructionParams.buybackFeeSource = 2;
+    instructionParams.buybackMaxQuoteAmountPerPeriod = instructionParams
+      .migrationQuoteThreshold.divn(10)
+      .muln(5)
+      .divn(100);
+    instructionParams.buybackPeriod = new BN(3600);
+
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    config = await createConfig(svm, program, params);
+
+    virtualPool = await createPoolWithSplToken(svm, program, {
+      poolCreator,
+      payer: operator,
+      quoteMint,
+      config,
+      instructionParams: {
+        name: "test token spl",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+  });
+
+  it("Buy back and burn with partner and creator quote fee", async () => {
+    const buyAmount = instructionParams.migrationQuoteThreshold.divn(10);
+    mintSplTokenTo(
+      svm,
+      user,
+      quoteMint,
+      admin,
+      user.publicKey,
+      buyAmount.toNumber()
+    );
+
+    let virtualPoolState = getVirtualPool(svm, program, virtualPool);
+    await swap2(svm, program, {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: quoteMint,
+      outputTokenMint: virtualPoolState.baseMint,
+      amount0: buyAmount,
+      amount1: new BN(0),
+      referralTokenAccount: null,
+      swapMode: SwapMode.ExactIn,
+    });
+
+    virtualPoolState = getVirtualPool(svm, program, virtualPool);
+    const partnerQuoteFee = virtualPoolState.partnerQuoteFee;
+    const creatorQuoteFee = virtualPoolState.creatorQuoteFee;
+    const buybackQuoteFee = partnerQuoteFee.add(creatorQuoteFee);
+    const quoteAmountPerPeriod =
+      instructionParams.buybackMaxQuoteAmountPerPeriod;
+    expect(partnerQuoteFee.gtn(0)).to.be.true;
+    expect(creatorQuoteFee.gtn(0)).to.be.true;
+    expect(buybackQuoteFee.gt(quoteAmountPerPeriod)).to.be.true;
+
+    // partner and creator quote fee are reserved for the buyback
+    await claimTradingFee(svm, program, {
+      feeClaimer: partner,
+      pool: virtualPool,
+      maxBaseAmount: new BN(0),
+      maxQuoteAmount: partnerQuoteFee,
+    });
+    await claimCreatorTradingFee(svm, program, {
+      creator: poolCreator,
+      pool: virtualPool,
+      maxBaseAmount: new BN(0),
+      maxQuoteAmount: creatorQuoteFee,
+    });
+    virtualPoolState = getVirtualPool(svm, program, virtualPool);
+    expect(virtualPoolState.partnerQuoteFee.toString()).eq(
+      partnerQuoteFee.toString()
+    );
+    expect(virtualPoolState.creatorQuoteFee.toString()).eq(
+      creatorQuoteFee.toString()
+    );
+
+    const preQuoteReserve = virtualPoolState.quoteReserve;
+    const preBaseReserve = virtualPoolState.baseReserve;
+    const preSupply = new BN(
+      getMint(svm, virtualPoolState.baseMint).supply.toString()
+    );
+
+    const buybackParams = {
+      pool: virtualPool,
+      payer: operator,
+      maxQuoteAmount: buybackQuoteFee,
+    };
+
+    await expectThrowsAsync(async () => {
+      await buybackAndBurn(svm, program, {
+        ...buybackParams,
+        maxQuoteAmount: new BN(0),
+      });
+    }, getDbcProgramErrorCodeHexString("AmountIsZero"));
+
+    // the price limit must be above the current price
+    await expectThrowsAsync(async () => {
+      await buybackAndBurn(svm, program, {
+        ...buybackParams,
+        sqrtPriceLimit: virtualPoolState.sqrtPrice,
+      });
+    }, getDbcProgramErrorCodeHexString("InvalidSqrtPriceLimit"));
+
+    // the first buyback spends the amount of the period, partner fee first
+    await buybackAndBurn(svm, program, buybackParams);
+    virtualPoolState = getVirtualPool(svm, program, virtualPool);
+    expect(virtualPoolState.partnerQuoteFee.toString()).eq("0");
+    expect(virtualPoolState.creatorQuoteFee.toString()).eq(
+      buybackQuoteFee.sub(quoteAmountPerPeriod).toString()
+    );
+    expect(virtualPoolState.buybackQuoteAmountInPeriod.toString()).eq(
+      quoteAmountPerPeriod.toString()
+    );
+    const firstBurnedAmount = virtualPoolState.totalBurnedBaseAmount;
+    expect(firstBurnedAmount.gtn(0)).to.be.true;
+
+    // nothing left to spend until the next period
+    await expectThrowsAsync(async () => {
+      await buybackAndBurn(svm, program, buybackParams);
+    }, getDbcProgramErrorCodeHexString("AmountIsZero"));
+
+    warpTimestampBy(svm, 3600);
+    await buybackAndBurn(svm, program, buybackParams);
+
+    virtualPoolState = getVirtualPool(svm, program, virtualPool);
+    const burnedAmount = virtualPoolState.totalBurnedBaseAmount;
+    const postSupply = new BN(
+      getMint(svm, virtualPoolState.baseMint).supply.toString()
+    );
+
+    expect(burnedAmount.gt(firstBurnedAmount)).to.be.true;
+    expect(virtualPoolState.creatorQuoteFee.toString()).eq("0");
+    expect(virtualPoolState.quoteReserve.toString()).eq(
+      preQuoteReserve.add(buybackQuoteFee).toString()
+    );
+    expect(virtualPoolState.baseReserve.toString()).eq(
+      preBaseReserve.sub(burnedAmount).toString()
+    );
+    expect(postSupply.toString()).eq(preSupply.sub(burnedAmount).toString());
+
+    // nothing left to buy back
+    warpTimestampBy(svm, 3600);
+    await expectThrowsAsync(async () => {
+      await buybackAndBurn(svm, program, buybackParams);
+    }, getDbcProgramErrorCodeHexString("AmountIsZero"));
+  });
+});
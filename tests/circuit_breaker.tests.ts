@@ -0,0 +1,176 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  ConfigParameters,
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+  DynamicFee,
+  swap2,
+  SwapMode,
+} from "./instructions";
+import {
+  createVirtualCurveProgram,
+  designCurve,
+  expectThrowsAsync,
+  generateAndFund,
+  getDbcProgramErrorCodeHexString,
+  startSvm,
+  warpTimestampBy,
+} from "./utils";
+import { getVirtualPool } from "./utils/fetcher";
+import { VirtualCurveProgram } from "./utils/types";
+
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { createToken, mintSplTokenTo } from "./utils/token";
+
+describe("Volatility circuit breaker", () => {
+  let svm: LiteSVM;
+  let admin: Keypair;
+  let operator: Keypair;
+  let partner: Keypair;
+  let user: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let quoteMint: PublicKey;
+  let instructionParams: ConfigParameters;
+
+  const dynamicFee: DynamicFee = {
+    binStep: 1,
+    binStepU128: new BN("1844674407370955"),
+    filterPeriod: 10,
+    decayPeriod: 120,
+    reductionFactor: 5000,
+    maxVolatilityAccumulator: 14460000,
+    variableFeeControl: 100_000,
+    circuitBreakerVolatilityAccumulator: 100_000,
+  };
+
+  before(async () => {
+    svm = startSvm();
+    admin = generateAndFund(svm);
+    operator = generateAndFund(svm);
+    partner = generateAndFund(svm);
+    user = generateAndFund(svm);
+    poolCreator = generateAndFund(svm);
+    program = createVirtualCurveProgram();
+
+    let lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    quoteMint = createToken(svm, admin, admin.publicKey, 9);
+    instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      0,
+      6,
+      9,
+      0,
+      0,
+      lockedVesting,
+      {
+        feePercentage: 0,
+        creatorFeePercentage: 0,
+      }
+    );
+  });
+
+  it("Reject unreachable circuit breaker threshold", async () => {
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams: {
+        ...instructionParams,
+        poolFees: {
+          ...instructionParams.poolFees,
+          dynamicFee: {
+            ...dynamicFee,
+            circuitBreakerVolatilityAccumulator:
+              dynamicFee.maxVolatilityAccumulator,
+          },
+        },
+      },
+    };
+    await expectThrowsAsync(async () => {
+      await createConfig(svm, program, params);
+    }, getDbcProgramErrorCodeHexString("InvalidInput"));
+  });
+
+  it("Halt swaps until the volatility decays", async () => {
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams: {
+        ...instructionParams,
+        poolFees: {
+          ...instructionParams.poolFees,
+          dynamicFee,
+        },
+      },
+    };
+    const config = await createConfig(svm, program, params);
+
+    const virtualPool = await createPoolWithSplToken(svm, program, {
+      poolCreator,
+      payer: operator,
+      quoteMint,
+      config,
+      instructionParams: {
+        name: "test token spl",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+    const virtualPoolState = getVirtualPool(svm, program, virtualPool);
+
+    const buyAmount = instructionParams.migrationQuoteThreshold.divn(20);
+    mintSplTokenTo(
+      svm,
+      user,
+      quoteMint,
+      admin,
+      user.publicKey,
+      buyAmount.muln(3).toNumber()
+    );
+
+    const buy = async () => {
+      await swap2(svm, program, {
+        config,
+        payer: user,
+        pool: virtualPool,
+        inputTokenMint: quoteMint,
+        outputTokenMint: virtualPoolState.baseMint,
+        amount0: buyAmount,
+        amount1: new BN(0),
+        referralTokenAccount: null,
+        swapMode: SwapMode.ExactIn,
+      });
+    };
+
+    // the first buy moves the price over the threshold
+    await buy();
+    const volatilityAccumulator = getVirtualPool(svm, program, virtualPool)
+      .volatilityTracker.volatilityAccumulator;
+    expect(
+      volatilityAccumulator.gtn(dynamicFee.circuitBreakerVolatilityAccumulator)
+    ).to.be.true;
+
+    await expectThrowsAsync(async () => {
+      await buy();
+    }, getDbcProgramErrorCodeHexString("CircuitBreakerTriggered"));
+
+    // volatility is reset after the decay period
+    warpTimestampBy(svm, dynamicFee.decayPeriod);
+    await buy();
+  });
+});
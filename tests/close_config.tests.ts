@@ -0,0 +1,242 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  closeConfig,
+  ConfigParameters,
+  createAllowedCreator,
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+  proposeFeeClaimer,
+  retireConfig,
+} from "./instructions";
+import {
+  createVirtualCurveProgram,
+  deriveConfigExtensionAddress,
+  deriveFeeClaimerTransferAddress,
+  designCurve,
+  expectThrowsAsync,
+  generateAndFund,
+  getDbcProgramErrorCodeHexString,
+  startSvm,
+} from "./utils";
+import { getConfig } from "./utils/fetcher";
+import { VirtualCurveProgram } from "./utils/types";
+
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { createToken } from "./utils/token";
+
+describe("Retire and close config", () => {
+  let svm: LiteSVM;
+  let admin: Keypair;
+  let operator: Keypair;
+  let partner: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let quoteMint: PublicKey;
+  let params: CreateConfigParams<ConfigParameters>;
+
+  before(async () => {
+    svm = startSvm();
+    admin = generateAndFund(svm);
+    operator = generateAndFund(svm);
+    partner = generateAndFund(svm);
+    poolCreator = generateAndFund(svm);
+    program = createVirtualCurveProgram();
+
+    let lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    quoteMint = createToken(svm, admin, admin.publicKey, 9);
+    const instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      0,
+      6,
+      9,
+      0,
+      0,
+      lockedVesting,
+      {
+        feePercentage: 0,
+        creatorFeePercentage: 0,
+      }
+    );
+
+    params = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+  });
+
+  const createPoolParams = (config: PublicKey) => ({
+    poolCreator,
+    payer: operator,
+    quoteMint,
+    config,
+    instructionParams: {
+      name: "test token spl",
+      symbol: "TEST",
+      uri: "abc.com",
+    },
+  });
+
+  it("Close a config without pools", async () => {
+    const config = await createConfig(svm, program, params);
+    const configLamports = svm.getAccount(config).lamports;
+
+    // only the fee claimer can close
+    const otherPartner = generateAndFund(svm);
+    await expectThrowsAsync(async () => {
+      await closeConfig(svm, program, {
+        config,
+        feeClaimer: otherPartner,
+        rentReceiver: otherPartner.publicKey,
+      });
+    }, getDbcProgramErrorCodeHexString("Unauthorized"));
+
+    const rentReceiver = Keypair.generate().publicKey;
+    await closeConfig(svm, program, {
+      config,
+      feeClaimer: partner,
+      rentReceiver,
+    });
+    expect(svm.getAccount(config)).null;
+    expect(svm.getBalance(rentReceiver).toString()).eq(
+      configLamports.toString()
+    );
+  });
+
+  it("Can not close a config with a pool", async () => {
+    const config = await createConfig(svm, program, params);
+    await createPoolWithSplToken(svm, program, createPoolParams(config));
+    expect(getConfig(svm, program, config).poolCount).eq(1);
+
+    await expectThrowsAsync(async () => {
+      await closeConfig(svm, program, {
+        config,
+        feeClaimer: partner,
+        rentReceiver: partner.publicKey,
+      });
+    }, getDbcProgramErrorCodeHexString("ConfigInUse"));
+
+    // retiring only blocks new pools
+    await retireConfig(svm, program, { config, feeClaimer: partner });
+    await expectThrowsAsync(async () => {
+      await closeConfig(svm, program, {
+        config,
+        feeClaimer: partner,
+        rentReceiver: partner.publicKey,
+      });
+    }, getDbcProgramErrorCodeHexString("ConfigInUse"));
+  });
+
+  it("Can not create pools on a retired config", async () => {
+    const config = await createConfig(svm, program, params);
+    await createPoolWithSplToken(svm, program, createPoolParams(config));
+
+    // only the fee claimer can retire
+    await expectThrowsAsync(async () => {
+      await retireConfig(svm, program, {
+        config,
+        feeClaimer: generateAndFund(svm),
+      });
+    }, getDbcProgramErrorCodeHexString("Unauthorized"));
+    await retireConfig(svm, program, { config, feeClaimer: partner });
+    expect(getConfig(svm, program, config).retiredFlag).eq(1);
+
+    await expectThrowsAsync(async () => {
+      await createPoolWithSplToken(svm, program, {
+        ...createPoolParams(config),
+        poolCreator: operator,
+      });
+    }, getDbcProgramErrorCodeHexString("ConfigRetired"));
+  });
+
+  it("Close the accounts of the config together with the config", async () => {
+    const config = await createConfig(svm, program, {
+      ...params,
+      instructionParams: {
+        ...params.instructionParams,
+        enableCreatorAllowlist: true,
+      },
+    });
+    const allowedCreator = await createAllowedCreator(svm, program, {
+      config,
+      creator: poolCreator.publicKey,
+      feeClaimer: partner,
+      payer: partner,
+    });
+    await proposeFeeClaimer(svm, program, {
+      config,
+      newFeeClaimer: Keypair.generate().publicKey,
+      newLeftoverReceiver: Keypair.generate().publicKey,
+      feeClaimer: partner,
+      payer: partner,
+    });
+    // the rent of the fee claimer transfer goes back to its payer
+    await expectThrowsAsync(async () => {
+      await closeConfig(svm, program, {
+        config,
+        feeClaimer: partner,
+        rentReceiver: operator.publicKey,
+        withFeeClaimerTransfer: true,
+      });
+    }, getDbcProgramErrorCodeHexString("InvalidAccount"));
+
+    await closeConfig(svm, program, {
+      config,
+      feeClaimer: partner,
+      rentReceiver: partner.publicKey,
+      withFeeClaimerTransfer: true,
+      allowedCreators: [allowedCreator],
+    });
+    expect(svm.getAccount(config)).null;
+    expect(svm.getAccount(allowedCreator)).null;
+    expect(svm.getAccount(deriveFeeClaimerTransferAddress(config))).null;
+  });
+
+  it("Close the config extension together with the config", async () => {
+    const config = await createConfig(svm, program, {
+      ...params,
+      instructionParams: {
+        ...params.instructionParams,
+        poolFees: {
+          ...params.instructionParams.poolFees,
+          sellBaseFee: {
+            cliffFeeNumerator: new BN(100_000_000),
+            firstFactor: 0,
+            secondFactor: new BN(0),
+            thirdFactor: new BN(0),
+            baseFeeMode: 0,
+          },
+        },
+      },
+    });
+    await expectThrowsAsync(async () => {
+      await closeConfig(svm, program, {
+        config,
+        feeClaimer: partner,
+        rentReceiver: partner.publicKey,
+      });
+    }, getDbcProgramErrorCodeHexString("MissingConfigExtension"));
+
+    await closeConfig(svm, program, {
+      config,
+      feeClaimer: partner,
+      rentReceiver: partner.publicKey,
+      withConfigExtension: true,
+    });
+    expect(svm.getAccount(config)).null;
+    expect(svm.getAccount(deriveConfigExtensionAddress(config))).null;
+  });
+});
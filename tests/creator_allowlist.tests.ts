@@ -0,0 +1,171 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  closeAllowedCreator,
+  ConfigParameters,
+  createAllowedCreator,
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+  CreatePoolSplTokenParams,
+} from "./instructions";
+import {
+  createVirtualCurveProgram,
+  designCurve,
+  expectThrowsAsync,
+  generateAndFund,
+  getDbcProgramErrorCodeHexString,
+  startSvm,
+} from "./utils";
+import { getConfig } from "./utils/fetcher";
+import { VirtualCurveProgram } from "./utils/types";
+
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { createToken } from "./utils/token";
+
+describe("Creator allowlist", () => {
+  let svm: LiteSVM;
+  let admin: Keypair;
+  let operator: Keypair;
+  let partner: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let quoteMint: PublicKey;
+  let config: PublicKey;
+  let instructionParams: ConfigParameters;
+
+  before(async () => {
+    svm = startSvm();
+    admin = generateAndFund(svm);
+    operator = generateAndFund(svm);
+    partner = generateAndFund(svm);
+    poolCreator = generateAndFund(svm);
+    program = createVirtualCurveProgram();
+
+    let lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    quoteMint = createToken(svm, admin, admin.publicKey, 9);
+    instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      0,
+      6,
+      9,
+      0,
+      0,
+      lockedVesting,
+      {
+        feePercentage: 0,
+        creatorFeePercentage: 0,
+      }
+    );
+
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams: {
+        ...instructionParams,
+        enableCreatorAllowlist: true,
+      },
+    };
+    config = await createConfig(svm, program, params);
+    expect(getConfig(svm, program, config).creatorAllowlistFlag).eq(1);
+  });
+
+  const getCreatePoolParams = (): CreatePoolSplTokenParams => ({
+    poolCreator,
+    payer: operator,
+    quoteMint,
+    config,
+    instructionParams: {
+      name: "test token spl",
+      symbol: "TEST",
+      uri: "abc.com",
+    },
+  });
+
+  it("Reject pool creator that is not allowed", async () => {
+    await expectThrowsAsync(async () => {
+      await createPoolWithSplToken(svm, program, getCreatePoolParams());
+    }, getDbcProgramErrorCodeHexString("UnauthorizedPoolCreator"));
+  });
+
+  it("Create pool with the fee claimer co-signature", async () => {
+    await createPoolWithSplToken(svm, program, {
+      ...getCreatePoolParams(),
+      feeClaimer: partner,
+    });
+  });
+
+  it("Create pool as allowed creator until it is removed", async () => {
+    // only the fee claimer can allow creators
+    await expectThrowsAsync(async () => {
+      await createAllowedCreator(svm, program, {
+        config,
+        creator: poolCreator.publicKey,
+        feeClaimer: poolCreator,
+        payer: poolCreator,
+      });
+    }, getDbcProgramErrorCodeHexString("Unauthorized"));
+
+    const allowedCreator = await createAllowedCreator(svm, program, {
+      config,
+      creator: poolCreator.publicKey,
+      feeClaimer: partner,
+      payer: partner,
+    });
+    await createPoolWithSplToken(svm, program, {
+      ...getCreatePoolParams(),
+      allowedCreator,
+    });
+
+    // the allowed creator account of another creator doesn't work
+    await expectThrowsAsync(async () => {
+      await createPoolWithSplToken(svm, program, {
+        ...getCreatePoolParams(),
+        poolCreator: operator,
+        allowedCreator,
+      });
+    }, getDbcProgramErrorCodeHexString("UnauthorizedPoolCreator"));
+
+    await closeAllowedCreator(svm, program, {
+      config,
+      creator: poolCreator.publicKey,
+      feeClaimer: partner,
+    });
+    expect(svm.getAccount(allowedCreator)).null;
+  });
+
+  it("Can not allow creators on config without creator allowlist", async () => {
+    const otherConfig = await createConfig(svm, program, {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    });
+    await expectThrowsAsync(async () => {
+      await createAllowedCreator(svm, program, {
+        config: otherConfig,
+        creator: poolCreator.publicKey,
+        feeClaimer: partner,
+        payer: partner,
+      });
+    }, getDbcProgramErrorCodeHexString("CreatorAllowlistNotEnabled"));
+
+    // anyone can create pools
+    await createPoolWithSplToken(svm, program, {
+      ...getCreatePoolParams(),
+      config: otherConfig,
+    });
+  });
+});
@@ -0,0 +1,192 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  claimCreatorTradingFee,
+  ConfigParameters,
+  createConfig,
+  CreateConfigParams,
+  createCreatorFeeShare,
+  createPoolWithSplToken,
+  distributeCreatorFee,
+  swap2,
+  SwapMode,
+} from "./instructions";
+import {
+  createVirtualCurveProgram,
+  designCurve,
+  expectThrowsAsync,
+  generateAndFund,
+  getDbcProgramErrorCodeHexString,
+  getTokenAccount,
+  startSvm,
+} from "./utils";
+import { getCreatorFeeShare, getVirtualPool } from "./utils/fetcher";
+import { VirtualCurveProgram } from "./utils/types";
+
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { createToken, mintSplTokenTo } from "./utils/token";
+
+describe("Creator fee share", () => {
+  let svm: LiteSVM;
+  let admin: Keypair;
+  let operator: Keypair;
+  let partner: Keypair;
+  let user: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let quoteMint: PublicKey;
+  let virtualPool: PublicKey;
+  let instructionParams: ConfigParameters;
+
+  before(async () => {
+    svm = startSvm();
+    admin = generateAndFund(svm);
+    operator = generateAndFund(svm);
+    partner = generateAndFund(svm);
+    user = generateAndFund(svm);
+    poolCreator = generateAndFund(svm);
+    program = createVirtualCurveProgram();
+
+    let lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    quoteMint = createToken(svm, admin, admin.publicKey, 9);
+    instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      0,
+      6,
+      9,
+      100, // all trading fee goes to the creator
+      0,
+      lockedVesting,
+      {
+        feePercentage: 0,
+        creatorFeePercentage: 0,
+      }
+    );
+    // 10% flat base fee
+    instructionParams.poolFees.baseFee = {
+      cliffFeeNumerator: new BN(100_000_000),
+      firstFactor: 0,
+      secondFactor: new BN(0),
+      thirdFactor: new BN(0),
+      baseFeeMode: 0,
+    };
+
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    const config = await createConfig(svm, program, params);
+
+    virtualPool = await createPoolWithSplToken(svm, program, {
+      poolCreator,
+      payer: operator,
+      quoteMint,
+      config,
+      instructionParams: {
+        name: "test token spl",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+  });
+
+  it("Reject recipients not adding up to 100%", async () => {
+    await expectThrowsAsync(async () => {
+      await createCreatorFeeShare(svm, program, {
+        pool: virtualPool,
+        creator: poolCreator,
+        recipients: [
+          { recipient: Keypair.generate().publicKey, weightBps: 3_000 },
+          { recipient: Keypair.generate().publicKey, weightBps: 6_000 },
+        ],
+      });
+    }, getDbcProgramErrorCodeHexString("InvalidCreatorFeeShare"));
+  });
+
+  it("Distribute creator trading fee to recipients", async () => {
+    const recipientA = Keypair.generate().publicKey;
+    const recipientB = Keypair.generate().publicKey;
+    const creatorFeeShare = await createCreatorFeeShare(svm, program, {
+      pool: virtualPool,
+      creator: poolCreator,
+      recipients: [
+        { recipient: recipientA, weightBps: 3_000 },
+        { recipient: recipientB, weightBps: 7_000 },
+      ],
+    });
+    const creatorFeeShareState = getCreatorFeeShare(
+      svm,
+      program,
+      creatorFeeShare
+    );
+    expect(creatorFeeShareState.pool.toString()).eq(virtualPool.toString());
+    expect(creatorFeeShareState.recipients[1].weightBps).eq(7_000);
+
+    const buyAmount = instructionParams.migrationQuoteThreshold.divn(10);
+    mintSplTokenTo(
+      svm,
+      user,
+      quoteMint,
+      admin,
+      user.publicKey,
+      buyAmount.toNumber()
+    );
+    let virtualPoolState = getVirtualPool(svm, program, virtualPool);
+    await swap2(svm, program, {
+      config: virtualPoolState.config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: quoteMint,
+      outputTokenMint: virtualPoolState.baseMint,
+      amount0: buyAmount,
+      amount1: new BN(0),
+      referralTokenAccount: null,
+      swapMode: SwapMode.ExactIn,
+    });
+
+    virtualPoolState = getVirtualPool(svm, program, virtualPool);
+    const creatorQuoteFee = virtualPoolState.creatorQuoteFee;
+    expect(creatorQuoteFee.gtn(0)).to.be.true;
+
+    // the creator can't claim the trading fee by itself anymore
+    await expectThrowsAsync(async () => {
+      await claimCreatorTradingFee(svm, program, {
+        creator: poolCreator,
+        pool: virtualPool,
+        maxBaseAmount: new BN(0),
+        maxQuoteAmount: creatorQuoteFee,
+      });
+    }, getDbcProgramErrorCodeHexString("CreatorFeeShareEnabled"));
+
+    await distributeCreatorFee(svm, program, virtualPool, operator);
+
+    virtualPoolState = getVirtualPool(svm, program, virtualPool);
+    expect(virtualPoolState.creatorQuoteFee.toString()).eq("0");
+
+    const getQuoteBalance = (recipient: PublicKey) =>
+      new BN(
+        getTokenAccount(
+          svm,
+          getAssociatedTokenAddressSync(quoteMint, recipient, true)
+        )!.amount.toString()
+      );
+    const amountA = creatorQuoteFee.muln(3_000).divn(10_000);
+    expect(getQuoteBalance(recipientA).toString()).eq(amountA.toString());
+    expect(getQuoteBalance(recipientB).toString()).eq(
+      creatorQuoteFee.sub(amountA).toString()
+    );
+  });
+});
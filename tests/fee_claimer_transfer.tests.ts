@@ -0,0 +1,171 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  acceptFeeClaimer,
+  cancelFeeClaimerTransfer,
+  ConfigParameters,
+  createConfig,
+  CreateConfigParams,
+  proposeFeeClaimer,
+} from "./instructions";
+import {
+  createVirtualCurveProgram,
+  deriveFeeClaimerTransferAddress,
+  designCurve,
+  expectThrowsAsync,
+  generateAndFund,
+  getDbcProgramErrorCodeHexString,
+  startSvm,
+} from "./utils";
+import { getConfig, getFeeClaimerTransfer } from "./utils/fetcher";
+import { VirtualCurveProgram } from "./utils/types";
+
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { createToken } from "./utils/token";
+
+describe("Fee claimer transfer", () => {
+  let svm: LiteSVM;
+  let admin: Keypair;
+  let partner: Keypair;
+  let newPartner: Keypair;
+  let program: VirtualCurveProgram;
+  let config: PublicKey;
+
+  before(async () => {
+    svm = startSvm();
+    admin = generateAndFund(svm);
+    partner = generateAndFund(svm);
+    newPartner = generateAndFund(svm);
+    program = createVirtualCurveProgram();
+
+    let lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    const quoteMint = createToken(svm, admin, admin.publicKey, 9);
+    const instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      0,
+      6,
+      9,
+      0,
+      0,
+      lockedVesting,
+      {
+        feePercentage: 0,
+        creatorFeePercentage: 0,
+      }
+    );
+
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    config = await createConfig(svm, program, params);
+  });
+
+  it("Cancel a proposed transfer", async () => {
+    const feeClaimerTransfer = await proposeFeeClaimer(svm, program, {
+      config,
+      newFeeClaimer: Keypair.generate().publicKey,
+      newLeftoverReceiver: Keypair.generate().publicKey,
+      feeClaimer: partner,
+      payer: partner,
+    });
+    expect(svm.getAccount(feeClaimerTransfer)).not.null;
+
+    // the rent goes back to the payer
+    await expectThrowsAsync(async () => {
+      await cancelFeeClaimerTransfer(svm, program, {
+        config,
+        feeClaimer: partner,
+        rentReceiver: Keypair.generate().publicKey,
+      });
+    }, getDbcProgramErrorCodeHexString("InvalidAccount"));
+
+    await cancelFeeClaimerTransfer(svm, program, {
+      config,
+      feeClaimer: partner,
+      rentReceiver: partner.publicKey,
+    });
+    expect(svm.getAccount(deriveFeeClaimerTransferAddress(config))).null;
+  });
+
+  it("Transfer fee claimer and leftover receiver to a new key", async () => {
+    const newLeftoverReceiver = Keypair.generate().publicKey;
+
+    // only the fee claimer can propose
+    await expectThrowsAsync(async () => {
+      await proposeFeeClaimer(svm, program, {
+        config,
+        newFeeClaimer: newPartner.publicKey,
+        newLeftoverReceiver,
+        feeClaimer: newPartner,
+        payer: newPartner,
+      });
+    }, getDbcProgramErrorCodeHexString("Unauthorized"));
+
+    const feeClaimerTransfer = await proposeFeeClaimer(svm, program, {
+      config,
+      newFeeClaimer: newPartner.publicKey,
+      newLeftoverReceiver,
+      feeClaimer: partner,
+      payer: partner,
+    });
+    const feeClaimerTransferState = getFeeClaimerTransfer(
+      svm,
+      program,
+      feeClaimerTransfer
+    );
+    expect(feeClaimerTransferState.newFeeClaimer.toString()).eq(
+      newPartner.publicKey.toString()
+    );
+
+    // roles don't change until the new fee claimer accepts
+    let configState = getConfig(svm, program, config);
+    expect(configState.feeClaimer.toString()).eq(partner.publicKey.toString());
+
+    await expectThrowsAsync(async () => {
+      await acceptFeeClaimer(svm, program, {
+        config,
+        newFeeClaimer: newPartner,
+        rentReceiver: newPartner.publicKey,
+      });
+    }, getDbcProgramErrorCodeHexString("InvalidAccount"));
+
+    await acceptFeeClaimer(svm, program, {
+      config,
+      newFeeClaimer: newPartner,
+      rentReceiver: partner.publicKey,
+    });
+
+    configState = getConfig(svm, program, config);
+    expect(configState.feeClaimer.toString()).eq(
+      newPartner.publicKey.toString()
+    );
+    expect(configState.leftoverReceiver.toString()).eq(
+      newLeftoverReceiver.toString()
+    );
+    expect(svm.getAccount(feeClaimerTransfer)).null;
+
+    // the old fee claimer lost the role
+    await expectThrowsAsync(async () => {
+      await proposeFeeClaimer(svm, program, {
+        config,
+        newFeeClaimer: partner.publicKey,
+        newLeftoverReceiver: partner.publicKey,
+        feeClaimer: partner,
+        payer: partner,
+      });
+    }, getDbcProgramErrorCodeHexString("Unauthorized"));
+  });
+});
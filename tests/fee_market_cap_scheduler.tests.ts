@@ -0,0 +1,150 @@
+import { Keypair } from "@solana/web3.js";
+import {
+  ConfigParameters,
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+  swap,
+  SwapMode,
+} from "./instructions";
+import {
+  createVirtualCurveProgram,
+  designCurve,
+  generateAndFund,
+  startSvm,
+} from "./utils";
+import { getVirtualPool } from "./utils/fetcher";
+import { VirtualCurveProgram } from "./utils/types";
+
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { createToken, mintSplTokenTo } from "./utils/token";
+
+describe("Fee market cap scheduler", () => {
+  let svm: LiteSVM;
+  let admin: Keypair;
+  let operator: Keypair;
+  let partner: Keypair;
+  let user: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+
+  before(async () => {
+    svm = startSvm();
+    admin = generateAndFund(svm);
+    operator = generateAndFund(svm);
+    partner = generateAndFund(svm);
+    user = generateAndFund(svm);
+    poolCreator = generateAndFund(svm);
+    program = createVirtualCurveProgram();
+  });
+
+  it("Base fee steps down as sqrt price rises", async () => {
+    let totalTokenSupply = 1_000_000_000; // 1 billion
+    let percentageSupplyOnMigration = 10; // 10%;
+    let migrationQuoteThreshold = 300; // 300 sol
+    let tokenBaseDecimal = 6;
+    let tokenQuoteDecimal = 9;
+    let migrationOption = 0; // damm v1
+    let lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    let collectFeeMode = 0;
+    let cliffFeeNumerator = new BN(100_000_000); // 10%
+    let quoteMint = createToken(svm, admin, admin.publicKey, tokenQuoteDecimal);
+    let instructionParams = designCurve(
+      totalTokenSupply,
+      percentageSupplyOnMigration,
+      migrationQuoteThreshold,
+      migrationOption,
+      tokenBaseDecimal,
+      tokenQuoteDecimal,
+      0,
+      collectFeeMode,
+      lockedVesting,
+      {
+        feePercentage: 0,
+        creatorFeePercentage: 0,
+      },
+      {
+        baseFeeOption: {
+          cliffFeeNumerator,
+          firstFactor: 50, // number of period
+          secondFactor: new BN(100), // sqrt price step 1%
+          thirdFactor: new BN(1_500_000), // reduction factor
+          baseFeeMode: 3, // fee market cap scheduler linear
+        },
+      }
+    );
+
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    let config = await createConfig(svm, program, params);
+    mintSplTokenTo(
+      svm,
+      user,
+      quoteMint,
+      admin,
+      user.publicKey,
+      instructionParams.migrationQuoteThreshold.toNumber()
+    );
+
+    // create pool
+    let virtualPool = await createPoolWithSplToken(svm, program, {
+      poolCreator,
+      payer: operator,
+      quoteMint,
+      config,
+      instructionParams: {
+        name: "test token spl",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+    let virtualPoolState = getVirtualPool(svm, program, virtualPool);
+
+    const amountIn = new BN(1_000_000_000);
+    const buy = async (amountIn: BN) => {
+      virtualPoolState = getVirtualPool(svm, program, virtualPool);
+      const preQuoteFee = virtualPoolState.partnerQuoteFee.add(
+        virtualPoolState.protocolQuoteFee
+      );
+      await swap(svm, program, {
+        config,
+        payer: user,
+        pool: virtualPool,
+        inputTokenMint: quoteMint,
+        outputTokenMint: virtualPoolState.baseMint,
+        amountIn,
+        minimumAmountOut: new BN(0),
+        swapMode: SwapMode.ExactIn,
+        referralTokenAccount: null,
+      });
+      virtualPoolState = getVirtualPool(svm, program, virtualPool);
+      return virtualPoolState.partnerQuoteFee
+        .add(virtualPoolState.protocolQuoteFee)
+        .sub(preQuoteFee);
+    };
+
+    // first buy is at the start price, so it pays the cliff fee
+    const firstFee = await buy(amountIn);
+    expect(firstFee.toString()).eq(
+      amountIn.mul(cliffFeeNumerator).divn(1_000_000_000).toString()
+    );
+
+    // push the price up, then the same buy pays less fee
+    await buy(amountIn.muln(50));
+    const secondFee = await buy(amountIn);
+    expect(secondFee.lt(firstFee)).to.be.true;
+  });
+});
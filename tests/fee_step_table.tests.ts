@@ -0,0 +1,195 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  ConfigParameters,
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+  FeeStep,
+  swap2,
+  SwapMode,
+} from "./instructions";
+import {
+  createVirtualCurveProgram,
+  deriveConfigExtensionAddress,
+  designCurve,
+  expectThrowsAsync,
+  generateAndFund,
+  getCurrentSlot,
+  getDbcProgramErrorCodeHexString,
+  startSvm,
+  warpSlotBy,
+} from "./utils";
+import { getConfig, getConfigExtension, getVirtualPool } from "./utils/fetcher";
+import { VirtualCurveProgram } from "./utils/types";
+
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { createToken, mintSplTokenTo } from "./utils/token";
+
+describe("Fee step table", () => {
+  let svm: LiteSVM;
+  let admin: Keypair;
+  let operator: Keypair;
+  let partner: Keypair;
+  let user: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let quoteMint: PublicKey;
+  let instructionParams: ConfigParameters;
+
+  // 50% for 10 slots, 10% for 100 slots, 1% forever
+  const feeSteps: FeeStep[] = [
+    { elapsedPoint: new BN(0), feeNumerator: new BN(500_000_000) },
+    { elapsedPoint: new BN(10), feeNumerator: new BN(100_000_000) },
+    { elapsedPoint: new BN(110), feeNumerator: new BN(10_000_000) },
+  ];
+
+  before(async () => {
+    svm = startSvm();
+    admin = generateAndFund(svm);
+    operator = generateAndFund(svm);
+    partner = generateAndFund(svm);
+    user = generateAndFund(svm);
+    poolCreator = generateAndFund(svm);
+    program = createVirtualCurveProgram();
+
+    let lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    quoteMint = createToken(svm, admin, admin.publicKey, 9);
+    instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      0,
+      6,
+      9,
+      0,
+      0,
+      lockedVesting,
+      {
+        feePercentage: 0,
+        creatorFeePercentage: 0,
+      }
+    );
+    // base fee is derived from the fee steps
+    instructionParams.poolFees.baseFee = {
+      cliffFeeNumerator: new BN(0),
+      firstFactor: 0,
+      secondFactor: new BN(0),
+      thirdFactor: new BN(0),
+      baseFeeMode: 5,
+    };
+  });
+
+  it("Reject unsorted fee steps", async () => {
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams: {
+        ...instructionParams,
+        feeSteps: [feeSteps[0], feeSteps[2], feeSteps[1]],
+      },
+    };
+    await expectThrowsAsync(async () => {
+      await createConfig(svm, program, params);
+    }, getDbcProgramErrorCodeHexString("InvalidFeeStepTable"));
+  });
+
+  it("Charge the fee of the current step", async () => {
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams: {
+        ...instructionParams,
+        feeSteps,
+      },
+    };
+    const config = await createConfig(svm, program, params);
+
+    const configState = getConfig(svm, program, config);
+    const baseFee = configState.poolFees.baseFee;
+    expect(baseFee.cliffFeeNumerator.toString()).eq("500000000");
+    expect(baseFee.firstFactor).eq(feeSteps.length);
+    expect(baseFee.thirdFactor.toString()).eq("10000000");
+
+    const configExtension = deriveConfigExtensionAddress(config);
+    const configExtensionState = getConfigExtension(
+      svm,
+      program,
+      configExtension
+    );
+    expect(configExtensionState.feeSteps[1].elapsedPoint.toString()).eq("10");
+    expect(configExtensionState.feeSteps[1].feeNumerator.toString()).eq(
+      "100000000"
+    );
+
+    const virtualPool = await createPoolWithSplToken(svm, program, {
+      poolCreator,
+      payer: operator,
+      quoteMint,
+      config,
+      instructionParams: {
+        name: "test token spl",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+    const virtualPoolState = getVirtualPool(svm, program, virtualPool);
+
+    const buyAmount = instructionParams.migrationQuoteThreshold.divn(20);
+    mintSplTokenTo(
+      svm,
+      user,
+      quoteMint,
+      admin,
+      user.publicKey,
+      buyAmount.muln(3).toNumber()
+    );
+
+    const getTotalQuoteFee = () => {
+      const state = getVirtualPool(svm, program, virtualPool);
+      return state.partnerQuoteFee
+        .add(state.protocolQuoteFee)
+        .add(state.creatorQuoteFee);
+    };
+
+    const buy = async (configExtension?: PublicKey) => {
+      const preTotalQuoteFee = getTotalQuoteFee();
+      await swap2(svm, program, {
+        config,
+        payer: user,
+        pool: virtualPool,
+        inputTokenMint: quoteMint,
+        outputTokenMint: virtualPoolState.baseMint,
+        amount0: buyAmount,
+        amount1: new BN(0),
+        referralTokenAccount: null,
+        configExtension,
+        swapMode: SwapMode.ExactIn,
+      });
+      return getTotalQuoteFee().sub(preTotalQuoteFee);
+    };
+
+    // the fee steps are read from the config extension
+    await expectThrowsAsync(async () => {
+      await buy();
+    }, getDbcProgramErrorCodeHexString("MissingConfigExtension"));
+
+    const firstStepFee = await buy(configExtension);
+    expect(firstStepFee.eq(buyAmount.divn(2))).to.be.true;
+
+    warpSlotBy(svm, getCurrentSlot(svm).addn(10));
+    const secondStepFee = await buy(configExtension);
+    expect(secondStepFee.eq(buyAmount.divn(10))).to.be.true;
+  });
+});
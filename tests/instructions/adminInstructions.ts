@@ -16,6 +16,7 @@ import {
   deriveClaimFeeOperatorAddress,
   deriveOperatorAddress,
   derivePoolAuthority,
+  deriveProtocolConfigAddress,
 } from "../utils/accounts";
 import { VirtualCurveProgram } from "../utils/types";
 import BN from "bn.js";
@@ -55,6 +56,60 @@ export async function createOperatorAccount(
   sendTransactionMaybeThrow(svm, transaction, [admin]);
 }
 
+export type ProtocolConfigParams = {
+  protocolFeePercent: number;
+  referralFeePercent: number;
+  protocolPoolCreationFeePercent: number;
+};
+
+export async function createProtocolConfig(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  params: {
+    admin: Keypair;
+    partner: PublicKey;
+    protocolConfigParams: ProtocolConfigParams;
+  }
+): Promise<PublicKey> {
+  const { admin, partner, protocolConfigParams } = params;
+  const protocolConfig = deriveProtocolConfigAddress(partner);
+
+  const transaction = await program.methods
+    .createProtocolConfig(partner, protocolConfigParams)
+    .accountsPartial({
+      protocolConfig,
+      signer: admin.publicKey,
+      payer: admin.publicKey,
+    })
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [admin]);
+
+  return protocolConfig;
+}
+
+export async function updateProtocolConfig(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  params: {
+    admin: Keypair;
+    protocolConfig: PublicKey;
+    protocolConfigParams: ProtocolConfigParams;
+  }
+) {
+  const { admin, protocolConfig, protocolConfigParams } = params;
+
+  const transaction = await program.methods
+    .updateProtocolConfig(protocolConfigParams)
+    .accountsPartial({
+      protocolConfig,
+      signer: admin.publicKey,
+    })
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [admin]);
+}
+
 export type ClaimLegacyPoolCreationFeeParams = {
   operator: Keypair;
   pool: PublicKey;
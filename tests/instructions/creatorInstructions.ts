@@ -4,9 +4,16 @@ import {
   TOKEN_2022_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
-import { Keypair, PublicKey, TransactionInstruction } from "@solana/web3.js";
+import {
+  AccountMeta,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  TransactionInstruction,
+} from "@solana/web3.js";
 import { LiteSVM } from "litesvm";
 import {
+  deriveCreatorFeeShareAddress,
   deriveMigrationMetadataAddress,
   derivePoolAuthority,
   getOrCreateAssociatedTokenAccount,
@@ -15,7 +22,11 @@ import {
   sendTransactionMaybeThrow,
   unwrapSOLInstruction,
 } from "../utils";
-import { getConfig, getVirtualPool } from "../utils/fetcher";
+import {
+  getConfig,
+  getCreatorFeeShare,
+  getVirtualPool,
+} from "../utils/fetcher";
 import { VirtualCurveProgram } from "../utils/types";
 
 export type ClaimCreatorTradeFeeParams = {
@@ -173,6 +184,103 @@ export async function transferCreator(
   sendTransactionMaybeThrow(svm, transaction, [creator]);
 }
 
+export type CreatorFeeShareRecipient = {
+  recipient: PublicKey;
+  weightBps: number;
+};
+
+export async function createCreatorFeeShare(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  params: {
+    pool: PublicKey;
+    creator: Keypair;
+    recipients: CreatorFeeShareRecipient[];
+  }
+): Promise<PublicKey> {
+  const { pool, creator, recipients } = params;
+  const creatorFeeShare = deriveCreatorFeeShareAddress(pool);
+  const transaction = await program.methods
+    .createCreatorFeeShare(recipients)
+    .accountsPartial({
+      pool,
+      creatorFeeShare,
+      creator: creator.publicKey,
+      payer: creator.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [creator]);
+
+  return creatorFeeShare;
+}
+
+export async function distributeCreatorFee(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  pool: PublicKey,
+  payer: Keypair
+) {
+  const poolState = getVirtualPool(svm, program, pool);
+  const configState = getConfig(svm, program, poolState.config);
+  const creatorFeeShare = deriveCreatorFeeShareAddress(pool);
+  const creatorFeeShareState = getCreatorFeeShare(
+    svm,
+    program,
+    creatorFeeShare
+  );
+
+  const quoteMint = getTokenAccount(svm, poolState.quoteVault)!.mint;
+  const tokenBaseProgram = getTokenProgram(configState.tokenType);
+  const tokenQuoteProgram = getTokenProgram(configState.quoteTokenFlag);
+
+  const preInstructions: TransactionInstruction[] = [];
+  const remainingAccounts: AccountMeta[] = [];
+  for (const { recipient, weightBps } of creatorFeeShareState.recipients) {
+    if (weightBps == 0) {
+      break;
+    }
+    for (const [mint, tokenProgram] of [
+      [poolState.baseMint, tokenBaseProgram],
+      [quoteMint, tokenQuoteProgram],
+    ]) {
+      const { ata, ix } = getOrCreateAssociatedTokenAccount(
+        svm,
+        payer,
+        mint,
+        recipient,
+        tokenProgram
+      );
+      ix && preInstructions.push(ix);
+      remainingAccounts.push({
+        isSigner: false,
+        isWritable: true,
+        pubkey: ata,
+      });
+    }
+  }
+
+  const transaction = await program.methods
+    .distributeCreatorFee()
+    .accountsPartial({
+      poolAuthority: derivePoolAuthority(),
+      pool,
+      creatorFeeShare,
+      baseVault: poolState.baseVault,
+      quoteVault: poolState.quoteVault,
+      baseMint: poolState.baseMint,
+      quoteMint,
+      tokenBaseProgram,
+      tokenQuoteProgram,
+    })
+    .remainingAccounts(remainingAccounts)
+    .preInstructions(preInstructions)
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [payer]);
+}
+
 export type CreatorWithdrawMigrationFeeParams = {
   creator: Keypair;
   virtualPool: PublicKey;
@@ -14,8 +14,15 @@ import { expect } from "chai";
 import { LiteSVM } from "litesvm";
 import {
   createVirtualCurveProgram,
+  deriveAllowedCreatorAddress,
+  deriveConfigExtensionAddress,
+  deriveFeeClaimerTransferAddress,
   derivePartnerMetadata,
   derivePoolAuthority,
+  derivePresaleAllowlistAddress,
+  deriveProtocolConfigAddress,
+  deriveSwapHookAddress,
+  deriveVolumeFeeTiersAddress,
   getOrCreateAssociatedTokenAccount,
   getTokenAccount,
   getTokenProgram,
@@ -25,6 +32,8 @@ import {
 import {
   getConfig,
   getPartnerMetadata,
+  getPresaleAllowlist,
+  getSwapHook,
   getVirtualPool,
 } from "../utils/fetcher";
 import { VirtualCurveProgram } from "../utils/types";
@@ -45,6 +54,7 @@ export type DynamicFee = {
   reductionFactor: number;
   maxVolatilityAccumulator: number;
   variableFeeControl: number;
+  circuitBreakerVolatilityAccumulator: number;
 };
 
 export type LockedVestingParams = {
@@ -77,10 +87,17 @@ export type MigratedPoolMarketCapFeeSchedulerParams = {
   reductionFactor: BN;
 };
 
+export type FeeStep = {
+  elapsedPoint: BN;
+  feeNumerator: BN;
+};
+
 export type ConfigParameters = {
   poolFees: {
     baseFee: BaseFee;
     dynamicFee: DynamicFee | null;
+    sellBaseFee?: BaseFee | null;
+    rateLimiterMode?: number;
   };
   collectFeeMode: number;
   migrationOption: number;
@@ -111,6 +128,18 @@ export type ConfigParameters = {
   creatorLiquidityVestingInfo: LiquidityVestingInfoParams;
   enableFirstSwapWithMinFee: boolean;
   compoundingFeeBps: number;
+  maxBuyAmountPerWallet?: BN;
+  buyCapDuration?: number;
+  allowlistDuration?: number;
+  // leaf = sha256(0 || wallet || allocation_le), set with allowlistDuration
+  allowlistMerkleRoot?: Buffer;
+  feeSteps?: FeeStep[];
+  curveType?: number;
+  enableCreatorAllowlist?: boolean;
+  // 0 partner, 1 creator, 2 partner and creator, set with collectFeeMode 2
+  buybackFeeSource?: number;
+  buybackMaxQuoteAmountPerPeriod?: BN;
+  buybackPeriod?: BN;
   curve: Array<LiquidityDistributionParameters>;
 };
 
@@ -128,6 +157,10 @@ export type CreateConfigParams<T> = {
   feeClaimer: PublicKey;
   quoteMint: PublicKey;
   instructionParams: T;
+  // fee claimer signature, required when admin created a protocol config for it
+  feeClaimerSigner?: Keypair;
+  // hook program invoked after every swap on the config's pools
+  hookProgram?: PublicKey;
 };
 
 export async function createConfig(
@@ -135,8 +168,15 @@ export async function createConfig(
   program: VirtualCurveProgram,
   params: CreateConfigParams<ConfigParameters>
 ): Promise<PublicKey> {
-  const { payer, leftoverReceiver, feeClaimer, quoteMint, instructionParams } =
-    params;
+  const {
+    payer,
+    leftoverReceiver,
+    feeClaimer,
+    quoteMint,
+    instructionParams,
+    feeClaimerSigner,
+    hookProgram,
+  } = params;
   const config = Keypair.generate();
 
   if (instructionParams.migratedPoolMarketCapFeeSchedulerParams == null) {
@@ -148,10 +188,33 @@ export async function createConfig(
     };
   }
 
+  const sellBaseFee = instructionParams.poolFees.sellBaseFee ?? null;
+  const feeSteps = instructionParams.feeSteps ?? [];
+  const buyCapDuration = instructionParams.buyCapDuration ?? 0;
+  const allowlistDuration = instructionParams.allowlistDuration ?? 0;
+  const isBuybackBurn = instructionParams.collectFeeMode == 2;
+  const allowlistMerkleRoot =
+    instructionParams.allowlistMerkleRoot ?? Buffer.alloc(32);
   const transaction = await program.methods
     .createConfig({
       ...instructionParams,
-      padding: new Array(2).fill(0),
+      poolFees: {
+        ...instructionParams.poolFees,
+        sellBaseFee,
+        rateLimiterMode: instructionParams.poolFees.rateLimiterMode ?? 0,
+      },
+      maxBuyAmountPerWallet:
+        instructionParams.maxBuyAmountPerWallet ?? new BN(0),
+      buyCapDuration,
+      allowlistDuration,
+      allowlistMerkleRoot: Array.from(allowlistMerkleRoot),
+      feeSteps,
+      curveType: instructionParams.curveType ?? 0,
+      enableCreatorAllowlist: instructionParams.enableCreatorAllowlist ?? false,
+      buybackFeeSource: instructionParams.buybackFeeSource ?? 0,
+      buybackMaxQuoteAmountPerPeriod:
+        instructionParams.buybackMaxQuoteAmountPerPeriod ?? new BN(0),
+      buybackPeriod: instructionParams.buybackPeriod ?? new BN(0),
     })
     .accountsPartial({
       config: config.publicKey,
@@ -159,13 +222,54 @@ export async function createConfig(
       leftoverReceiver,
       quoteMint,
       payer: payer.publicKey,
+      configExtension:
+        sellBaseFee ||
+        feeSteps.length > 0 ||
+        buyCapDuration > 0 ||
+        isBuybackBurn
+          ? deriveConfigExtensionAddress(config.publicKey)
+          : null,
+      protocolConfig: deriveProtocolConfigAddress(feeClaimer),
+      globalProtocolConfig: deriveProtocolConfigAddress(PublicKey.default),
+      swapHook: hookProgram ? deriveSwapHookAddress(config.publicKey) : null,
+      hookProgram: hookProgram ?? null,
+      presaleAllowlist:
+        allowlistDuration > 0
+          ? derivePresaleAllowlistAddress(config.publicKey)
+          : null,
     })
     .transaction();
 
-  sendTransactionMaybeThrow(svm, transaction, [payer, config]);
+  const signers = [payer, config];
+  if (feeClaimerSigner) {
+    transaction.instructions[0].keys
+      .filter((key) => key.pubkey.equals(feeClaimer))
+      .forEach((key) => (key.isSigner = true));
+    signers.push(feeClaimerSigner);
+  }
+  sendTransactionMaybeThrow(svm, transaction, signers);
   //
   const configState = getConfig(svm, program, config.publicKey);
   // TODO add assertion data fields
+  if (hookProgram) {
+    const swapHookState = getSwapHook(
+      svm,
+      program,
+      deriveSwapHookAddress(config.publicKey)
+    );
+    expect(swapHookState.hookProgram.toString()).equal(hookProgram.toString());
+    expect(configState.swapHookFlag).equal(1);
+  }
+  if (allowlistDuration > 0) {
+    const presaleAllowlistState = getPresaleAllowlist(
+      svm,
+      program,
+      derivePresaleAllowlistAddress(config.publicKey)
+    );
+    expect(
+      Buffer.from(presaleAllowlistState.merkleRoot).equals(allowlistMerkleRoot)
+    ).to.be.true;
+  }
   expect(configState.quoteMint.toString()).equal(quoteMint.toString());
   expect(configState.partnerLiquidityPercentage).equal(
     instructionParams.partnerLiquidityPercentage
@@ -222,6 +326,280 @@ export async function createPartnerMetadata(
   expect(metadataState.logo.toString()).equal(logo.toString());
 }
 
+export type VolumeFeeTier = {
+  minVolume: BN;
+  feeDiscountBps: number;
+};
+
+export async function createVolumeFeeTiers(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  params: {
+    config: PublicKey;
+    tiers: VolumeFeeTier[];
+    feeClaimer: Keypair;
+    payer: Keypair;
+  }
+): Promise<PublicKey> {
+  const { config, tiers, feeClaimer, payer } = params;
+  const volumeFeeTiers = deriveVolumeFeeTiersAddress(config);
+  const transaction = await program.methods
+    .createVolumeFeeTiers(tiers)
+    .accountsPartial({
+      config,
+      volumeFeeTiers,
+      feeClaimer: feeClaimer.publicKey,
+      payer: payer.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [payer, feeClaimer]);
+
+  return volumeFeeTiers;
+}
+
+export async function updateVolumeFeeTiers(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  params: {
+    config: PublicKey;
+    tiers: VolumeFeeTier[];
+    feeClaimer: Keypair;
+  }
+) {
+  const { config, tiers, feeClaimer } = params;
+  const transaction = await program.methods
+    .updateVolumeFeeTiers(tiers)
+    .accountsPartial({
+      config,
+      volumeFeeTiers: deriveVolumeFeeTiersAddress(config),
+      feeClaimer: feeClaimer.publicKey,
+    })
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [feeClaimer]);
+}
+
+export async function closeVolumeFeeTiers(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  params: {
+    config: PublicKey;
+    feeClaimer: Keypair;
+  }
+) {
+  const { config, feeClaimer } = params;
+  const transaction = await program.methods
+    .closeVolumeFeeTiers()
+    .accountsPartial({
+      config,
+      volumeFeeTiers: deriveVolumeFeeTiersAddress(config),
+      feeClaimer: feeClaimer.publicKey,
+      rentReceiver: feeClaimer.publicKey,
+    })
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [feeClaimer]);
+}
+
+export async function proposeFeeClaimer(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  params: {
+    config: PublicKey;
+    newFeeClaimer: PublicKey;
+    newLeftoverReceiver: PublicKey;
+    feeClaimer: Keypair;
+    payer: Keypair;
+  }
+): Promise<PublicKey> {
+  const { config, newFeeClaimer, newLeftoverReceiver, feeClaimer, payer } =
+    params;
+  const feeClaimerTransfer = deriveFeeClaimerTransferAddress(config);
+  const transaction = await program.methods
+    .proposeFeeClaimer()
+    .accountsPartial({
+      config,
+      feeClaimerTransfer,
+      newFeeClaimer,
+      newLeftoverReceiver,
+      feeClaimer: feeClaimer.publicKey,
+      payer: payer.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [payer, feeClaimer]);
+
+  return feeClaimerTransfer;
+}
+
+export async function cancelFeeClaimerTransfer(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  params: {
+    config: PublicKey;
+    feeClaimer: Keypair;
+    rentReceiver: PublicKey;
+  }
+) {
+  const { config, feeClaimer, rentReceiver } = params;
+  const transaction = await program.methods
+    .cancelFeeClaimerTransfer()
+    .accountsPartial({
+      config,
+      feeClaimerTransfer: deriveFeeClaimerTransferAddress(config),
+      feeClaimer: feeClaimer.publicKey,
+      rentReceiver,
+    })
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [feeClaimer]);
+}
+
+export async function acceptFeeClaimer(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  params: {
+    config: PublicKey;
+    newFeeClaimer: Keypair;
+    rentReceiver: PublicKey;
+  }
+) {
+  const { config, newFeeClaimer, rentReceiver } = params;
+  const transaction = await program.methods
+    .acceptFeeClaimer()
+    .accountsPartial({
+      config,
+      feeClaimerTransfer: deriveFeeClaimerTransferAddress(config),
+      newFeeClaimer: newFeeClaimer.publicKey,
+      rentReceiver,
+    })
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [newFeeClaimer]);
+}
+
+export async function retireConfig(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  params: {
+    config: PublicKey;
+    feeClaimer: Keypair;
+  }
+) {
+  const { config, feeClaimer } = params;
+  const transaction = await program.methods
+    .retireConfig()
+    .accountsPartial({
+      config,
+      feeClaimer: feeClaimer.publicKey,
+    })
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [feeClaimer]);
+}
+
+export async function closeConfig(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  params: {
+    config: PublicKey;
+    feeClaimer: Keypair;
+    rentReceiver: PublicKey;
+    withConfigExtension?: boolean;
+    withSwapHook?: boolean;
+    withPresaleAllowlist?: boolean;
+    withVolumeFeeTiers?: boolean;
+    withFeeClaimerTransfer?: boolean;
+    allowedCreators?: PublicKey[];
+  }
+) {
+  const { config, feeClaimer, rentReceiver } = params;
+  const transaction = await program.methods
+    .closeConfig()
+    .accountsPartial({
+      config,
+      configExtension: params.withConfigExtension
+        ? deriveConfigExtensionAddress(config)
+        : null,
+      swapHook: params.withSwapHook ? deriveSwapHookAddress(config) : null,
+      presaleAllowlist: params.withPresaleAllowlist
+        ? derivePresaleAllowlistAddress(config)
+        : null,
+      volumeFeeTiers: params.withVolumeFeeTiers
+        ? deriveVolumeFeeTiersAddress(config)
+        : null,
+      feeClaimerTransfer: params.withFeeClaimerTransfer
+        ? deriveFeeClaimerTransferAddress(config)
+        : null,
+      feeClaimer: feeClaimer.publicKey,
+      rentReceiver,
+    })
+    .remainingAccounts(
+      (params.allowedCreators ?? []).map((pubkey) => ({
+        pubkey,
+        isSigner: false,
+        isWritable: true,
+      }))
+    )
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [feeClaimer]);
+}
+
+export async function createAllowedCreator(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  params: {
+    config: PublicKey;
+    creator: PublicKey;
+    feeClaimer: Keypair;
+    payer: Keypair;
+  }
+): Promise<PublicKey> {
+  const { config, creator, feeClaimer, payer } = params;
+  const allowedCreator = deriveAllowedCreatorAddress(config, creator);
+  const transaction = await program.methods
+    .createAllowedCreator()
+    .accountsPartial({
+      config,
+      allowedCreator,
+      creator,
+      feeClaimer: feeClaimer.publicKey,
+      payer: payer.publicKey,
+    })
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [feeClaimer, payer]);
+
+  return allowedCreator;
+}
+
+export async function closeAllowedCreator(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  params: {
+    config: PublicKey;
+    creator: PublicKey;
+    feeClaimer: Keypair;
+  }
+) {
+  const { config, creator, feeClaimer } = params;
+  const transaction = await program.methods
+    .closeAllowedCreator()
+    .accountsPartial({
+      config,
+      allowedCreator: deriveAllowedCreatorAddress(config, creator),
+      feeClaimer: feeClaimer.publicKey,
+      rentReceiver: feeClaimer.publicKey,
+    })
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [feeClaimer]);
+}
+
 export type ClaimTradeFeeParams = {
   feeClaimer: Keypair;
   pool: PublicKey;
@@ -1,10 +1,13 @@
 import { BN } from "@coral-xyz/anchor";
 import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
   NATIVE_MINT,
   TOKEN_2022_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
+  AccountMeta,
   ComputeBudgetProgram,
   Keypair,
   PublicKey,
@@ -25,15 +28,21 @@ import {
   wrapSOLInstruction,
 } from "../utils";
 import {
+  deriveConfigExtensionAddress,
   deriveMetadataAccount,
   derivePoolAddress,
   derivePoolAuthority,
+  derivePoolObservationsAddress,
+  deriveReferrerAddress,
   deriveTokenVaultAddress,
+  deriveWalletBuyTrackerAddress,
+  deriveWalletVolumeTrackerAddress,
 } from "../utils/accounts";
 import {
   getConfig,
   getVirtualPool,
   getVirtualPoolMetadata,
+  getWalletBuyTracker,
 } from "../utils/fetcher";
 import { VirtualCurveProgram } from "../utils/types";
 
@@ -48,6 +57,9 @@ export type CreatePoolSplTokenParams = {
   quoteMint: PublicKey;
   config: PublicKey;
   instructionParams: InitializePoolParameters;
+  // for config with creator allowlist
+  feeClaimer?: Keypair;
+  allowedCreator?: PublicKey;
 };
 
 export type CreatePoolToken2022Params = CreatePoolSplTokenParams;
@@ -91,6 +103,7 @@ export async function createInitializePoolWithSplTokenIx(
       tokenQuoteProgram: TOKEN_PROGRAM_ID,
       tokenProgram,
     })
+    .remainingAccounts(getPoolCreatorRemainingAccounts(params))
     .instruction();
 
   return {
@@ -108,7 +121,7 @@ export async function createPoolWithSplToken(
   const { instruction, pool, baseMintKP } =
     await createInitializePoolWithSplTokenIx(svm, program, params);
 
-  const { payer, poolCreator } = params;
+  const { payer, poolCreator, feeClaimer } = params;
 
   const transaction = new Transaction();
   transaction.recentBlockhash = svm.latestBlockhash();
@@ -120,17 +133,50 @@ export async function createPoolWithSplToken(
     instruction
   );
 
-  sendTransactionMaybeThrow(svm, transaction, [payer, baseMintKP, poolCreator]);
+  const signers = [payer, baseMintKP, poolCreator];
+  if (feeClaimer) {
+    signers.push(feeClaimer);
+  }
+  sendTransactionMaybeThrow(svm, transaction, signers);
 
   return pool;
 }
 
+function getPoolCreatorRemainingAccounts(
+  params: CreatePoolSplTokenParams
+): AccountMeta[] {
+  const { feeClaimer, allowedCreator } = params;
+  const remainingAccounts: AccountMeta[] = [];
+  if (feeClaimer) {
+    remainingAccounts.push({
+      pubkey: feeClaimer.publicKey,
+      isSigner: true,
+      isWritable: false,
+    });
+  }
+  if (allowedCreator) {
+    remainingAccounts.push({
+      pubkey: allowedCreator,
+      isSigner: false,
+      isWritable: false,
+    });
+  }
+  return remainingAccounts;
+}
+
 export async function createPoolWithToken2022(
   svm: LiteSVM,
   program: VirtualCurveProgram,
   params: CreatePoolToken2022Params
 ): Promise<PublicKey> {
-  const { payer, quoteMint, config, instructionParams, poolCreator } = params;
+  const {
+    payer,
+    quoteMint,
+    config,
+    instructionParams,
+    poolCreator,
+    feeClaimer,
+  } = params;
 
   const poolAuthority = derivePoolAuthority();
   const baseMintKP = Keypair.generate();
@@ -152,6 +198,7 @@ export async function createPoolWithToken2022(
       tokenQuoteProgram: TOKEN_PROGRAM_ID,
       tokenProgram: TOKEN_2022_PROGRAM_ID,
     })
+    .remainingAccounts(getPoolCreatorRemainingAccounts(params))
     .transaction();
 
   transaction.add(
@@ -160,7 +207,11 @@ export async function createPoolWithToken2022(
     })
   );
 
-  sendTransactionMaybeThrow(svm, transaction, [payer, baseMintKP, poolCreator]);
+  const signers = [payer, baseMintKP, poolCreator];
+  if (feeClaimer) {
+    signers.push(feeClaimer);
+  }
+  sendTransactionMaybeThrow(svm, transaction, signers);
 
   return pool;
 }
@@ -183,6 +234,11 @@ export type SwapParams = {
   referralTokenAccount: PublicKey | null;
 };
 
+export type AllowlistProof = {
+  allocation: BN;
+  proof: Buffer[];
+};
+
 export type SwapParams2 = {
   config: PublicKey;
   payer: Keypair;
@@ -192,6 +248,21 @@ export type SwapParams2 = {
   amount0: BN;
   amount1: BN;
   swapMode: number;
+  sqrtPriceLimit?: BN;
+  expiryPoint?: BN;
+  walletBuyTracker?: PublicKey;
+  presaleAllowlist?: PublicKey;
+  allowlistProof?: AllowlistProof;
+  useNativeSol?: boolean;
+  configExtension?: PublicKey;
+  swapHook?: PublicKey;
+  hookProgram?: PublicKey;
+  hookAccounts?: AccountMeta[];
+  referrer?: PublicKey;
+  referrerTokenAccount?: PublicKey;
+  walletVolumeTracker?: PublicKey;
+  volumeFeeTiers?: PublicKey;
+  poolObservations?: PublicKey;
   referralTokenAccount: PublicKey | null;
 };
 
@@ -654,6 +725,21 @@ export async function swap2(
     amount1: minimumAmountOut,
     referralTokenAccount,
     swapMode,
+    sqrtPriceLimit,
+    expiryPoint,
+    walletBuyTracker,
+    presaleAllowlist,
+    allowlistProof,
+    useNativeSol,
+    configExtension,
+    swapHook,
+    hookProgram,
+    hookAccounts,
+    referrer,
+    referrerTokenAccount,
+    walletVolumeTracker,
+    volumeFeeTiers,
+    poolObservations,
   } = params;
 
   const poolAuthority = derivePoolAuthority();
@@ -693,31 +779,56 @@ export async function swap2(
       outputTokenProgram
     ),
   ];
-  createInputTokenXIx && preInstructions.push(createInputTokenXIx);
-  createOutputTokenYIx && preInstructions.push(createOutputTokenYIx);
+  // program creates the native SOL token account by itself
+  if (!useNativeSol || !inputTokenMint.equals(NATIVE_MINT)) {
+    createInputTokenXIx && preInstructions.push(createInputTokenXIx);
+  }
+  if (!useNativeSol || !outputTokenMint.equals(NATIVE_MINT)) {
+    createOutputTokenYIx && preInstructions.push(createOutputTokenYIx);
+  }
 
-  if (inputTokenMint.equals(NATIVE_MINT) && !amountIn.isZero()) {
-    const wrapSOLIx = wrapSOLInstruction(
-      payer.publicKey,
-      inputTokenAccount,
-      BigInt(amountIn.toString())
-    );
+  // program wraps and unwraps native SOL by itself
+  if (!useNativeSol) {
+    if (inputTokenMint.equals(NATIVE_MINT) && !amountIn.isZero()) {
+      const wrapSOLIx = wrapSOLInstruction(
+        payer.publicKey,
+        inputTokenAccount,
+        BigInt(amountIn.toString())
+      );
 
-    preInstructions.push(...wrapSOLIx);
-  }
+      preInstructions.push(...wrapSOLIx);
+    }
 
-  if (outputTokenMint.equals(NATIVE_MINT)) {
-    const unrapSOLIx = unwrapSOLInstruction(payer.publicKey);
+    if (outputTokenMint.equals(NATIVE_MINT)) {
+      const unrapSOLIx = unwrapSOLInstruction(payer.publicKey);
 
-    unrapSOLIx && postInstructions.push(unrapSOLIx);
+      unrapSOLIx && postInstructions.push(unrapSOLIx);
+    }
   }
 
-  const transaction = await program.methods
-    .swap2({
-      amount0: amountIn,
-      amount1: minimumAmountOut,
-      swapMode,
-    })
+  const swapParameters = {
+    amount0: amountIn,
+    amount1: minimumAmountOut,
+    swapMode,
+  };
+  const swapParameters3 = {
+    ...swapParameters,
+    sqrtPriceLimit: sqrtPriceLimit ?? new BN(0),
+    expiryPoint: expiryPoint ?? new BN(0),
+    useNativeSol: useNativeSol ?? false,
+  };
+  // swap3 is only needed for the price limit, the expiry or native SOL
+  const isSwap3 = !!sqrtPriceLimit || !!expiryPoint || !!useNativeSol;
+  const swapMethod = allowlistProof
+    ? program.methods.swapWithAllowlistProof(swapParameters3, {
+        allocation: allowlistProof.allocation,
+        proof: allowlistProof.proof.map((node) => Array.from(node)),
+      })
+    : isSwap3
+    ? program.methods.swap3(swapParameters3)
+    : program.methods.swap2(swapParameters);
+
+  const transaction = await swapMethod
     .accountsPartial({
       poolAuthority,
       config,
@@ -741,6 +852,108 @@ export async function swap2(
         isSigner: false,
         isWritable: false,
       },
+      ...(walletBuyTracker
+        ? [
+            {
+              pubkey: walletBuyTracker,
+              isSigner: false,
+              isWritable: true,
+            },
+          ]
+        : []),
+      ...(presaleAllowlist
+        ? [
+            {
+              pubkey: presaleAllowlist,
+              isSigner: false,
+              isWritable: false,
+            },
+          ]
+        : []),
+      ...(useNativeSol
+        ? [
+            {
+              pubkey: SystemProgram.programId,
+              isSigner: false,
+              isWritable: false,
+            },
+            {
+              pubkey: ASSOCIATED_TOKEN_PROGRAM_ID,
+              isSigner: false,
+              isWritable: false,
+            },
+          ]
+        : []),
+      ...(configExtension
+        ? [
+            {
+              pubkey: configExtension,
+              isSigner: false,
+              isWritable: false,
+            },
+          ]
+        : []),
+      ...(referrer
+        ? [
+            {
+              pubkey: referrer,
+              isSigner: false,
+              isWritable: true,
+            },
+            {
+              pubkey: referrerTokenAccount,
+              isSigner: false,
+              isWritable: true,
+            },
+          ]
+        : []),
+      ...(walletVolumeTracker
+        ? [
+            {
+              pubkey: walletVolumeTracker,
+              isSigner: false,
+              isWritable: true,
+            },
+          ]
+        : []),
+      ...(volumeFeeTiers
+        ? [
+            {
+              pubkey: volumeFeeTiers,
+              isSigner: false,
+              isWritable: false,
+            },
+          ]
+        : []),
+      ...(poolObservations
+        ? [
+            {
+              pubkey: poolObservations,
+              isSigner: false,
+              isWritable: true,
+            },
+          ]
+        : []),
+      ...(swapHook
+        ? [
+            {
+              pubkey: swapHook,
+              isSigner: false,
+              isWritable: false,
+            },
+          ]
+        : []),
+      // accounts after the hook program are forwarded to the hook program
+      ...(hookProgram
+        ? [
+            {
+              pubkey: hookProgram,
+              isSigner: false,
+              isWritable: false,
+            },
+            ...(hookAccounts ?? []),
+          ]
+        : []),
     ])
     .transaction();
 
@@ -764,6 +977,118 @@ export async function swap2(
   };
 }
 
+export type SwapBatchLegParams = {
+  config: PublicKey;
+  pool: PublicKey;
+  tradeDirection: number;
+  amount0: BN;
+  amount1: BN;
+  swapMode: number;
+};
+
+export async function swapBatch(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  params: {
+    payer: Keypair;
+    quoteMint: PublicKey;
+    legs: SwapBatchLegParams[];
+  }
+) {
+  const { payer, quoteMint, legs } = params;
+  const poolAuthority = derivePoolAuthority();
+
+  const preInstructions: TransactionInstruction[] = [];
+  const { ata: quoteTokenAccount, ix: createQuoteTokenAccountIx } =
+    getOrCreateAssociatedTokenAccount(
+      svm,
+      payer,
+      quoteMint,
+      payer.publicKey,
+      TOKEN_PROGRAM_ID
+    );
+  createQuoteTokenAccountIx && preInstructions.push(createQuoteTokenAccountIx);
+
+  const remainingAccounts = [
+    {
+      pubkey: SYSVAR_INSTRUCTIONS_PUBKEY,
+      isSigner: false,
+      isWritable: false,
+    },
+  ];
+  // pool observations are found by their pool after the legs
+  const poolObservationsAccounts: AccountMeta[] = [];
+  for (const leg of legs) {
+    const poolState = getVirtualPool(svm, program, leg.pool);
+    const configState = getConfig(svm, program, leg.config);
+    const tokenBaseProgram =
+      configState.tokenType == 0 ? TOKEN_PROGRAM_ID : TOKEN_2022_PROGRAM_ID;
+    const { ata: baseTokenAccount, ix: createBaseTokenAccountIx } =
+      getOrCreateAssociatedTokenAccount(
+        svm,
+        payer,
+        poolState.baseMint,
+        payer.publicKey,
+        tokenBaseProgram
+      );
+    createBaseTokenAccountIx && preInstructions.push(createBaseTokenAccountIx);
+
+    remainingAccounts.push(
+      ...[
+        { pubkey: leg.config, isWritable: false },
+        { pubkey: leg.pool, isWritable: true },
+        { pubkey: poolState.baseVault, isWritable: true },
+        { pubkey: poolState.quoteVault, isWritable: true },
+        { pubkey: poolState.baseMint, isWritable: false },
+        { pubkey: baseTokenAccount, isWritable: true },
+        { pubkey: tokenBaseProgram, isWritable: false },
+      ].map((account) => ({ ...account, isSigner: false }))
+    );
+    // the pool observations are updated when they exist
+    const poolObservations = derivePoolObservationsAddress(leg.pool);
+    if (svm.getAccount(poolObservations)) {
+      poolObservationsAccounts.push({
+        pubkey: poolObservations,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+  }
+  remainingAccounts.push(...poolObservationsAccounts);
+
+  const transaction = await program.methods
+    .swapBatch(
+      legs.map((leg) => ({
+        tradeDirection: leg.tradeDirection,
+        params: {
+          amount0: leg.amount0,
+          amount1: leg.amount1,
+          swapMode: leg.swapMode,
+          sqrtPriceLimit: new BN(0),
+          expiryPoint: new BN(0),
+          useNativeSol: false,
+        },
+      }))
+    )
+    .accountsPartial({
+      poolAuthority,
+      quoteTokenAccount,
+      quoteMint,
+      payer: payer.publicKey,
+      tokenQuoteProgram: TOKEN_PROGRAM_ID,
+    })
+    .preInstructions([
+      ComputeBudgetProgram.setComputeUnitLimit({
+        units: 1_400_000,
+      }),
+      ...preInstructions,
+    ])
+    .remainingAccounts(remainingAccounts)
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [payer]);
+}
+
 export async function swapSimulate(
   svm: LiteSVM,
   program: VirtualCurveProgram,
@@ -876,6 +1201,189 @@ export async function swapSimulate(
   };
 }
 
+export async function createWalletBuyTracker(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  pool: PublicKey,
+  owner: Keypair
+): Promise<PublicKey> {
+  const walletBuyTracker = deriveWalletBuyTrackerAddress(pool, owner.publicKey);
+  const transaction = await program.methods
+    .createWalletBuyTracker()
+    .accountsPartial({
+      pool,
+      walletBuyTracker,
+      owner: owner.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [owner]);
+
+  const walletBuyTrackerState = getWalletBuyTracker(
+    svm,
+    program,
+    walletBuyTracker
+  );
+  expect(walletBuyTrackerState.pool.toString()).equal(pool.toString());
+  expect(walletBuyTrackerState.owner.toString()).equal(
+    owner.publicKey.toString()
+  );
+
+  return walletBuyTracker;
+}
+
+export async function createWalletVolumeTracker(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  config: PublicKey,
+  owner: Keypair
+): Promise<PublicKey> {
+  const walletVolumeTracker = deriveWalletVolumeTrackerAddress(
+    config,
+    owner.publicKey
+  );
+  const transaction = await program.methods
+    .createWalletVolumeTracker()
+    .accountsPartial({
+      config,
+      walletVolumeTracker,
+      owner: owner.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [owner]);
+
+  return walletVolumeTracker;
+}
+
+export async function createReferrer(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  owner: Keypair
+): Promise<PublicKey> {
+  const referrer = deriveReferrerAddress(owner.publicKey);
+  const transaction = await program.methods
+    .createReferrer()
+    .accountsPartial({
+      referrer,
+      owner: owner.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [owner]);
+
+  return referrer;
+}
+
+export async function claimReferralFee(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  params: {
+    owner: Keypair;
+    tokenMint: PublicKey;
+    tokenProgram?: PublicKey;
+  }
+) {
+  const { owner, tokenMint } = params;
+  const tokenProgram = params.tokenProgram ?? TOKEN_PROGRAM_ID;
+  const referrer = deriveReferrerAddress(owner.publicKey);
+
+  const preInstructions: TransactionInstruction[] = [];
+  const { ata: tokenAccount, ix: createTokenAccountIx } =
+    getOrCreateAssociatedTokenAccount(
+      svm,
+      owner,
+      tokenMint,
+      owner.publicKey,
+      tokenProgram
+    );
+  createTokenAccountIx && preInstructions.push(createTokenAccountIx);
+
+  const transaction = await program.methods
+    .claimReferralFee()
+    .accountsPartial({
+      referrer,
+      referrerTokenAccount: getAssociatedTokenAddressSync(
+        tokenMint,
+        referrer,
+        true,
+        tokenProgram
+      ),
+      tokenAccount,
+      tokenMint,
+      owner: owner.publicKey,
+      tokenProgram,
+    })
+    .preInstructions(preInstructions)
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [owner]);
+}
+
+export async function createPoolObservations(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  pool: PublicKey,
+  payer: Keypair
+): Promise<PublicKey> {
+  const poolObservations = derivePoolObservationsAddress(pool);
+  const transaction = await program.methods
+    .createPoolObservations()
+    .accountsPartial({
+      pool,
+      poolObservations,
+      payer: payer.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [payer]);
+
+  return poolObservations;
+}
+
+export async function buybackAndBurn(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  params: {
+    pool: PublicKey;
+    payer: Keypair;
+    maxQuoteAmount: BN;
+    sqrtPriceLimit?: BN;
+  }
+) {
+  const { pool, payer, maxQuoteAmount, sqrtPriceLimit } = params;
+  const poolState = getVirtualPool(svm, program, pool);
+  const configState = getConfig(svm, program, poolState.config);
+  const tokenBaseProgram =
+    configState.tokenType == 0 ? TOKEN_PROGRAM_ID : TOKEN_2022_PROGRAM_ID;
+  const poolObservations = derivePoolObservationsAddress(pool);
+
+  const transaction = await program.methods
+    .buybackAndBurn({
+      maxQuoteAmount,
+      sqrtPriceLimit: sqrtPriceLimit ?? new BN(0),
+    })
+    .accountsPartial({
+      poolAuthority: derivePoolAuthority(),
+      config: poolState.config,
+      configExtension: deriveConfigExtensionAddress(poolState.config),
+      pool,
+      poolObservations: svm.getAccount(poolObservations)
+        ? poolObservations
+        : null,
+      baseVault: poolState.baseVault,
+      baseMint: poolState.baseMint,
+      tokenBaseProgram,
+    })
+    .transaction();
+
+  sendTransactionMaybeThrow(svm, transaction, [payer]);
+}
+
 export async function createVirtualPoolMetadata(
   svm: LiteSVM,
   program: VirtualCurveProgram,
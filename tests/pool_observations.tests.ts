@@ -0,0 +1,187 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  ConfigParameters,
+  createConfig,
+  CreateConfigParams,
+  createPoolObservations,
+  createPoolWithSplToken,
+  swap2,
+  SwapMode,
+  SwapParams2,
+} from "./instructions";
+import {
+  createVirtualCurveProgram,
+  derivePoolObservationsAddress,
+  designCurve,
+  generateAndFund,
+  startSvm,
+  warpTimestampBy,
+} from "./utils";
+import { getPoolObservations, getVirtualPool } from "./utils/fetcher";
+import { VirtualCurveProgram } from "./utils/types";
+
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { createToken, mintSplTokenTo } from "./utils/token";
+
+describe("Pool observations", () => {
+  let svm: LiteSVM;
+  let admin: Keypair;
+  let operator: Keypair;
+  let partner: Keypair;
+  let user: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let quoteMint: PublicKey;
+  let virtualPool: PublicKey;
+  let swapParams: SwapParams2;
+
+  before(async () => {
+    svm = startSvm();
+    admin = generateAndFund(svm);
+    operator = generateAndFund(svm);
+    partner = generateAndFund(svm);
+    user = generateAndFund(svm);
+    poolCreator = generateAndFund(svm);
+    program = createVirtualCurveProgram();
+
+    let lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    quoteMint = createToken(svm, admin, admin.publicKey, 9);
+    const instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      0,
+      6,
+      9,
+      0,
+      0,
+      lockedVesting,
+      {
+        feePercentage: 0,
+        creatorFeePercentage: 0,
+      }
+    );
+
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    const config = await createConfig(svm, program, params);
+
+    const buyAmount = instructionParams.migrationQuoteThreshold.divn(100);
+    mintSplTokenTo(
+      svm,
+      user,
+      quoteMint,
+      admin,
+      user.publicKey,
+      buyAmount.muln(4).toNumber()
+    );
+
+    virtualPool = await createPoolWithSplToken(svm, program, {
+      poolCreator,
+      payer: operator,
+      quoteMint,
+      config,
+      instructionParams: {
+        name: "test token spl",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+    const virtualPoolState = getVirtualPool(svm, program, virtualPool);
+
+    swapParams = {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: quoteMint,
+      outputTokenMint: virtualPoolState.baseMint,
+      amount0: buyAmount,
+      amount1: new BN(0),
+      swapMode: SwapMode.ExactIn,
+      referralTokenAccount: null,
+    };
+  });
+
+  it("Swaps passing the pool observations update them", async () => {
+    // pools don't need observations until they are created
+    await swap2(svm, program, swapParams);
+
+    const poolObservations = await createPoolObservations(
+      svm,
+      program,
+      virtualPool,
+      operator
+    );
+    let sqrtPrice = getVirtualPool(svm, program, virtualPool).sqrtPrice;
+    let poolObservationsState = getPoolObservations(
+      svm,
+      program,
+      poolObservations
+    );
+    expect(poolObservationsState.pool.toString()).eq(virtualPool.toString());
+    expect(poolObservationsState.observationCount).eq(1);
+    expect(poolObservationsState.lastSqrtPrice.eq(sqrtPrice)).to.be.true;
+
+    warpTimestampBy(svm, 60);
+    await swap2(svm, program, { ...swapParams, poolObservations });
+
+    // the price before the swap is accumulated for the elapsed seconds
+    poolObservationsState = getPoolObservations(svm, program, poolObservations);
+    expect(poolObservationsState.observationCount).eq(2);
+    expect(poolObservationsState.latestIndex).eq(1);
+    expect(
+      poolObservationsState.sqrtPriceCumulative.eq(sqrtPrice.muln(60))
+    ).to.be.true;
+    expect(
+      poolObservationsState.observations[1].sqrtPriceCumulative.eq(
+        poolObservationsState.sqrtPriceCumulative
+      )
+    ).to.be.true;
+    sqrtPrice = getVirtualPool(svm, program, virtualPool).sqrtPrice;
+    expect(poolObservationsState.lastSqrtPrice.eq(sqrtPrice)).to.be.true;
+  });
+
+  it("Swaps without the pool observations restart them", async () => {
+    const poolObservations = derivePoolObservationsAddress(virtualPool);
+    const sqrtPriceCumulative = getPoolObservations(
+      svm,
+      program,
+      poolObservations
+    ).sqrtPriceCumulative;
+
+    // the account is optional
+    await swap2(svm, program, swapParams);
+
+    warpTimestampBy(svm, 60);
+    await swap2(svm, program, { ...swapParams, poolObservations });
+
+    // the price path since the last update is unknown, so it isn't accumulated
+    const poolObservationsState = getPoolObservations(
+      svm,
+      program,
+      poolObservations
+    );
+    expect(poolObservationsState.observationCount).eq(1);
+    expect(poolObservationsState.latestIndex).eq(0);
+    expect(poolObservationsState.sqrtPriceCumulative.eq(sqrtPriceCumulative))
+      .to.be.true;
+    expect(
+      poolObservationsState.lastSqrtPrice.eq(
+        getVirtualPool(svm, program, virtualPool).sqrtPrice
+      )
+    ).to.be.true;
+  });
+});
@@ -0,0 +1,238 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { createHash } from "crypto";
+import {
+  ConfigParameters,
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+  createWalletBuyTracker,
+  swap2,
+  SwapMode,
+  SwapParams2,
+} from "./instructions";
+import {
+  createVirtualCurveProgram,
+  derivePresaleAllowlistAddress,
+  designCurve,
+  expectThrowsAsync,
+  generateAndFund,
+  getCurrentSlot,
+  getDbcProgramErrorCodeHexString,
+  startSvm,
+  warpSlotBy,
+} from "./utils";
+import { getVirtualPool, getWalletBuyTracker } from "./utils/fetcher";
+import { VirtualCurveProgram } from "./utils/types";
+
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { createToken, mintSplTokenTo } from "./utils/token";
+
+function hashLeaf(wallet: PublicKey, allocation: BN): Buffer {
+  return createHash("sha256")
+    .update(Buffer.from([0]))
+    .update(wallet.toBuffer())
+    .update(allocation.toArrayLike(Buffer, "le", 8))
+    .digest();
+}
+
+function hashNode(a: Buffer, b: Buffer): Buffer {
+  const [left, right] = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+  return createHash("sha256")
+    .update(Buffer.from([1]))
+    .update(left)
+    .update(right)
+    .digest();
+}
+
+describe("Presale allowlist", () => {
+  let svm: LiteSVM;
+  let admin: Keypair;
+  let operator: Keypair;
+  let partner: Keypair;
+  let user: Keypair;
+  let otherUser: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+
+  before(async () => {
+    svm = startSvm();
+    admin = generateAndFund(svm);
+    operator = generateAndFund(svm);
+    partner = generateAndFund(svm);
+    user = generateAndFund(svm);
+    otherUser = generateAndFund(svm);
+    poolCreator = generateAndFund(svm);
+    program = createVirtualCurveProgram();
+  });
+
+  it("Only allowlisted wallets can buy in allowlist phase", async () => {
+    let totalTokenSupply = 1_000_000_000; // 1 billion
+    let percentageSupplyOnMigration = 10; // 10%;
+    let migrationQuoteThreshold = 300; // 300 sol
+    let tokenBaseDecimal = 6;
+    let tokenQuoteDecimal = 9;
+    let migrationOption = 0; // damm v1
+    let lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    let collectFeeMode = 0;
+    let quoteMint = createToken(svm, admin, admin.publicKey, tokenQuoteDecimal);
+    let instructionParams = designCurve(
+      totalTokenSupply,
+      percentageSupplyOnMigration,
+      migrationQuoteThreshold,
+      migrationOption,
+      tokenBaseDecimal,
+      tokenQuoteDecimal,
+      0,
+      collectFeeMode,
+      lockedVesting,
+      {
+        feePercentage: 0,
+        creatorFeePercentage: 0,
+      }
+    );
+    const allowlistDuration = 1000; // activation type is slot
+    instructionParams.allowlistDuration = allowlistDuration;
+
+    // allowlist: user and 2 random wallets, each with its own allocation
+    const allocationPerWallet =
+      instructionParams.migrationQuoteThreshold.divn(10);
+    const leaves = [
+      hashLeaf(user.publicKey, allocationPerWallet),
+      hashLeaf(Keypair.generate().publicKey, allocationPerWallet.muln(2)),
+      hashLeaf(Keypair.generate().publicKey, allocationPerWallet.muln(3)),
+    ];
+    const node01 = hashNode(leaves[0], leaves[1]);
+    const merkleRoot = hashNode(node01, leaves[2]);
+    const userProof = {
+      allocation: allocationPerWallet,
+      proof: [leaves[1], leaves[2]],
+    };
+
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+
+    // the allowlist is created together with the config
+    await expectThrowsAsync(async () => {
+      await createConfig(svm, program, params);
+    }, getDbcProgramErrorCodeHexString("InvalidAllowlistParameters"));
+
+    instructionParams.allowlistMerkleRoot = merkleRoot;
+    let config = await createConfig(svm, program, params);
+    const presaleAllowlist = derivePresaleAllowlistAddress(config);
+
+    for (const wallet of [user, otherUser]) {
+      mintSplTokenTo(
+        svm,
+        wallet,
+        quoteMint,
+        admin,
+        wallet.publicKey,
+        allocationPerWallet.muln(2).toNumber()
+      );
+    }
+
+    // create pool
+    let virtualPool = await createPoolWithSplToken(svm, program, {
+      poolCreator,
+      payer: operator,
+      quoteMint,
+      config,
+      instructionParams: {
+        name: "test token spl",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+    let virtualPoolState = getVirtualPool(svm, program, virtualPool);
+
+    const walletBuyTracker = await createWalletBuyTracker(
+      svm,
+      program,
+      virtualPool,
+      user
+    );
+
+    const swapParams: SwapParams2 = {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: quoteMint,
+      outputTokenMint: virtualPoolState.baseMint,
+      amount0: allocationPerWallet,
+      amount1: new BN(0),
+      walletBuyTracker,
+      presaleAllowlist,
+      referralTokenAccount: null,
+      swapMode: SwapMode.ExactIn,
+    };
+
+    // buying without proof is rejected in the allowlist phase
+    await expectThrowsAsync(async () => {
+      await swap2(svm, program, swapParams);
+    }, getDbcProgramErrorCodeHexString("AllowlistPhaseActive"));
+
+    // the allocation is part of the leaf
+    swapParams.allowlistProof = {
+      ...userProof,
+      allocation: allocationPerWallet.muln(2),
+    };
+    await expectThrowsAsync(async () => {
+      await swap2(svm, program, swapParams);
+    }, getDbcProgramErrorCodeHexString("InvalidAllowlistProof"));
+
+    swapParams.allowlistProof = userProof;
+    await swap2(svm, program, swapParams);
+
+    const walletBuyTrackerState = getWalletBuyTracker(
+      svm,
+      program,
+      walletBuyTracker
+    );
+    expect(walletBuyTrackerState.allowlistBuyAmount.eq(allocationPerWallet)).to
+      .be.true;
+
+    // any further buy exceeds the allocation
+    swapParams.amount0 = new BN(1);
+    await expectThrowsAsync(async () => {
+      await swap2(svm, program, swapParams);
+    }, getDbcProgramErrorCodeHexString("ExceededAllowlistAllocation"));
+
+    // wallet is not in the allowlist
+    const otherWalletBuyTracker = await createWalletBuyTracker(
+      svm,
+      program,
+      virtualPool,
+      otherUser
+    );
+    const otherSwapParams: SwapParams2 = {
+      ...swapParams,
+      payer: otherUser,
+      walletBuyTracker: otherWalletBuyTracker,
+    };
+    await expectThrowsAsync(async () => {
+      await swap2(svm, program, otherSwapParams);
+    }, getDbcProgramErrorCodeHexString("InvalidAllowlistProof"));
+
+    // public trading starts once the allowlist phase is over
+    warpSlotBy(svm, getCurrentSlot(svm).addn(allowlistDuration + 1));
+    otherSwapParams.allowlistProof = undefined;
+    otherSwapParams.amount0 = allocationPerWallet.muln(2);
+    await swap2(svm, program, otherSwapParams);
+
+    virtualPoolState = getVirtualPool(svm, program, virtualPool);
+    expect(virtualPoolState.quoteReserve.gt(allocationPerWallet)).to.be.true;
+  });
+});
@@ -0,0 +1,183 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  ConfigParameters,
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+  swap2,
+  SwapMode,
+} from "./instructions";
+import {
+  createVirtualCurveProgram,
+  designCurve,
+  expectThrowsAsync,
+  generateAndFund,
+  getDbcProgramErrorCodeHexString,
+  startSvm,
+} from "./utils";
+import { getConfig, getVirtualPool } from "./utils/fetcher";
+import { VirtualCurveProgram } from "./utils/types";
+
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { createToken, mintSplTokenTo } from "./utils/token";
+
+// price 1
+const SQRT_START_PRICE = new BN(1).shln(64);
+// price 9
+const END_SQRT_PRICE = new BN(3).shln(64);
+// 1B tokens with 6 decimals
+const BASE_AMOUNT = new BN("1000000000000000");
+const MIGRATION_QUOTE_THRESHOLD = new BN("2000000000000000");
+
+describe("Price curve", () => {
+  let svm: LiteSVM;
+  let admin: Keypair;
+  let operator: Keypair;
+  let partner: Keypair;
+  let user: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let quoteMint: PublicKey;
+  let instructionParams: ConfigParameters;
+
+  before(async () => {
+    svm = startSvm();
+    admin = generateAndFund(svm);
+    operator = generateAndFund(svm);
+    partner = generateAndFund(svm);
+    user = generateAndFund(svm);
+    poolCreator = generateAndFund(svm);
+    program = createVirtualCurveProgram();
+
+    let lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    quoteMint = createToken(svm, admin, admin.publicKey, 9);
+    instructionParams = {
+      ...designCurve(
+        1_000_000_000,
+        10,
+        300,
+        0,
+        6,
+        9,
+        0,
+        0,
+        lockedVesting,
+        {
+          feePercentage: 0,
+          creatorFeePercentage: 0,
+        }
+      ),
+      tokenSupply: null,
+      migrationQuoteThreshold: MIGRATION_QUOTE_THRESHOLD,
+      sqrtStartPrice: SQRT_START_PRICE,
+      curveType: 1, // linear price
+      curve: [{ sqrtPrice: END_SQRT_PRICE, liquidity: BASE_AMOUNT }],
+    };
+  });
+
+  it("Reject invalid price curve", async () => {
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams: { ...instructionParams, curveType: 3 },
+    };
+    await expectThrowsAsync(async () => {
+      await createConfig(svm, program, params);
+    }, getDbcProgramErrorCodeHexString("InvalidCurveType"));
+
+    // the price curves have a single curve point
+    params.instructionParams = {
+      ...instructionParams,
+      curve: [
+        { sqrtPrice: END_SQRT_PRICE, liquidity: BASE_AMOUNT },
+        { sqrtPrice: END_SQRT_PRICE.muln(2), liquidity: BASE_AMOUNT },
+      ],
+    };
+    await expectThrowsAsync(async () => {
+      await createConfig(svm, program, params);
+    }, getDbcProgramErrorCodeHexString("InvalidCurve"));
+  });
+
+  for (const [name, curveType] of [
+    ["linear", 1],
+    ["exponential", 2],
+  ] as const) {
+    it(`Swap to migration on ${name} price curve`, async () => {
+      const config = await createConfig(svm, program, {
+        payer: partner,
+        leftoverReceiver: partner.publicKey,
+        feeClaimer: partner.publicKey,
+        quoteMint,
+        instructionParams: { ...instructionParams, curveType },
+      });
+      const configState = getConfig(svm, program, config);
+      expect(configState.version).eq(1);
+      expect(configState.curveType).eq(curveType);
+      expect(configState.migrationSqrtPrice.gt(SQRT_START_PRICE)).to.be.true;
+      expect(configState.migrationSqrtPrice.lte(END_SQRT_PRICE)).to.be.true;
+
+      const virtualPool = await createPoolWithSplToken(svm, program, {
+        poolCreator,
+        payer: operator,
+        quoteMint,
+        config,
+        instructionParams: {
+          name: "test token spl",
+          symbol: "TEST",
+          uri: "abc.com",
+        },
+      });
+      const virtualPoolState = getVirtualPool(svm, program, virtualPool);
+
+      const buyAmount = MIGRATION_QUOTE_THRESHOLD.muln(2);
+      mintSplTokenTo(
+        svm,
+        user,
+        quoteMint,
+        admin,
+        user.publicKey,
+        buyAmount.toNumber()
+      );
+
+      const swapParams = {
+        config,
+        payer: user,
+        pool: virtualPool,
+        inputTokenMint: quoteMint,
+        outputTokenMint: virtualPoolState.baseMint,
+        amount0: buyAmount,
+        amount1: new BN(0),
+        referralTokenAccount: null,
+      };
+
+      // can't buy over the migration price with exact input
+      await expectThrowsAsync(async () => {
+        await swap2(svm, program, {
+          ...swapParams,
+          swapMode: SwapMode.ExactIn,
+        });
+      }, getDbcProgramErrorCodeHexString("InsufficientLiquidity"));
+
+      await swap2(svm, program, {
+        ...swapParams,
+        swapMode: SwapMode.PartialFill,
+      });
+      const poolState = getVirtualPool(svm, program, virtualPool);
+      expect(poolState.sqrtPrice.eq(configState.migrationSqrtPrice)).to.be
+        .true;
+      expect(poolState.quoteReserve.gte(MIGRATION_QUOTE_THRESHOLD)).to.be.true;
+      expect(poolState.baseReserve.lt(virtualPoolState.baseReserve)).to.be
+        .true;
+    });
+  }
+});
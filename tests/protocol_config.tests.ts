@@ -0,0 +1,210 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  ConfigParameters,
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+  createProtocolConfig,
+  swap2,
+  SwapMode,
+  updateProtocolConfig,
+} from "./instructions";
+import {
+  createVirtualCurveProgram,
+  designCurve,
+  expectThrowsAsync,
+  generateAndFund,
+  getDbcProgramErrorCodeHexString,
+  startSvm,
+} from "./utils";
+import { getConfig, getProtocolConfig, getVirtualPool } from "./utils/fetcher";
+import { VirtualCurveProgram } from "./utils/types";
+
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { createToken, mintSplTokenTo } from "./utils/token";
+
+describe("Protocol config", () => {
+  let svm: LiteSVM;
+  let admin: Keypair;
+  let operator: Keypair;
+  let partner: Keypair;
+  let otherPartner: Keypair;
+  let user: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let instructionParams: ConfigParameters;
+
+  before(async () => {
+    svm = startSvm();
+    admin = generateAndFund(svm);
+    operator = generateAndFund(svm);
+    partner = generateAndFund(svm);
+    otherPartner = generateAndFund(svm);
+    user = generateAndFund(svm);
+    poolCreator = generateAndFund(svm);
+    program = createVirtualCurveProgram();
+
+    let lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      0,
+      6,
+      9,
+      0,
+      0,
+      lockedVesting,
+      {
+        feePercentage: 0,
+        creatorFeePercentage: 0,
+      }
+    );
+  });
+
+  it("Admin create and update protocol config for a partner", async () => {
+    const protocolConfig = await createProtocolConfig(svm, program, {
+      admin,
+      partner: partner.publicKey,
+      protocolConfigParams: {
+        protocolFeePercent: 10,
+        referralFeePercent: 20,
+        protocolPoolCreationFeePercent: 10,
+      },
+    });
+
+    await updateProtocolConfig(svm, program, {
+      admin,
+      protocolConfig,
+      protocolConfigParams: {
+        protocolFeePercent: 50,
+        referralFeePercent: 0,
+        protocolPoolCreationFeePercent: 5,
+      },
+    });
+
+    const protocolConfigState = getProtocolConfig(svm, program, protocolConfig);
+    expect(protocolConfigState.partner.toString()).eq(
+      partner.publicKey.toString()
+    );
+    expect(protocolConfigState.protocolFeePercent).eq(50);
+    expect(protocolConfigState.referralFeePercent).eq(0);
+    expect(protocolConfigState.protocolPoolCreationFeePercent).eq(5);
+  });
+
+  it("Reject protocol fee percent above 100", async () => {
+    await expectThrowsAsync(async () => {
+      await createProtocolConfig(svm, program, {
+        admin,
+        partner: otherPartner.publicKey,
+        protocolConfigParams: {
+          protocolFeePercent: 101,
+          referralFeePercent: 20,
+          protocolPoolCreationFeePercent: 10,
+        },
+      });
+    }, getDbcProgramErrorCodeHexString("InvalidProtocolConfig"));
+  });
+
+  it("Snapshot protocol config into pool config and split trading fee", async () => {
+    const quoteMint = createToken(svm, admin, admin.publicKey, 9);
+
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: otherPartner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+
+    // the fee claimer must sign to apply its protocol config
+    await expectThrowsAsync(async () => {
+      await createConfig(svm, program, params);
+    }, getDbcProgramErrorCodeHexString("Unauthorized"));
+
+    const config = await createConfig(svm, program, {
+      ...params,
+      feeClaimerSigner: partner,
+    });
+    const configState = getConfig(svm, program, config);
+    expect(configState.version).eq(1);
+    expect(configState.protocolFeePercent).eq(50);
+    expect(configState.referralFeePercent).eq(0);
+    expect(configState.protocolPoolCreationFeePercent).eq(5);
+
+    const buyAmount = instructionParams.migrationQuoteThreshold.divn(10);
+    mintSplTokenTo(
+      svm,
+      user,
+      quoteMint,
+      admin,
+      user.publicKey,
+      buyAmount.toNumber()
+    );
+
+    const virtualPool = await createPoolWithSplToken(svm, program, {
+      poolCreator,
+      payer: operator,
+      quoteMint,
+      config,
+      instructionParams: {
+        name: "test token spl",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+    let virtualPoolState = getVirtualPool(svm, program, virtualPool);
+
+    await swap2(svm, program, {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: quoteMint,
+      outputTokenMint: virtualPoolState.baseMint,
+      amount0: buyAmount,
+      amount1: new BN(0),
+      referralTokenAccount: null,
+      swapMode: SwapMode.ExactIn,
+    });
+
+    virtualPoolState = getVirtualPool(svm, program, virtualPool);
+    const protocolFee = virtualPoolState.protocolQuoteFee;
+    const partnerFee = virtualPoolState.partnerQuoteFee;
+    expect(protocolFee.gtn(0)).to.be.true;
+    // protocol takes 50% of the trading fee, allowing for rounding
+    expect(protocolFee.sub(partnerFee).abs().lten(1)).to.be.true;
+  });
+
+  it("Fall back to the protocol config of all partners", async () => {
+    await createProtocolConfig(svm, program, {
+      admin,
+      partner: PublicKey.default,
+      protocolConfigParams: {
+        protocolFeePercent: 15,
+        referralFeePercent: 10,
+        protocolPoolCreationFeePercent: 20,
+      },
+    });
+
+    // the fee claimer has no protocol config, so it doesn't need to sign
+    const config = await createConfig(svm, program, {
+      payer: otherPartner,
+      leftoverReceiver: otherPartner.publicKey,
+      feeClaimer: Keypair.generate().publicKey,
+      quoteMint: createToken(svm, admin, admin.publicKey, 9),
+      instructionParams,
+    });
+    const configState = getConfig(svm, program, config);
+    expect(configState.protocolFeePercent).eq(15);
+    expect(configState.referralFeePercent).eq(10);
+    expect(configState.protocolPoolCreationFeePercent).eq(20);
+  });
+});
@@ -1,3 +1,4 @@
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
 import { Keypair, Transaction } from "@solana/web3.js";
 import {
   createConfig,
@@ -21,7 +22,7 @@ import { VirtualCurveProgram } from "./utils/types";
 import { BN } from "bn.js";
 import { expect } from "chai";
 import { FailedTransactionMetadata, LiteSVM } from "litesvm";
-import { createToken, mintSplTokenTo } from "./utils/token";
+import { createToken, getTokenAccount, mintSplTokenTo } from "./utils/token";
 
 describe("Rate limiter", () => {
   let svm: LiteSVM;
@@ -300,4 +301,125 @@ describe("Rate limiter", () => {
         ).length
     ).eq(1);
   });
+
+  it("Rate limiter on sells with output token collect fee mode", async () => {
+    let totalTokenSupply = 1_000_000_000; // 1 billion
+    let initialMarketcap = 30; // 30 SOL;
+    let migrationMarketcap = 300; // 300 SOL;
+    let tokenBaseDecimal = 6;
+    let tokenQuoteDecimal = 9;
+    let kFactor = 1.2;
+    let lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    let leftOver = 10_000;
+    let migrationOption = 0;
+    let collectFeeMode = 1; // output token
+    let quoteMint = createToken(svm, admin, admin.publicKey, tokenQuoteDecimal);
+    let referenceAmount = new BN(1_000_000_000);
+    let maxRateLimiterDuration = new BN(10);
+    let instructionParams = designGraphCurve(
+      totalTokenSupply,
+      initialMarketcap,
+      migrationMarketcap,
+      migrationOption,
+      tokenBaseDecimal,
+      tokenQuoteDecimal,
+      0,
+      collectFeeMode,
+      lockedVesting,
+      leftOver,
+      kFactor,
+      {
+        cliffFeeNumerator: new BN(10_000_000), // 100bps
+        firstFactor: 10, // 10 bps
+        secondFactor: maxRateLimiterDuration, // 10 slot
+        thirdFactor: referenceAmount, // 1 sol of quote output
+        baseFeeMode: 2, // rate limiter mode
+      }
+    );
+    instructionParams.poolFees.rateLimiterMode = 1; // sells only
+    let config = await createConfig(svm, program, {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    });
+    mintSplTokenTo(
+      svm,
+      user,
+      quoteMint,
+      admin,
+      user.publicKey,
+      instructionParams.migrationQuoteThreshold.toNumber()
+    );
+
+    // create pool
+    let virtualPool = await createPoolWithSplToken(svm, program, {
+      poolCreator,
+      payer: operator,
+      quoteMint,
+      config,
+      instructionParams: {
+        name: "test token spl",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+    let virtualPoolState = getVirtualPool(svm, program, virtualPool);
+
+    // buy with 5 SOL, the fee is collected in base token with the cliff fee
+    await swap(svm, program, {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: quoteMint,
+      outputTokenMint: virtualPoolState.baseMint,
+      amountIn: referenceAmount.muln(5),
+      minimumAmountOut: new BN(0),
+      swapMode: SwapMode.ExactIn,
+      referralTokenAccount: null,
+    });
+
+    virtualPoolState = getVirtualPool(svm, program, virtualPool);
+    const preQuoteReserve = virtualPoolState.quoteReserve;
+    const preQuoteFee = virtualPoolState.partnerQuoteFee.add(
+      virtualPoolState.protocolQuoteFee
+    );
+    expect(preQuoteFee.isZero()).to.be.true;
+
+    // sell everything back, the quote output is far above the reference amount
+    const userBaseTokenAccount = getAssociatedTokenAddressSync(
+      virtualPoolState.baseMint,
+      user.publicKey
+    );
+    await swap(svm, program, {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: virtualPoolState.baseMint,
+      outputTokenMint: quoteMint,
+      amountIn: new BN(
+        getTokenAccount(svm, userBaseTokenAccount).amount.toString()
+      ),
+      minimumAmountOut: new BN(0),
+      swapMode: SwapMode.ExactIn,
+      referralTokenAccount: null,
+    });
+
+    virtualPoolState = getVirtualPool(svm, program, virtualPool);
+    const quoteOutIncludingFee = preQuoteReserve.sub(
+      virtualPoolState.quoteReserve
+    );
+    const quoteFee = virtualPoolState.partnerQuoteFee
+      .add(virtualPoolState.protocolQuoteFee)
+      .sub(preQuoteFee);
+    expect(quoteOutIncludingFee.gt(referenceAmount.muln(2))).to.be.true;
+    expect(quoteFee.gt(quoteOutIncludingFee.divn(100))).to.be.true;
+  });
 });
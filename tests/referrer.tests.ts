@@ -0,0 +1,297 @@
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  claimReferralFee,
+  ConfigParameters,
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+  createReferrer,
+  LockedVestingParams,
+  swap2,
+  SwapMode,
+} from "./instructions";
+import {
+  createVirtualCurveProgram,
+  designCurve,
+  expectThrowsAsync,
+  generateAndFund,
+  getDbcProgramErrorCodeHexString,
+  startSvm,
+} from "./utils";
+import { getReferrer, getVirtualPool } from "./utils/fetcher";
+import { VirtualCurveProgram } from "./utils/types";
+
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import {
+  createToken,
+  getOrCreateAssociatedTokenAccount,
+  getTokenAccount,
+  mintSplTokenTo,
+} from "./utils/token";
+
+describe("Referrer", () => {
+  let svm: LiteSVM;
+  let admin: Keypair;
+  let operator: Keypair;
+  let partner: Keypair;
+  let user: Keypair;
+  let poolCreator: Keypair;
+  let referrerOwner: Keypair;
+  let program: VirtualCurveProgram;
+  let quoteMint: PublicKey;
+  let virtualPool: PublicKey;
+  let referrer: PublicKey;
+  let config: PublicKey;
+  let buyAmount: BN;
+  let lockedVesting: LockedVestingParams;
+
+  before(async () => {
+    svm = startSvm();
+    admin = generateAndFund(svm);
+    operator = generateAndFund(svm);
+    partner = generateAndFund(svm);
+    user = generateAndFund(svm);
+    poolCreator = generateAndFund(svm);
+    referrerOwner = generateAndFund(svm);
+    program = createVirtualCurveProgram();
+
+    lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    quoteMint = createToken(svm, admin, admin.publicKey, 9);
+    let instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      0,
+      6,
+      9,
+      0,
+      0,
+      lockedVesting,
+      {
+        feePercentage: 0,
+        creatorFeePercentage: 0,
+      }
+    );
+
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    config = await createConfig(svm, program, params);
+
+    buyAmount = instructionParams.migrationQuoteThreshold.divn(10);
+    mintSplTokenTo(
+      svm,
+      user,
+      quoteMint,
+      admin,
+      user.publicKey,
+      buyAmount.toNumber()
+    );
+
+    virtualPool = await createPoolWithSplToken(svm, program, {
+      poolCreator,
+      payer: operator,
+      quoteMint,
+      config,
+      instructionParams: {
+        name: "test token spl",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+  });
+
+  it("Accrue referral fee to referrer and claim in batch", async () => {
+    referrer = await createReferrer(svm, program, referrerOwner);
+    let referrerState = getReferrer(svm, program, referrer);
+    expect(referrerState.owner.toString()).eq(
+      referrerOwner.publicKey.toString()
+    );
+
+    // the referral fee accrues in the referrer token account of the fee mint
+    const referrerQuoteTokenAccount = getOrCreateAssociatedTokenAccount(
+      svm,
+      referrerOwner,
+      quoteMint,
+      referrer
+    );
+    let virtualPoolState = getVirtualPool(svm, program, virtualPool);
+
+    // buy and sell, both with quote fee
+    await swap2(svm, program, {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: quoteMint,
+      outputTokenMint: virtualPoolState.baseMint,
+      amount0: buyAmount,
+      amount1: new BN(0),
+      referralTokenAccount: null,
+      referrer,
+      referrerTokenAccount: referrerQuoteTokenAccount,
+      swapMode: SwapMode.ExactIn,
+    });
+
+    const userBaseTokenAccount = getAssociatedTokenAddressSync(
+      virtualPoolState.baseMint,
+      user.publicKey
+    );
+    const baseBalance = new BN(
+      getTokenAccount(svm, userBaseTokenAccount).amount.toString()
+    );
+    await swap2(svm, program, {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: virtualPoolState.baseMint,
+      outputTokenMint: quoteMint,
+      amount0: baseBalance.divn(2),
+      amount1: new BN(0),
+      referralTokenAccount: null,
+      referrer,
+      referrerTokenAccount: referrerQuoteTokenAccount,
+      swapMode: SwapMode.ExactIn,
+    });
+
+    referrerState = getReferrer(svm, program, referrer);
+    virtualPoolState = getVirtualPool(svm, program, virtualPool);
+    expect(referrerState.totalReferredSwap.toNumber()).eq(2);
+    const accruedQuoteFee = new BN(
+      getTokenAccount(svm, referrerQuoteTokenAccount).amount.toString()
+    );
+    expect(accruedQuoteFee.gtn(0)).to.be.true;
+    // referral takes 20% of the protocol fee, allowing for rounding
+    expect(
+      accruedQuoteFee
+        .muln(4)
+        .sub(virtualPoolState.protocolQuoteFee)
+        .abs()
+        .lten(8)
+    ).to.be.true;
+
+    await claimReferralFee(svm, program, {
+      owner: referrerOwner,
+      tokenMint: quoteMint,
+    });
+
+    const ownerQuoteTokenAccount = getAssociatedTokenAddressSync(
+      quoteMint,
+      referrerOwner.publicKey
+    );
+    expect(getTokenAccount(svm, ownerQuoteTokenAccount).amount.toString()).eq(
+      accruedQuoteFee.toString()
+    );
+    expect(getTokenAccount(svm, referrerQuoteTokenAccount).amount).eq(0n);
+
+    // nothing left to claim
+    await expectThrowsAsync(async () => {
+      await claimReferralFee(svm, program, {
+        owner: referrerOwner,
+        tokenMint: quoteMint,
+      });
+    }, getDbcProgramErrorCodeHexString("AmountIsZero"));
+  });
+
+  it("Accrue referral fee in base token", async () => {
+    // output token collect fee mode, buys pay the fee in base token
+    const baseFeeConfig = await createConfig(svm, program, {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams: designCurve(
+        1_000_000_000,
+        10,
+        300,
+        0,
+        6,
+        9,
+        0,
+        1,
+        lockedVesting,
+        {
+          feePercentage: 0,
+          creatorFeePercentage: 0,
+        }
+      ),
+    });
+    const baseFeePool = await createPoolWithSplToken(svm, program, {
+      poolCreator,
+      payer: operator,
+      quoteMint,
+      config: baseFeeConfig,
+      instructionParams: {
+        name: "test token spl",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+    const virtualPoolState = getVirtualPool(svm, program, baseFeePool);
+    mintSplTokenTo(svm, user, quoteMint, admin, user.publicKey, 1_000_000);
+    const swapParams = {
+      config: baseFeeConfig,
+      payer: user,
+      pool: baseFeePool,
+      inputTokenMint: quoteMint,
+      outputTokenMint: virtualPoolState.baseMint,
+      amount0: new BN(1_000_000),
+      amount1: new BN(0),
+      referralTokenAccount: null,
+      referrer,
+      // the referrer has no token account of the base mint yet
+      referrerTokenAccount: getAssociatedTokenAddressSync(
+        quoteMint,
+        referrer,
+        true
+      ),
+      swapMode: SwapMode.ExactIn,
+    };
+    await expectThrowsAsync(async () => {
+      await swap2(svm, program, swapParams);
+    }, getDbcProgramErrorCodeHexString("MissingReferrerTokenAccount"));
+
+    // the same referrer accrues the base token referral fee of any pool
+    swapParams.referrerTokenAccount = getOrCreateAssociatedTokenAccount(
+      svm,
+      referrerOwner,
+      virtualPoolState.baseMint,
+      referrer
+    );
+    await swap2(svm, program, swapParams);
+    const accruedBaseFee = getTokenAccount(
+      svm,
+      swapParams.referrerTokenAccount
+    ).amount;
+    expect(accruedBaseFee > 0n).to.be.true;
+    expect(
+      getReferrer(svm, program, referrer).totalReferredSwap.toNumber()
+    ).eq(3);
+
+    await claimReferralFee(svm, program, {
+      owner: referrerOwner,
+      tokenMint: virtualPoolState.baseMint,
+    });
+    expect(
+      getTokenAccount(
+        svm,
+        getAssociatedTokenAddressSync(
+          virtualPoolState.baseMint,
+          referrerOwner.publicKey
+        )
+      ).amount
+    ).eq(accruedBaseFee);
+  });
+});
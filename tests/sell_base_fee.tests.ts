@@ -0,0 +1,192 @@
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { Keypair } from "@solana/web3.js";
+import {
+  ConfigParameters,
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+  swap2,
+  SwapMode,
+  SwapParams2,
+} from "./instructions";
+import {
+  createVirtualCurveProgram,
+  deriveConfigExtensionAddress,
+  designCurve,
+  expectThrowsAsync,
+  generateAndFund,
+  getDbcProgramErrorCodeHexString,
+  startSvm,
+} from "./utils";
+import { getConfig, getConfigExtension, getVirtualPool } from "./utils/fetcher";
+import { VirtualCurveProgram } from "./utils/types";
+
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { createToken, getTokenAccount, mintSplTokenTo } from "./utils/token";
+
+describe("Sell base fee", () => {
+  let svm: LiteSVM;
+  let admin: Keypair;
+  let operator: Keypair;
+  let partner: Keypair;
+  let user: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+
+  before(async () => {
+    svm = startSvm();
+    admin = generateAndFund(svm);
+    operator = generateAndFund(svm);
+    partner = generateAndFund(svm);
+    user = generateAndFund(svm);
+    poolCreator = generateAndFund(svm);
+    program = createVirtualCurveProgram();
+  });
+
+  it("Charge sell base fee on base to quote swaps", async () => {
+    let totalTokenSupply = 1_000_000_000; // 1 billion
+    let percentageSupplyOnMigration = 10; // 10%;
+    let migrationQuoteThreshold = 300; // 300 sol
+    let tokenBaseDecimal = 6;
+    let tokenQuoteDecimal = 9;
+    let migrationOption = 0; // damm v1
+    let lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    let collectFeeMode = 0;
+    let quoteMint = createToken(svm, admin, admin.publicKey, tokenQuoteDecimal);
+    let instructionParams = designCurve(
+      totalTokenSupply,
+      percentageSupplyOnMigration,
+      migrationQuoteThreshold,
+      migrationOption,
+      tokenBaseDecimal,
+      tokenQuoteDecimal,
+      0,
+      collectFeeMode,
+      lockedVesting,
+      {
+        feePercentage: 0,
+        creatorFeePercentage: 0,
+      }
+    );
+    // 10% fee on sells, buys keep the default base fee
+    instructionParams.poolFees.sellBaseFee = {
+      cliffFeeNumerator: new BN(100_000_000),
+      firstFactor: 0,
+      secondFactor: new BN(0),
+      thirdFactor: new BN(0),
+      baseFeeMode: 0,
+    };
+
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    let config = await createConfig(svm, program, params);
+    const configExtension = deriveConfigExtensionAddress(config);
+
+    const configState = getConfig(svm, program, config);
+    expect(configState.sellBaseFeeFlag).eq(1);
+    const configExtensionState = getConfigExtension(
+      svm,
+      program,
+      configExtension
+    );
+    expect(configExtensionState.config.toString()).eq(config.toString());
+    expect(
+      configExtensionState.sellBaseFee.cliffFeeNumerator.toString()
+    ).eq("100000000");
+
+    const buyAmount = instructionParams.migrationQuoteThreshold.divn(10);
+    mintSplTokenTo(
+      svm,
+      user,
+      quoteMint,
+      admin,
+      user.publicKey,
+      buyAmount.toNumber()
+    );
+
+    // create pool
+    let virtualPool = await createPoolWithSplToken(svm, program, {
+      poolCreator,
+      payer: operator,
+      quoteMint,
+      config,
+      instructionParams: {
+        name: "test token spl",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+    let virtualPoolState = getVirtualPool(svm, program, virtualPool);
+
+    // buys do not need the config extension
+    await swap2(svm, program, {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: quoteMint,
+      outputTokenMint: virtualPoolState.baseMint,
+      amount0: buyAmount,
+      amount1: new BN(0),
+      referralTokenAccount: null,
+      swapMode: SwapMode.ExactIn,
+    });
+
+    const userBaseTokenAccount = getAssociatedTokenAddressSync(
+      virtualPoolState.baseMint,
+      user.publicKey
+    );
+    const baseBalance = new BN(
+      getTokenAccount(svm, userBaseTokenAccount).amount.toString()
+    );
+
+    const sellParams: SwapParams2 = {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: virtualPoolState.baseMint,
+      outputTokenMint: quoteMint,
+      amount0: baseBalance.divn(2),
+      amount1: new BN(0),
+      referralTokenAccount: null,
+      swapMode: SwapMode.ExactIn,
+    };
+
+    // sells must provide the config extension
+    await expectThrowsAsync(async () => {
+      await swap2(svm, program, sellParams);
+    }, getDbcProgramErrorCodeHexString("MissingConfigExtension"));
+
+    virtualPoolState = getVirtualPool(svm, program, virtualPool);
+    const preQuoteReserve = virtualPoolState.quoteReserve;
+    const preQuoteFee = virtualPoolState.partnerQuoteFee.add(
+      virtualPoolState.protocolQuoteFee
+    );
+
+    await swap2(svm, program, { ...sellParams, configExtension });
+
+    virtualPoolState = getVirtualPool(svm, program, virtualPool);
+    const quoteOutIncludingFee = preQuoteReserve.sub(
+      virtualPoolState.quoteReserve
+    );
+    const quoteFee = virtualPoolState.partnerQuoteFee
+      .add(virtualPoolState.protocolQuoteFee)
+      .sub(preQuoteFee);
+
+    // fee is 10% of the quote leaving the curve, allowing for rounding
+    expect(quoteFee.muln(10).sub(quoteOutIncludingFee).abs().lten(10)).to.be
+      .true;
+  });
+});
@@ -0,0 +1,183 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  ConfigParameters,
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+  swapBatch,
+  SwapMode,
+} from "./instructions";
+import {
+  createVirtualCurveProgram,
+  designCurve,
+  expectThrowsAsync,
+  generateAndFund,
+  getDbcProgramErrorCodeHexString,
+  getTokenAccount,
+  startSvm,
+} from "./utils";
+import { getVirtualPool } from "./utils/fetcher";
+import { VirtualCurveProgram } from "./utils/types";
+
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { createToken, mintSplTokenTo } from "./utils/token";
+
+describe("Swap batch", () => {
+  let svm: LiteSVM;
+  let admin: Keypair;
+  let operator: Keypair;
+  let partner: Keypair;
+  let user: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+
+  before(async () => {
+    svm = startSvm();
+    admin = generateAndFund(svm);
+    operator = generateAndFund(svm);
+    partner = generateAndFund(svm);
+    user = generateAndFund(svm);
+    poolCreator = generateAndFund(svm);
+    program = createVirtualCurveProgram();
+  });
+
+  it("Swap on multiple pools in one instruction", async () => {
+    let totalTokenSupply = 1_000_000_000; // 1 billion
+    let percentageSupplyOnMigration = 10; // 10%;
+    let migrationQuoteThreshold = 300; // 300 sol
+    let tokenBaseDecimal = 6;
+    let tokenQuoteDecimal = 9;
+    let migrationOption = 0; // damm v1
+    let lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    let collectFeeMode = 0;
+    let quoteMint = createToken(svm, admin, admin.publicKey, tokenQuoteDecimal);
+    let instructionParams = designCurve(
+      totalTokenSupply,
+      percentageSupplyOnMigration,
+      migrationQuoteThreshold,
+      migrationOption,
+      tokenBaseDecimal,
+      tokenQuoteDecimal,
+      0,
+      collectFeeMode,
+      lockedVesting,
+      {
+        feePercentage: 0,
+        creatorFeePercentage: 0,
+      }
+    );
+
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    let config = await createConfig(svm, program, params);
+    let swapAmount = instructionParams.migrationQuoteThreshold.divn(10);
+
+    mintSplTokenTo(
+      svm,
+      user,
+      quoteMint,
+      admin,
+      user.publicKey,
+      swapAmount.muln(3).toNumber()
+    );
+
+    const pools: PublicKey[] = [];
+    for (let i = 0; i < 3; i++) {
+      pools.push(
+        await createPoolWithSplToken(svm, program, {
+          poolCreator,
+          payer: operator,
+          quoteMint,
+          config,
+          instructionParams: {
+            name: "test token spl",
+            symbol: "TEST",
+            uri: "abc.com",
+          },
+        })
+      );
+    }
+
+    // a pool can only be swapped once in a batch
+    await expectThrowsAsync(async () => {
+      await swapBatch(svm, program, {
+        payer: user,
+        quoteMint,
+        legs: [pools[0], pools[0]].map((pool) => ({
+          config,
+          pool,
+          tradeDirection: 1, // quote to base
+          amount0: swapAmount,
+          amount1: new BN(0),
+          swapMode: SwapMode.ExactIn,
+        })),
+      });
+    }, getDbcProgramErrorCodeHexString("InvalidSwapBatchLegs"));
+
+    await swapBatch(svm, program, {
+      payer: user,
+      quoteMint,
+      legs: pools.map((pool) => ({
+        config,
+        pool,
+        tradeDirection: 1, // quote to base
+        amount0: swapAmount,
+        amount1: new BN(0),
+        swapMode: SwapMode.ExactIn,
+      })),
+    });
+
+    for (const pool of pools) {
+      const virtualPoolState = getVirtualPool(svm, program, pool);
+      const quoteVaultBalance = getTokenAccount(
+        svm,
+        virtualPoolState.quoteVault
+      ).amount;
+      expect(quoteVaultBalance.toString()).eq(swapAmount.toString());
+    }
+
+    // sell back half of the base token received from the first pool
+    const firstPoolState = getVirtualPool(svm, program, pools[0]);
+    const baseTokenAccount = getAssociatedTokenAddressSync(
+      firstPoolState.baseMint,
+      user.publicKey
+    );
+    const baseBalance = new BN(
+      getTokenAccount(svm, baseTokenAccount).amount.toString()
+    );
+    await swapBatch(svm, program, {
+      payer: user,
+      quoteMint,
+      legs: [
+        {
+          config,
+          pool: pools[0],
+          tradeDirection: 0, // base to quote
+          amount0: baseBalance.divn(2),
+          amount1: new BN(0),
+          swapMode: SwapMode.ExactIn,
+        },
+      ],
+    });
+
+    const postBaseBalance = new BN(
+      getTokenAccount(svm, baseTokenAccount).amount.toString()
+    );
+    expect(postBaseBalance.eq(baseBalance.sub(baseBalance.divn(2)))).to.be
+      .true;
+  });
+});
@@ -0,0 +1,162 @@
+import { Keypair } from "@solana/web3.js";
+import {
+  ConfigParameters,
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+  swap2,
+  SwapMode,
+  SwapParams2,
+} from "./instructions";
+import {
+  createVirtualCurveProgram,
+  deriveSwapHookAddress,
+  designCurve,
+  expectThrowsAsync,
+  generateAndFund,
+  getDbcProgramErrorCodeHexString,
+  JUPITER_V6_PROGRAM_ID,
+  startSvm,
+} from "./utils";
+import { getVirtualPool } from "./utils/fetcher";
+import { VirtualCurveProgram } from "./utils/types";
+
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { createToken, mintSplTokenTo } from "./utils/token";
+
+describe("Swap hook", () => {
+  let svm: LiteSVM;
+  let admin: Keypair;
+  let operator: Keypair;
+  let partner: Keypair;
+  let user: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+
+  before(async () => {
+    svm = startSvm();
+    admin = generateAndFund(svm);
+    operator = generateAndFund(svm);
+    partner = generateAndFund(svm);
+    user = generateAndFund(svm);
+    poolCreator = generateAndFund(svm);
+    program = createVirtualCurveProgram();
+  });
+
+  it("Swap requires the registered hook", async () => {
+    let totalTokenSupply = 1_000_000_000; // 1 billion
+    let percentageSupplyOnMigration = 10; // 10%;
+    let migrationQuoteThreshold = 300; // 300 sol
+    let tokenBaseDecimal = 6;
+    let tokenQuoteDecimal = 9;
+    let migrationOption = 0; // damm v1
+    let lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    let collectFeeMode = 0;
+    let quoteMint = createToken(svm, admin, admin.publicKey, tokenQuoteDecimal);
+    let instructionParams = designCurve(
+      totalTokenSupply,
+      percentageSupplyOnMigration,
+      migrationQuoteThreshold,
+      migrationOption,
+      tokenBaseDecimal,
+      tokenQuoteDecimal,
+      0,
+      collectFeeMode,
+      lockedVesting,
+      {
+        feePercentage: 0,
+        creatorFeePercentage: 0,
+      }
+    );
+
+    // the hook program can't be the program itself
+    await expectThrowsAsync(async () => {
+      await createConfig(svm, program, {
+        payer: partner,
+        leftoverReceiver: partner.publicKey,
+        feeClaimer: partner.publicKey,
+        quoteMint,
+        instructionParams,
+        hookProgram: program.programId,
+      });
+    }, getDbcProgramErrorCodeHexString("InvalidSwapHook"));
+
+    // any executable program works for registration, the hook interface is only checked on swap
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+      hookProgram: JUPITER_V6_PROGRAM_ID,
+    };
+    let config = await createConfig(svm, program, params);
+    const swapHook = deriveSwapHookAddress(config);
+    let swapAmount = instructionParams.migrationQuoteThreshold.divn(10);
+
+    mintSplTokenTo(
+      svm,
+      user,
+      quoteMint,
+      admin,
+      user.publicKey,
+      swapAmount.muln(2).toNumber()
+    );
+
+    let virtualPool = await createPoolWithSplToken(svm, program, {
+      poolCreator,
+      payer: operator,
+      quoteMint,
+      config,
+      instructionParams: {
+        name: "test token spl",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+    let virtualPoolState = getVirtualPool(svm, program, virtualPool);
+
+    const swapParams: SwapParams2 = {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: quoteMint,
+      outputTokenMint: virtualPoolState.baseMint,
+      amount0: swapAmount,
+      amount1: new BN(0),
+      referralTokenAccount: null,
+      swapMode: SwapMode.ExactIn,
+    };
+
+    // the hook can't be skipped
+    await expectThrowsAsync(async () => {
+      await swap2(svm, program, swapParams);
+    }, getDbcProgramErrorCodeHexString("MissingSwapHook"));
+
+    swapParams.swapHook = swapHook;
+    await expectThrowsAsync(async () => {
+      await swap2(svm, program, swapParams);
+    }, getDbcProgramErrorCodeHexString("MissingSwapHook"));
+
+    // the hook program rejects the unknown instruction, so the swap is reverted
+    swapParams.hookProgram = JUPITER_V6_PROGRAM_ID;
+    let failed = false;
+    try {
+      await swap2(svm, program, swapParams);
+    } catch {
+      failed = true;
+    }
+    expect(failed).to.be.true;
+
+    virtualPoolState = getVirtualPool(svm, program, virtualPool);
+    expect(virtualPoolState.quoteReserve.isZero()).to.be.true;
+  });
+});
@@ -1,4 +1,4 @@
-import { Keypair } from "@solana/web3.js";
+import { Keypair, Transaction } from "@solana/web3.js";
 import {
   ConfigParameters,
   createConfig,
@@ -11,17 +11,25 @@ import {
 import {
   createVirtualCurveProgram,
   designCurve,
+  expectThrowsAsync,
   FEE_DENOMINATOR,
   generateAndFund,
+  getBalance,
+  getCurrentSlot,
+  getDbcProgramErrorCodeHexString,
   getTokenAccount,
+  sendTransactionMaybeThrow,
   startSvm,
   U64_MAX,
+  warpSlotBy,
 } from "./utils";
 import { getVirtualPool } from "./utils/fetcher";
 import { VirtualCurveProgram } from "./utils/types";
 
 import {
+  createAssociatedTokenAccountInstruction,
   getAssociatedTokenAddressSync,
+  NATIVE_MINT,
   unpackAccount,
 } from "@solana/spl-token";
 import { BN } from "bn.js";
@@ -561,4 +569,255 @@ describe("Swap V2", () => {
     ).amount;
     expect(new BN(userOutTokenBal.toString()).eq(outAmount)).to.be.true;
   });
+
+  it("Swap with expiry point", async () => {
+    let totalTokenSupply = 1_000_000_000; // 1 billion
+    let percentageSupplyOnMigration = 10; // 10%;
+    let migrationQuoteThreshold = 300; // 300 sol
+    let tokenBaseDecimal = 6;
+    let tokenQuoteDecimal = 9;
+    let migrationOption = 0; // damm v1
+    let lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    let collectFeeMode = 0;
+    let quoteMint = createToken(svm, admin, admin.publicKey, tokenQuoteDecimal);
+    let instructionParams = designCurve(
+      totalTokenSupply,
+      percentageSupplyOnMigration,
+      migrationQuoteThreshold,
+      migrationOption,
+      tokenBaseDecimal,
+      tokenQuoteDecimal,
+      0,
+      collectFeeMode,
+      lockedVesting,
+      {
+        feePercentage: 0,
+        creatorFeePercentage: 0,
+      }
+    );
+
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    let config = await createConfig(svm, program, params);
+    let swapAmount = instructionParams.migrationQuoteThreshold.divn(10);
+
+    mintSplTokenTo(
+      svm,
+      user,
+      quoteMint,
+      admin,
+      user.publicKey,
+      swapAmount.toNumber()
+    );
+
+    // create pool
+    let virtualPool = await createPoolWithSplToken(svm, program, {
+      poolCreator,
+      payer: operator,
+      quoteMint,
+      config,
+      instructionParams: {
+        name: "test token spl",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+    let virtualPoolState = getVirtualPool(svm, program, virtualPool);
+
+    // activation type is slot
+    const expiryPoint = getCurrentSlot(svm);
+    warpSlotBy(svm, expiryPoint.addn(1));
+
+    const swapParams: SwapParams2 = {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: quoteMint,
+      outputTokenMint: virtualPoolState.baseMint,
+      amount0: swapAmount,
+      amount1: new BN(0),
+      expiryPoint,
+      referralTokenAccount: null,
+      swapMode: SwapMode.ExactIn,
+    };
+
+    const errorCode = getDbcProgramErrorCodeHexString("SwapExpired");
+    await expectThrowsAsync(async () => {
+      await swap2(svm, program, swapParams);
+    }, errorCode);
+
+    swapParams.expiryPoint = getCurrentSlot(svm).addn(10);
+    await swap2(svm, program, swapParams);
+
+    virtualPoolState = getVirtualPool(svm, program, virtualPool);
+    expect(virtualPoolState.quoteReserve.gtn(0)).to.be.true;
+  });
+
+  it("Swap with native SOL", async () => {
+    let totalTokenSupply = 1_000_000_000; // 1 billion
+    let percentageSupplyOnMigration = 10; // 10%;
+    let migrationQuoteThreshold = 300; // 300 sol
+    let tokenBaseDecimal = 6;
+    let tokenQuoteDecimal = 9;
+    let migrationOption = 0; // damm v1
+    let lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    let collectFeeMode = 0;
+    let instructionParams = designCurve(
+      totalTokenSupply,
+      percentageSupplyOnMigration,
+      migrationQuoteThreshold,
+      migrationOption,
+      tokenBaseDecimal,
+      tokenQuoteDecimal,
+      0,
+      collectFeeMode,
+      lockedVesting,
+      {
+        feePercentage: 0,
+        creatorFeePercentage: 0,
+      }
+    );
+
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint: NATIVE_MINT,
+      instructionParams,
+    };
+    let config = await createConfig(svm, program, params);
+    let swapAmount = new BN(1_000_000_000); // 1 sol
+
+    // create pool
+    let virtualPool = await createPoolWithSplToken(svm, program, {
+      poolCreator,
+      payer: operator,
+      quoteMint: NATIVE_MINT,
+      config,
+      instructionParams: {
+        name: "test token spl",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+    let virtualPoolState = getVirtualPool(svm, program, virtualPool);
+    const userQuoteTokenAccount = getAssociatedTokenAddressSync(
+      NATIVE_MINT,
+      user.publicKey
+    );
+
+    // buy with lamports
+    const preQuoteVaultBalance = getTokenAccount(
+      svm,
+      virtualPoolState.quoteVault
+    ).amount;
+    const preBuyBalance = getBalance(svm, user.publicKey);
+    await swap2(svm, program, {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: NATIVE_MINT,
+      outputTokenMint: virtualPoolState.baseMint,
+      amount0: swapAmount,
+      amount1: new BN(0),
+      useNativeSol: true,
+      referralTokenAccount: null,
+      swapMode: SwapMode.ExactIn,
+    });
+    const postQuoteVaultBalance = getTokenAccount(
+      svm,
+      virtualPoolState.quoteVault
+    ).amount;
+    expect((postQuoteVaultBalance - preQuoteVaultBalance).toString()).eq(
+      swapAmount.toString()
+    );
+    expect(
+      Number(preBuyBalance) - Number(getBalance(svm, user.publicKey))
+    ).gte(swapAmount.toNumber());
+    // quote token account is closed after the swap
+    expect(svm.getAccount(userQuoteTokenAccount)).to.be.null;
+
+    // sell for lamports
+    const userBaseTokenAccount = getAssociatedTokenAddressSync(
+      virtualPoolState.baseMint,
+      user.publicKey
+    );
+    const baseBalance = getTokenAccount(svm, userBaseTokenAccount).amount;
+    const preSellBalance = getBalance(svm, user.publicKey);
+    await swap2(svm, program, {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: virtualPoolState.baseMint,
+      outputTokenMint: NATIVE_MINT,
+      amount0: new BN(baseBalance.toString()),
+      amount1: new BN(0),
+      useNativeSol: true,
+      referralTokenAccount: null,
+      swapMode: SwapMode.ExactIn,
+    });
+    expect(Number(getBalance(svm, user.publicKey))).gt(Number(preSellBalance));
+    expect(svm.getAccount(userQuoteTokenAccount)).to.be.null;
+
+    // an existing native SOL token account is kept for buys
+    sendTransactionMaybeThrow(
+      svm,
+      new Transaction().add(
+        createAssociatedTokenAccountInstruction(
+          user.publicKey,
+          userQuoteTokenAccount,
+          user.publicKey,
+          NATIVE_MINT
+        )
+      ),
+      [user]
+    );
+    await swap2(svm, program, {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: NATIVE_MINT,
+      outputTokenMint: virtualPoolState.baseMint,
+      amount0: swapAmount,
+      amount1: new BN(0),
+      useNativeSol: true,
+      referralTokenAccount: null,
+      swapMode: SwapMode.ExactIn,
+    });
+    expect(svm.getAccount(userQuoteTokenAccount)).not.to.be.null;
+
+    // sells can't leave the quote output in the existing account
+    const newBaseBalance = getTokenAccount(svm, userBaseTokenAccount).amount;
+    await expectThrowsAsync(async () => {
+      await swap2(svm, program, {
+        config,
+        payer: user,
+        pool: virtualPool,
+        inputTokenMint: virtualPoolState.baseMint,
+        outputTokenMint: NATIVE_MINT,
+        amount0: new BN(newBaseBalance.toString()),
+        amount1: new BN(0),
+        useNativeSol: true,
+        referralTokenAccount: null,
+        swapMode: SwapMode.ExactIn,
+      });
+    }, getDbcProgramErrorCodeHexString("NativeSolTokenAccountExists"));
+  });
 });
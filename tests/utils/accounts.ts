@@ -146,6 +146,103 @@ export function deriveOperatorAddress(operator: PublicKey): PublicKey {
   )[0];
 }
 
+export function deriveWalletBuyTrackerAddress(
+  pool: PublicKey,
+  owner: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("wallet_buy_tracker"), pool.toBuffer(), owner.toBuffer()],
+    DYNAMIC_BONDING_CURVE_PROGRAM_ID
+  )[0];
+}
+
+export function derivePresaleAllowlistAddress(config: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("presale_allowlist"), config.toBuffer()],
+    DYNAMIC_BONDING_CURVE_PROGRAM_ID
+  )[0];
+}
+
+export function deriveConfigExtensionAddress(config: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("config_extension"), config.toBuffer()],
+    DYNAMIC_BONDING_CURVE_PROGRAM_ID
+  )[0];
+}
+
+export function deriveSwapHookAddress(config: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("swap_hook"), config.toBuffer()],
+    DYNAMIC_BONDING_CURVE_PROGRAM_ID
+  )[0];
+}
+
+export function deriveReferrerAddress(owner: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("referrer"), owner.toBuffer()],
+    DYNAMIC_BONDING_CURVE_PROGRAM_ID
+  )[0];
+}
+
+export function deriveVolumeFeeTiersAddress(config: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("volume_fee_tiers"), config.toBuffer()],
+    DYNAMIC_BONDING_CURVE_PROGRAM_ID
+  )[0];
+}
+
+export function deriveWalletVolumeTrackerAddress(
+  config: PublicKey,
+  owner: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("wallet_volume_tracker"),
+      config.toBuffer(),
+      owner.toBuffer(),
+    ],
+    DYNAMIC_BONDING_CURVE_PROGRAM_ID
+  )[0];
+}
+
+export function derivePoolObservationsAddress(pool: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("pool_observations"), pool.toBuffer()],
+    DYNAMIC_BONDING_CURVE_PROGRAM_ID
+  )[0];
+}
+
+export function deriveCreatorFeeShareAddress(pool: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("creator_fee_share"), pool.toBuffer()],
+    DYNAMIC_BONDING_CURVE_PROGRAM_ID
+  )[0];
+}
+
+export function deriveFeeClaimerTransferAddress(config: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("fee_claimer_transfer"), config.toBuffer()],
+    DYNAMIC_BONDING_CURVE_PROGRAM_ID
+  )[0];
+}
+
+export function deriveAllowedCreatorAddress(
+  config: PublicKey,
+  creator: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("allowed_creator"), config.toBuffer(), creator.toBuffer()],
+    DYNAMIC_BONDING_CURVE_PROGRAM_ID
+  )[0];
+}
+
+export function deriveProtocolConfigAddress(partner: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("protocol_config"), partner.toBuffer()],
+    DYNAMIC_BONDING_CURVE_PROGRAM_ID
+  )[0];
+}
+
 export const getVaultPdas = (tokenMint: PublicKey) => {
   const [vault, _vaultBump] = PublicKey.findProgramAddressSync(
     [Buffer.from("vault"), tokenMint.toBuffer(), VAULT_BASE_KEY.toBuffer()],
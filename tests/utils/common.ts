@@ -278,6 +278,12 @@ export function warpSlotBy(svm: LiteSVM, slots: BN) {
   svm.warpToSlot(BigInt(slots.toString()));
 }
 
+export function warpTimestampBy(svm: LiteSVM, seconds: number) {
+  const clock = svm.getClock();
+  clock.unixTimestamp = clock.unixTimestamp + BigInt(seconds);
+  svm.setClock(clock);
+}
+
 export const SET_COMPUTE_UNIT_LIMIT_IX =
   web3.ComputeBudgetProgram.setComputeUnitLimit({
     units: 1_400_000,
@@ -17,8 +17,19 @@ import {
   PartnerMetadata,
   Pool,
   PoolConfig,
+  PoolConfigExtension,
+  ProtocolConfig,
+  Referrer,
+  PresaleAllowlist,
+  SwapHook,
   VirtualCurveProgram,
   VirtualPoolMetadata,
+  VolumeFeeTiers,
+  WalletBuyTracker,
+  WalletVolumeTracker,
+  CreatorFeeShare,
+  FeeClaimerTransfer,
+  PoolObservations,
 } from "./types";
 
 export function getVirtualPool(
@@ -78,6 +89,132 @@ export function getClaimFeeOperator(
   );
 }
 
+export function getWalletBuyTracker(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  walletBuyTracker: PublicKey
+): WalletBuyTracker {
+  const account = svm.getAccount(walletBuyTracker);
+  return program.coder.accounts.decode(
+    "walletBuyTracker",
+    Buffer.from(account.data)
+  );
+}
+
+export function getPresaleAllowlist(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  presaleAllowlist: PublicKey
+): PresaleAllowlist {
+  const account = svm.getAccount(presaleAllowlist);
+  return program.coder.accounts.decode(
+    "presaleAllowlist",
+    Buffer.from(account.data)
+  );
+}
+
+export function getConfigExtension(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  configExtension: PublicKey
+): PoolConfigExtension {
+  const account = svm.getAccount(configExtension);
+  return program.coder.accounts.decode(
+    "poolConfigExtension",
+    Buffer.from(account.data)
+  );
+}
+
+export function getProtocolConfig(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  protocolConfig: PublicKey
+): ProtocolConfig {
+  const account = svm.getAccount(protocolConfig);
+  return program.coder.accounts.decode(
+    "protocolConfig",
+    Buffer.from(account.data)
+  );
+}
+
+export function getReferrer(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  referrer: PublicKey
+): Referrer {
+  const account = svm.getAccount(referrer);
+  return program.coder.accounts.decode("referrer", Buffer.from(account.data));
+}
+
+export function getVolumeFeeTiers(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  volumeFeeTiers: PublicKey
+): VolumeFeeTiers {
+  const account = svm.getAccount(volumeFeeTiers);
+  return program.coder.accounts.decode(
+    "volumeFeeTiers",
+    Buffer.from(account.data)
+  );
+}
+
+export function getWalletVolumeTracker(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  walletVolumeTracker: PublicKey
+): WalletVolumeTracker {
+  const account = svm.getAccount(walletVolumeTracker);
+  return program.coder.accounts.decode(
+    "walletVolumeTracker",
+    Buffer.from(account.data)
+  );
+}
+
+export function getCreatorFeeShare(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  creatorFeeShare: PublicKey
+): CreatorFeeShare {
+  const account = svm.getAccount(creatorFeeShare);
+  return program.coder.accounts.decode(
+    "creatorFeeShare",
+    Buffer.from(account.data)
+  );
+}
+
+export function getFeeClaimerTransfer(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  feeClaimerTransfer: PublicKey
+): FeeClaimerTransfer {
+  const account = svm.getAccount(feeClaimerTransfer);
+  return program.coder.accounts.decode(
+    "feeClaimerTransfer",
+    Buffer.from(account.data)
+  );
+}
+
+export function getPoolObservations(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  poolObservations: PublicKey
+): PoolObservations {
+  const account = svm.getAccount(poolObservations);
+  return program.coder.accounts.decode(
+    "poolObservations",
+    Buffer.from(account.data)
+  );
+}
+
+export function getSwapHook(
+  svm: LiteSVM,
+  program: VirtualCurveProgram,
+  swapHook: PublicKey
+): SwapHook {
+  const account = svm.getAccount(swapHook);
+  return program.coder.accounts.decode("swapHook", Buffer.from(account.data));
+}
+
 export function getMeteoraDammMigrationMetadata(
   svm: LiteSVM,
   program: VirtualCurveProgram,
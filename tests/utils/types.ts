@@ -15,6 +15,24 @@ export type VirtualPoolMetadata =
   IdlAccounts<DynamicBondingCurve>["virtualPoolMetadata"];
 export type ClaimFeeOperator =
   IdlAccounts<DynamicBondingCurve>["claimFeeOperator"];
+export type WalletBuyTracker =
+  IdlAccounts<DynamicBondingCurve>["walletBuyTracker"];
+export type PresaleAllowlist =
+  IdlAccounts<DynamicBondingCurve>["presaleAllowlist"];
+export type SwapHook = IdlAccounts<DynamicBondingCurve>["swapHook"];
+export type PoolConfigExtension =
+  IdlAccounts<DynamicBondingCurve>["poolConfigExtension"];
+export type ProtocolConfig = IdlAccounts<DynamicBondingCurve>["protocolConfig"];
+export type Referrer = IdlAccounts<DynamicBondingCurve>["referrer"];
+export type VolumeFeeTiers = IdlAccounts<DynamicBondingCurve>["volumeFeeTiers"];
+export type WalletVolumeTracker =
+  IdlAccounts<DynamicBondingCurve>["walletVolumeTracker"];
+export type CreatorFeeShare =
+  IdlAccounts<DynamicBondingCurve>["creatorFeeShare"];
+export type FeeClaimerTransfer =
+  IdlAccounts<DynamicBondingCurve>["feeClaimerTransfer"];
+export type PoolObservations =
+  IdlAccounts<DynamicBondingCurve>["poolObservations"];
 export type MeteoraDammMigrationMetadata =
   IdlAccounts<DynamicBondingCurve>["meteoraDammMigrationMetadata"];
 export type LockEscrow = IdlAccounts<DynamicAmm>["lockEscrow"];
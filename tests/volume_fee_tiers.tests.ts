@@ -0,0 +1,253 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  ConfigParameters,
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+  createVolumeFeeTiers,
+  closeVolumeFeeTiers,
+  createWalletVolumeTracker,
+  swap2,
+  SwapMode,
+  updateVolumeFeeTiers,
+} from "./instructions";
+import {
+  createVirtualCurveProgram,
+  designCurve,
+  expectThrowsAsync,
+  generateAndFund,
+  getDbcProgramErrorCodeHexString,
+  startSvm,
+} from "./utils";
+import {
+  getVirtualPool,
+  getVolumeFeeTiers,
+  getWalletVolumeTracker,
+} from "./utils/fetcher";
+import { VirtualCurveProgram } from "./utils/types";
+
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { createToken, mintSplTokenTo } from "./utils/token";
+
+describe("Volume fee tiers", () => {
+  let svm: LiteSVM;
+  let admin: Keypair;
+  let operator: Keypair;
+  let partner: Keypair;
+  let user: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let quoteMint: PublicKey;
+  let config: PublicKey;
+  let instructionParams: ConfigParameters;
+
+  before(async () => {
+    svm = startSvm();
+    admin = generateAndFund(svm);
+    operator = generateAndFund(svm);
+    partner = generateAndFund(svm);
+    user = generateAndFund(svm);
+    poolCreator = generateAndFund(svm);
+    program = createVirtualCurveProgram();
+
+    let lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    quoteMint = createToken(svm, admin, admin.publicKey, 9);
+    instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      0,
+      6,
+      9,
+      0,
+      0,
+      lockedVesting,
+      {
+        feePercentage: 0,
+        creatorFeePercentage: 0,
+      }
+    );
+    // 10% flat base fee
+    instructionParams.poolFees.baseFee = {
+      cliffFeeNumerator: new BN(100_000_000),
+      firstFactor: 0,
+      secondFactor: new BN(0),
+      thirdFactor: new BN(0),
+      baseFeeMode: 0,
+    };
+
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    config = await createConfig(svm, program, params);
+  });
+
+  it("Reject invalid tiers", async () => {
+    await expectThrowsAsync(async () => {
+      await createVolumeFeeTiers(svm, program, {
+        config,
+        tiers: [
+          { minVolume: new BN(1_000), feeDiscountBps: 5_000 },
+          { minVolume: new BN(500), feeDiscountBps: 6_000 },
+        ],
+        feeClaimer: partner,
+        payer: partner,
+      });
+    }, getDbcProgramErrorCodeHexString("InvalidVolumeFeeTiers"));
+  });
+
+  it("Update and close volume fee tiers", async () => {
+    const otherConfig = await createConfig(svm, program, {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    });
+    const volumeFeeTiers = await createVolumeFeeTiers(svm, program, {
+      config: otherConfig,
+      tiers: [{ minVolume: new BN(1_000), feeDiscountBps: 1_000 }],
+      feeClaimer: partner,
+      payer: partner,
+    });
+
+    await expectThrowsAsync(async () => {
+      await updateVolumeFeeTiers(svm, program, {
+        config: otherConfig,
+        tiers: [{ minVolume: new BN(1_000), feeDiscountBps: 1_000 }],
+        feeClaimer: user,
+      });
+    }, getDbcProgramErrorCodeHexString("Unauthorized"));
+
+    await updateVolumeFeeTiers(svm, program, {
+      config: otherConfig,
+      tiers: [
+        { minVolume: new BN(1_000), feeDiscountBps: 2_000 },
+        { minVolume: new BN(5_000), feeDiscountBps: 3_000 },
+      ],
+      feeClaimer: partner,
+    });
+    const volumeFeeTiersState = getVolumeFeeTiers(svm, program, volumeFeeTiers);
+    expect(volumeFeeTiersState.tiers[0].feeDiscountBps).eq(2_000);
+    expect(volumeFeeTiersState.tiers[1].minVolume.toNumber()).eq(5_000);
+
+    await closeVolumeFeeTiers(svm, program, {
+      config: otherConfig,
+      feeClaimer: partner,
+    });
+    expect(svm.getAccount(volumeFeeTiers)).null;
+  });
+
+  it("Discount fee of wallets above the volume threshold", async () => {
+    const buyAmount = instructionParams.migrationQuoteThreshold.divn(20);
+    const volumeFeeTiers = await createVolumeFeeTiers(svm, program, {
+      config,
+      // 50% discount once the wallet bought the amount once
+      tiers: [{ minVolume: buyAmount, feeDiscountBps: 5_000 }],
+      feeClaimer: partner,
+      payer: partner,
+    });
+    const volumeFeeTiersState = getVolumeFeeTiers(svm, program, volumeFeeTiers);
+    expect(volumeFeeTiersState.config.toString()).eq(config.toString());
+    expect(volumeFeeTiersState.tiers[0].feeDiscountBps).eq(5_000);
+
+    const walletVolumeTracker = await createWalletVolumeTracker(
+      svm,
+      program,
+      config,
+      user
+    );
+
+    mintSplTokenTo(
+      svm,
+      user,
+      quoteMint,
+      admin,
+      user.publicKey,
+      buyAmount.muln(2).toNumber()
+    );
+
+    const virtualPool = await createPoolWithSplToken(svm, program, {
+      poolCreator,
+      payer: operator,
+      quoteMint,
+      config,
+      instructionParams: {
+        name: "test token spl",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+    let virtualPoolState = getVirtualPool(svm, program, virtualPool);
+
+    const getQuoteFees = () => {
+      const state = getVirtualPool(svm, program, virtualPool);
+      return {
+        total: state.partnerQuoteFee
+          .add(state.protocolQuoteFee)
+          .add(state.creatorQuoteFee),
+        protocol: state.protocolQuoteFee,
+      };
+    };
+
+    const buy = async () => {
+      const preQuoteFees = getQuoteFees();
+      await swap2(svm, program, {
+        config,
+        payer: user,
+        pool: virtualPool,
+        inputTokenMint: quoteMint,
+        outputTokenMint: virtualPoolState.baseMint,
+        amount0: buyAmount,
+        amount1: new BN(0),
+        referralTokenAccount: null,
+        walletVolumeTracker,
+        volumeFeeTiers,
+        swapMode: SwapMode.ExactIn,
+      });
+      const postQuoteFees = getQuoteFees();
+      return {
+        total: postQuoteFees.total.sub(preQuoteFees.total),
+        protocol: postQuoteFees.protocol.sub(preQuoteFees.protocol),
+      };
+    };
+
+    // first buy pays the full fee, volume is below the threshold
+    const fullFee = await buy();
+    expect(
+      getWalletVolumeTracker(
+        svm,
+        program,
+        walletVolumeTracker
+      ).totalVolume.toString()
+    ).eq(buyAmount.toString());
+
+    // second buy reaches the tier
+    const discountedFee = await buy();
+    expect(
+      getWalletVolumeTracker(
+        svm,
+        program,
+        walletVolumeTracker
+      ).totalVolume.toString()
+    ).eq(buyAmount.muln(2).toString());
+
+    // the discount applies to the partner and creator share only, with the
+    // 20% protocol share the total fee is discounted by 40%
+    expect(fullFee.total.eq(buyAmount.divn(10))).to.be.true;
+    expect(discountedFee.total.eq(buyAmount.muln(6).divn(100))).to.be.true;
+    expect(discountedFee.protocol.eq(fullFee.protocol)).to.be.true;
+  });
+});
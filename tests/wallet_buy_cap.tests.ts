@@ -0,0 +1,178 @@
+import { Keypair } from "@solana/web3.js";
+import {
+  ConfigParameters,
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+  createWalletBuyTracker,
+  swap2,
+  SwapMode,
+  SwapParams2,
+} from "./instructions";
+import {
+  createVirtualCurveProgram,
+  deriveConfigExtensionAddress,
+  designCurve,
+  expectThrowsAsync,
+  generateAndFund,
+  getCurrentSlot,
+  getDbcProgramErrorCodeHexString,
+  startSvm,
+  warpSlotBy,
+} from "./utils";
+import { getVirtualPool, getWalletBuyTracker } from "./utils/fetcher";
+import { VirtualCurveProgram } from "./utils/types";
+
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { createToken, mintSplTokenTo } from "./utils/token";
+
+describe("Wallet buy cap", () => {
+  let svm: LiteSVM;
+  let admin: Keypair;
+  let operator: Keypair;
+  let partner: Keypair;
+  let user: Keypair;
+  let otherUser: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+
+  before(async () => {
+    svm = startSvm();
+    admin = generateAndFund(svm);
+    operator = generateAndFund(svm);
+    partner = generateAndFund(svm);
+    user = generateAndFund(svm);
+    otherUser = generateAndFund(svm);
+    poolCreator = generateAndFund(svm);
+    program = createVirtualCurveProgram();
+  });
+
+  it("Enforce wallet buy cap during launch window", async () => {
+    let totalTokenSupply = 1_000_000_000; // 1 billion
+    let percentageSupplyOnMigration = 10; // 10%;
+    let migrationQuoteThreshold = 300; // 300 sol
+    let tokenBaseDecimal = 6;
+    let tokenQuoteDecimal = 9;
+    let migrationOption = 0; // damm v1
+    let lockedVesting = {
+      amountPerPeriod: new BN(0),
+      cliffDurationFromMigrationTime: new BN(0),
+      frequency: new BN(0),
+      numberOfPeriod: new BN(0),
+      cliffUnlockAmount: new BN(0),
+    };
+    let collectFeeMode = 0;
+    let quoteMint = createToken(svm, admin, admin.publicKey, tokenQuoteDecimal);
+    let instructionParams = designCurve(
+      totalTokenSupply,
+      percentageSupplyOnMigration,
+      migrationQuoteThreshold,
+      migrationOption,
+      tokenBaseDecimal,
+      tokenQuoteDecimal,
+      0,
+      collectFeeMode,
+      lockedVesting,
+      {
+        feePercentage: 0,
+        creatorFeePercentage: 0,
+      }
+    );
+    const maxBuyAmountPerWallet =
+      instructionParams.migrationQuoteThreshold.divn(10);
+    const buyCapDuration = 1000; // activation type is slot
+    instructionParams.maxBuyAmountPerWallet = maxBuyAmountPerWallet;
+    instructionParams.buyCapDuration = buyCapDuration;
+
+    const params: CreateConfigParams<ConfigParameters> = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    let config = await createConfig(svm, program, params);
+
+    for (const wallet of [user, otherUser]) {
+      mintSplTokenTo(
+        svm,
+        wallet,
+        quoteMint,
+        admin,
+        wallet.publicKey,
+        maxBuyAmountPerWallet.muln(2).toNumber()
+      );
+    }
+
+    // create pool
+    let virtualPool = await createPoolWithSplToken(svm, program, {
+      poolCreator,
+      payer: operator,
+      quoteMint,
+      config,
+      instructionParams: {
+        name: "test token spl",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+    let virtualPoolState = getVirtualPool(svm, program, virtualPool);
+
+    const walletBuyTracker = await createWalletBuyTracker(
+      svm,
+      program,
+      virtualPool,
+      user
+    );
+
+    const swapParams: SwapParams2 = {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: quoteMint,
+      outputTokenMint: virtualPoolState.baseMint,
+      amount0: maxBuyAmountPerWallet,
+      amount1: new BN(0),
+      walletBuyTracker,
+      // the max buy amount per wallet is stored in the config extension
+      configExtension: deriveConfigExtensionAddress(config),
+      referralTokenAccount: null,
+      swapMode: SwapMode.ExactIn,
+    };
+    await swap2(svm, program, swapParams);
+
+    const walletBuyTrackerState = getWalletBuyTracker(
+      svm,
+      program,
+      walletBuyTracker
+    );
+    expect(walletBuyTrackerState.totalBuyAmount.eq(maxBuyAmountPerWallet)).to
+      .be.true;
+
+    // any further buy exceeds the cap
+    swapParams.amount0 = new BN(1);
+    await expectThrowsAsync(async () => {
+      await swap2(svm, program, swapParams);
+    }, getDbcProgramErrorCodeHexString("ExceededWalletBuyCap"));
+
+    // buying without a tracker is rejected during the window
+    const otherSwapParams: SwapParams2 = {
+      ...swapParams,
+      payer: otherUser,
+      walletBuyTracker: undefined,
+    };
+    await expectThrowsAsync(async () => {
+      await swap2(svm, program, otherSwapParams);
+    }, getDbcProgramErrorCodeHexString("MissingWalletBuyTracker"));
+
+    // the cap no longer applies once the window is over
+    warpSlotBy(svm, getCurrentSlot(svm).addn(buyCapDuration + 1));
+    otherSwapParams.amount0 = maxBuyAmountPerWallet.muln(2);
+    await swap2(svm, program, otherSwapParams);
+
+    virtualPoolState = getVirtualPool(svm, program, virtualPool);
+    expect(virtualPoolState.quoteReserve.gt(maxBuyAmountPerWallet)).to.be.true;
+  });
+});